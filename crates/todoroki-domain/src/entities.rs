@@ -1,9 +1,13 @@
+pub mod audit;
+pub mod change;
 pub mod client;
+pub mod comment;
 pub mod doit;
 pub mod label;
 pub mod todo;
 pub mod user;
 pub mod user_auth;
+pub mod webhook;
 
 #[macro_export]
 macro_rules! value_object {
@@ -0,0 +1,355 @@
+use crate::{
+    entities::{
+        doit::{Doit, DoitPublishment},
+        label::Label,
+        todo::{Todo, TodoPublishment, TodoSchedule},
+        user::{User, UserId, UserRole},
+    },
+    value_object,
+    value_objects::{datetime::DateTime, permission::Permission},
+};
+use getset::Getters;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+// 追記のみで、更新や削除はしない
+#[derive(Debug, Clone, Getters)]
+pub struct AuditEvent {
+    #[getset(get = "pub")]
+    id: AuditEventId,
+    #[getset(get = "pub")]
+    entity: AuditEntity,
+    #[getset(get = "pub")]
+    entity_id: Uuid,
+    #[getset(get = "pub")]
+    action: AuditAction,
+    // 未登録のユーザーによるユーザー登録では None
+    #[getset(get = "pub")]
+    actor_id: Option<UserId>,
+    // 操作を許可した Permission の名前
+    #[getset(get = "pub")]
+    permission: String,
+    // 変更のあったフィールドのみを持つ。作成では before が、削除以外では after が None にならない
+    #[getset(get = "pub")]
+    before: Option<Value>,
+    #[getset(get = "pub")]
+    after: Option<Value>,
+    #[getset(get = "pub")]
+    occurred_at: DateTime,
+}
+
+value_object!(AuditEventId(Uuid));
+
+impl AuditEventId {
+    pub(crate) fn generate() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEntity {
+    Todo,
+    Doit,
+    Label,
+    User,
+}
+
+impl std::fmt::Display for AuditEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Todo => write!(f, "todo"),
+            Self::Doit => write!(f, "doit"),
+            Self::Label => write!(f, "label"),
+            Self::User => write!(f, "user"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    StatusChange,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Create => write!(f, "create"),
+            Self::Update => write!(f, "update"),
+            Self::Delete => write!(f, "delete"),
+            Self::StatusChange => write!(f, "status-change"),
+        }
+    }
+}
+
+// 変更と同じトランザクションで監査ログを記録するために、リポジトリでの変更に渡す
+#[derive(Debug, Clone, Getters)]
+pub struct AuditActor {
+    // 未登録のユーザーによるユーザー登録では None
+    #[getset(get = "pub")]
+    id: Option<UserId>,
+    // 操作を許可した Permission
+    #[getset(get = "pub")]
+    permission: Permission,
+}
+
+impl AuditActor {
+    pub fn new(id: Option<UserId>, permission: Permission) -> Self {
+        Self { id, permission }
+    }
+}
+
+// 監査ログに記録する対象
+pub trait Auditable {
+    const ENTITY: AuditEntity;
+
+    fn audit_id(&self) -> Uuid;
+
+    // NOTE: created_at や updated_at など、変更のたびに変わるだけのフィールドは含めない
+    fn audit_snapshot(&self) -> Value;
+}
+
+impl AuditEvent {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: AuditEventId,
+        entity: AuditEntity,
+        entity_id: Uuid,
+        action: AuditAction,
+        actor_id: Option<UserId>,
+        permission: String,
+        before: Option<Value>,
+        after: Option<Value>,
+        occurred_at: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            entity,
+            entity_id,
+            action,
+            actor_id,
+            permission,
+            before,
+            after,
+            occurred_at,
+        }
+    }
+
+    // 変更前後の差分から作る。変更がなかった場合は None
+    pub fn generate<T: Auditable>(
+        action: AuditAction,
+        actor: &AuditActor,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Option<Self> {
+        let entity_id = before.or(after)?.audit_id();
+
+        let (before, after) = match (
+            before.map(Auditable::audit_snapshot),
+            after.map(Auditable::audit_snapshot),
+        ) {
+            (Some(before), Some(after)) => {
+                let (before, after) = diff(before, after)?;
+                (Some(before), Some(after))
+            }
+            (before, after) => (before, after),
+        };
+
+        Some(Self {
+            id: AuditEventId::generate(),
+            entity: T::ENTITY,
+            entity_id,
+            action,
+            actor_id: actor.id().clone(),
+            permission: actor.permission().to_string(),
+            before,
+            after,
+            occurred_at: DateTime::now(),
+        })
+    }
+}
+
+// 値の異なるフィールドのみを残す
+fn diff(before: Value, after: Value) -> Option<(Value, Value)> {
+    let (Value::Object(before), Value::Object(mut after)) = (before, after) else {
+        return None;
+    };
+
+    let mut before_diff = Map::new();
+    let mut after_diff = Map::new();
+
+    for (key, before_value) in before {
+        let after_value = after.remove(&key).unwrap_or(Value::Null);
+
+        if before_value != after_value {
+            before_diff.insert(key.clone(), before_value);
+            after_diff.insert(key, after_value);
+        }
+    }
+
+    for (key, after_value) in after {
+        before_diff.insert(key.clone(), Value::Null);
+        after_diff.insert(key, after_value);
+    }
+
+    if after_diff.is_empty() {
+        None
+    } else {
+        Some((Value::Object(before_diff), Value::Object(after_diff)))
+    }
+}
+
+fn datetime(value: &DateTime) -> Value {
+    json!(value.clone().value().to_rfc3339())
+}
+
+fn optional_datetime(value: &Option<DateTime>) -> Value {
+    value.as_ref().map(datetime).unwrap_or(Value::Null)
+}
+
+fn schedule(value: &TodoSchedule) -> Value {
+    let timezone = value.timezone().name();
+
+    match value {
+        TodoSchedule::Once(s, e) => json!({
+            "interval": "once",
+            "starts_at": datetime(s),
+            "ends_at": datetime(e),
+        }),
+        TodoSchedule::Daily(s, e, _) => json!({
+            "interval": "daily",
+            "starts_at": datetime(&DateTime::from(s.clone())),
+            "ends_at": datetime(&DateTime::from(e.clone())),
+            "timezone": timezone,
+        }),
+        TodoSchedule::Weekly(s, e, _) => json!({
+            "interval": "weekly",
+            "starts_at": datetime(&DateTime::from(s.clone())),
+            "ends_at": datetime(&DateTime::from(e.clone())),
+            "timezone": timezone,
+        }),
+        TodoSchedule::Monthly(s, e, _) => json!({
+            "interval": "monthly",
+            "starts_at": datetime(&DateTime::from(s.clone())),
+            "ends_at": datetime(&DateTime::from(e.clone())),
+            "timezone": timezone,
+        }),
+        TodoSchedule::Recurrence(s, e, rule, _) => json!({
+            "interval": "recurrence",
+            "starts_at": datetime(s),
+            "ends_at": datetime(e),
+            "rrule": rule.to_string(),
+            "exdates": rule.exdates().iter().map(datetime).collect::<Vec<_>>(),
+            "timezone": timezone,
+        }),
+    }
+}
+
+impl Auditable for Todo {
+    const ENTITY: AuditEntity = AuditEntity::Todo;
+
+    fn audit_id(&self) -> Uuid {
+        self.id().clone().value()
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        let (is_public, alternative_name) = match self.is_public() {
+            TodoPublishment::Public => (true, None),
+            TodoPublishment::Private(name) => (false, name.clone()),
+        };
+
+        json!({
+            "name": self.name().clone().value(),
+            "description": self.description().clone().value(),
+            "is_public": is_public,
+            "alternative_name": alternative_name,
+            "parent_id": self.parent_id().clone().map(|id| id.value()),
+            "label_ids": self.labels().iter().map(|l| l.id().clone().value()).collect::<Vec<_>>(),
+            "blocker_ids": self.blockers().iter().map(|b| b.id().clone().value()).collect::<Vec<_>>(),
+            "schedules": self.schedules().iter().map(schedule).collect::<Vec<_>>(),
+            "status": self.status().to_string(),
+            "completed_occurrences": self
+                .occurrence_completions()
+                .iter()
+                .map(|c| datetime(c.occurrence_start()))
+                .collect::<Vec<_>>(),
+            "started_at": optional_datetime(self.started_at()),
+            "deadlined_at": optional_datetime(self.deadlined_at()),
+            "ended_at": optional_datetime(self.ended_at()),
+            "deleted_at": optional_datetime(self.deleted_at()),
+        })
+    }
+}
+
+impl Auditable for Doit {
+    const ENTITY: AuditEntity = AuditEntity::Doit;
+
+    fn audit_id(&self) -> Uuid {
+        self.id().clone().value()
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        let (is_public, alternative_name) = match self.is_public() {
+            DoitPublishment::Public => (true, None),
+            DoitPublishment::Private(name) => (false, name.clone()),
+        };
+
+        json!({
+            "name": self.name().clone().value(),
+            "description": self.description().clone().value(),
+            "is_public": is_public,
+            "alternative_name": alternative_name,
+            "label_ids": self.labels().iter().map(|l| l.id().clone().value()).collect::<Vec<_>>(),
+            "voter_ids": self.voters().iter().map(|v| v.clone().value()).collect::<Vec<_>>(),
+            "affects_to": self.affects_to().clone().map(|id| id.value()),
+            "status": self.status().to_string(),
+            "status_reason": self.status_reason(),
+            "duplicate_of": self.duplicate_of().clone().map(|id| id.value()),
+            "reviewed_at": optional_datetime(self.reviewed_at()),
+            "deadlined_at": optional_datetime(self.deadlined_at()),
+            "created_by": self.created_by().clone().value(),
+        })
+    }
+}
+
+impl Auditable for Label {
+    const ENTITY: AuditEntity = AuditEntity::Label;
+
+    fn audit_id(&self) -> Uuid {
+        self.id().clone().value()
+    }
+
+    fn audit_snapshot(&self) -> Value {
+        json!({
+            "name": self.name().clone().value(),
+            "description": self.description().clone().value(),
+            "color": self
+                .color()
+                .as_ref()
+                .map(|c| format!("#{:02X}{:02X}{:02X}", c.red(), c.green(), c.blue())),
+        })
+    }
+}
+
+impl Auditable for User {
+    const ENTITY: AuditEntity = AuditEntity::User;
+
+    fn audit_id(&self) -> Uuid {
+        self.id().clone().value()
+    }
+
+    // NOTE: カレンダーのトークンなどの秘密の値は含めない
+    fn audit_snapshot(&self) -> Value {
+        json!({
+            "role": match self.role() {
+                UserRole::Owner => "owner",
+                UserRole::Contributor => "contributor",
+            },
+            "name": self.name().clone().value(),
+            "email": self.email().clone().value(),
+            "timezone": self.timezone().name(),
+        })
+    }
+}
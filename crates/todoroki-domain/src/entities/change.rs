@@ -0,0 +1,114 @@
+use crate::{
+    entities::{
+        doit::Doit,
+        label::{Label, LabelId},
+        todo::{Todo, TodoId},
+        user::UserId,
+    },
+    value_object,
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use getset::Getters;
+
+// 購読しているクライアントに配信する、todo / doit / label の変更
+#[derive(Debug, Clone, Getters)]
+pub struct ChangeEvent {
+    // 配信した順に大きくなる
+    #[getset(get = "pub")]
+    id: ChangeEventId,
+    #[getset(get = "pub")]
+    action: ChangeAction,
+    // NOTE: 購読者ごとに閲覧できる内容が異なるため、変更後の状態をそのまま持つ
+    #[getset(get = "pub")]
+    target: ChangeTarget,
+    #[getset(get = "pub")]
+    occurred_at: DateTime,
+}
+
+value_object!(ChangeEventId(u64));
+
+impl TryFrom<String> for ChangeEventId {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            value
+                .parse()
+                .map_err(|_| ErrorCode::InvalidChangeEventId(value))?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl std::fmt::Display for ChangeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Created => write!(f, "created"),
+            Self::Updated => write!(f, "updated"),
+            Self::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ChangeTarget {
+    Todo(Todo),
+    Doit(Doit),
+    Label(Label),
+}
+
+impl ChangeEvent {
+    pub fn new(
+        id: ChangeEventId,
+        action: ChangeAction,
+        target: ChangeTarget,
+        occurred_at: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            action,
+            target,
+            occurred_at,
+        }
+    }
+}
+
+// 購読する変更の範囲
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeTopic {
+    // すべての todo
+    Todos,
+    // ラベルのついた todo / doit と、ラベルそのもの
+    Label(LabelId),
+    // todo と、それに紐づく doit
+    Todo(TodoId),
+    // 自分が作った doit
+    MyDoits,
+}
+
+impl ChangeTopic {
+    // user_id は購読者のもの。ログインしていなければ None
+    // NOTE: 変更後の状態で判定するため、ラベルを外された todo の変更はラベルの範囲には含まれない
+    pub fn matches(&self, event: &ChangeEvent, user_id: Option<&UserId>) -> bool {
+        match (self, event.target()) {
+            (Self::Todos, ChangeTarget::Todo(_)) => true,
+            (Self::Label(id), ChangeTarget::Todo(todo)) => {
+                todo.labels().iter().any(|l| l.id() == id)
+            }
+            (Self::Label(id), ChangeTarget::Doit(doit)) => {
+                doit.labels().iter().any(|l| l.id() == id)
+            }
+            (Self::Label(id), ChangeTarget::Label(label)) => label.id() == id,
+            (Self::Todo(id), ChangeTarget::Todo(todo)) => todo.id() == id,
+            (Self::Todo(id), ChangeTarget::Doit(doit)) => doit.affects_to().as_ref() == Some(id),
+            (Self::MyDoits, ChangeTarget::Doit(doit)) => Some(doit.created_by()) == user_id,
+            _ => false,
+        }
+    }
+}
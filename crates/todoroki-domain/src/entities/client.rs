@@ -1,6 +1,9 @@
 use getset::Getters;
 
-use crate::entities::user::{User, UserEmail};
+use crate::{
+    entities::user::{User, UserEmail},
+    value_objects::datetime::Timezone,
+};
 
 #[derive(Debug, Clone)]
 pub enum Client {
@@ -9,6 +12,16 @@ pub enum Client {
     Unverified,
 }
 
+impl Client {
+    // スケジュールでタイムゾーンを指定しなかった場合に使う
+    pub fn timezone(&self) -> Timezone {
+        match self {
+            Self::User(user) => user.timezone().clone(),
+            Self::Unregistered(_) | Self::Unverified => Timezone::utc(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Getters)]
 pub struct ContextedClient<'a> {
     #[getset(get = "pub")]
@@ -0,0 +1,100 @@
+use crate::{
+    entities::{doit::DoitId, todo::TodoId, user::UserId},
+    value_object,
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use getset::Getters;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Getters)]
+pub struct Comment {
+    #[getset(get = "pub")]
+    id: CommentId,
+    #[getset(get = "pub")]
+    target: CommentTarget,
+    #[getset(get = "pub")]
+    parent_id: Option<CommentId>,
+    #[getset(get = "pub")]
+    body: CommentBody,
+    #[getset(get = "pub")]
+    created_by: UserId,
+    #[getset(get = "pub")]
+    created_at: DateTime,
+    #[getset(get = "pub")]
+    updated_at: DateTime,
+    #[getset(get = "pub")]
+    deleted_at: Option<DateTime>,
+}
+
+value_object!(CommentId(Uuid));
+value_object!(CommentBody(String));
+
+impl CommentId {
+    pub(crate) fn generate() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl TryFrom<String> for CommentId {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            Uuid::parse_str(&value).map_err(|_| ErrorCode::InvalidUuidFormat(value))?,
+        ))
+    }
+}
+
+// コメントを付ける対象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentTarget {
+    Todo(TodoId),
+    Doit(DoitId),
+}
+
+impl Comment {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: CommentId,
+        target: CommentTarget,
+        parent_id: Option<CommentId>,
+        body: CommentBody,
+        created_by: UserId,
+        created_at: DateTime,
+        updated_at: DateTime,
+        deleted_at: Option<DateTime>,
+    ) -> Self {
+        Self {
+            id,
+            target,
+            parent_id,
+            body,
+            created_by,
+            created_at,
+            updated_at,
+            deleted_at,
+        }
+    }
+
+    pub fn generate(
+        target: CommentTarget,
+        parent_id: Option<CommentId>,
+        body: CommentBody,
+        created_by: UserId,
+    ) -> Self {
+        Self {
+            id: CommentId::generate(),
+            target,
+            parent_id,
+            body,
+            created_by,
+            created_at: DateTime::now(),
+            updated_at: DateTime::now(),
+            deleted_at: None,
+        }
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+}
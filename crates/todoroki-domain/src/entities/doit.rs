@@ -1,5 +1,9 @@
 use crate::{
-    entities::{label::Label, todo::TodoId, user::UserId},
+    entities::{
+        label::Label,
+        todo::{Todo, TodoDescription, TodoId, TodoName, TodoPublishment},
+        user::UserId,
+    },
     value_object,
     value_objects::{datetime::DateTime, error::ErrorCode},
 };
@@ -19,8 +23,18 @@ pub struct Doit {
     #[getset(get = "pub")]
     labels: Vec<Label>,
     #[getset(get = "pub")]
+    voters: Vec<UserId>,
+    #[getset(get = "pub")]
     affects_to: Option<TodoId>,
     #[getset(get = "pub")]
+    status: DoitStatus,
+    #[getset(get = "pub")]
+    status_reason: Option<String>,
+    #[getset(get = "pub")]
+    duplicate_of: Option<DoitId>,
+    #[getset(get = "pub")]
+    reviewed_at: Option<DateTime>,
+    #[getset(get = "pub")]
     deadlined_at: Option<DateTime>,
     #[getset(get = "pub")]
     created_at: DateTime,
@@ -56,14 +70,66 @@ pub enum DoitPublishment {
     Private(Option<String>), // alternative name
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoitStatus {
+    Pending,
+    OnHold,
+    Accepted,
+    Rejected,
+    Duplicate,
+}
+
+impl DoitStatus {
+    pub fn can_transition_to(&self, to: &DoitStatus) -> bool {
+        matches!(
+            (self, to),
+            (
+                Self::Pending,
+                Self::OnHold | Self::Accepted | Self::Rejected | Self::Duplicate
+            ) | (
+                Self::OnHold,
+                Self::Pending | Self::Accepted | Self::Rejected | Self::Duplicate
+            )
+            // NOTE: 却下や重複としたものは審査をやり直せる。受け入れたものは戻せない
+            | (Self::Rejected | Self::Duplicate, Self::Pending)
+        )
+    }
+
+    pub fn transition_to(&self, to: DoitStatus) -> Result<DoitStatus, ErrorCode> {
+        if self.can_transition_to(&to) {
+            Ok(to)
+        } else {
+            Err(ErrorCode::DoitStatusTransitionInvalid(*self, to))
+        }
+    }
+}
+
+impl std::fmt::Display for DoitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::OnHold => write!(f, "on-hold"),
+            Self::Accepted => write!(f, "accepted"),
+            Self::Rejected => write!(f, "rejected"),
+            Self::Duplicate => write!(f, "duplicate"),
+        }
+    }
+}
+
 impl Doit {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: DoitId,
         name: DoitName,
         description: DoitDescription,
         is_public: DoitPublishment,
         labels: Vec<Label>,
+        voters: Vec<UserId>,
         affects_to: Option<TodoId>,
+        status: DoitStatus,
+        status_reason: Option<String>,
+        duplicate_of: Option<DoitId>,
+        reviewed_at: Option<DateTime>,
         deadlined_at: Option<DateTime>,
         created_at: DateTime,
         updated_at: DateTime,
@@ -75,7 +141,12 @@ impl Doit {
             description,
             is_public,
             labels,
+            voters,
             affects_to,
+            status,
+            status_reason,
+            duplicate_of,
+            reviewed_at,
             deadlined_at,
             created_at,
             updated_at,
@@ -97,7 +168,12 @@ impl Doit {
             description,
             is_public,
             labels,
+            voters: Vec::new(),
             affects_to: None,
+            status: DoitStatus::Pending,
+            status_reason: None,
+            duplicate_of: None,
+            reviewed_at: None,
             deadlined_at,
             created_at: DateTime::now(),
             updated_at: DateTime::now(),
@@ -105,11 +181,39 @@ impl Doit {
         }
     }
 
+    pub fn has_voted(&self, user_id: &UserId) -> bool {
+        self.voters.contains(user_id)
+    }
+
+    // 審査中のもの
     pub fn is_alive(&self) -> bool {
-        self.affects_to.is_none()
+        matches!(self.status, DoitStatus::Pending | DoitStatus::OnHold)
+    }
+
+    // 名前・説明・公開設定・ラベル・締め切りを引き継いだ Todo を作る
+    pub fn generate_todo(&self) -> Todo {
+        Todo::generate(
+            TodoName::new(self.name.clone().value()),
+            TodoDescription::new(self.description.clone().value()),
+            match &self.is_public {
+                DoitPublishment::Public => TodoPublishment::Public,
+                DoitPublishment::Private(alt) => TodoPublishment::Private(alt.clone()),
+            },
+            None,
+            self.labels.clone(),
+            Vec::new(),
+            self.deadlined_at.clone(),
+        )
     }
 }
 
+// Doit を受け入れるときの紐づけ先
+#[derive(Debug, Clone)]
+pub enum DoitAcceptance {
+    NewTodo(Box<Todo>),   // 新しく作る Todo
+    ExistingTodo(TodoId), // 既存の Todo
+}
+
 // None fieald will not be updated
 #[derive(Debug, Clone, Getters)]
 pub struct DoitUpdateCommand {
@@ -154,3 +258,54 @@ impl DoitUpdateCommand {
             && self.deadlined_at.is_none()
     }
 }
+
+// Doit の審査結果。受け入れは DoitAcceptance で行う
+#[derive(Debug, Clone, Getters)]
+pub struct DoitReviewCommand {
+    #[getset(get = "pub")]
+    id: DoitId,
+    #[getset(get = "pub")]
+    status: DoitStatus,
+    #[getset(get = "pub")]
+    reason: Option<String>,
+    #[getset(get = "pub")]
+    duplicate_of: Option<DoitId>,
+}
+
+impl DoitReviewCommand {
+    pub fn hold(id: DoitId, reason: Option<String>) -> Self {
+        Self {
+            id,
+            status: DoitStatus::OnHold,
+            reason,
+            duplicate_of: None,
+        }
+    }
+
+    pub fn reject(id: DoitId, reason: String) -> Self {
+        Self {
+            id,
+            status: DoitStatus::Rejected,
+            reason: Some(reason),
+            duplicate_of: None,
+        }
+    }
+
+    pub fn duplicate(id: DoitId, duplicate_of: DoitId, reason: Option<String>) -> Self {
+        Self {
+            id,
+            status: DoitStatus::Duplicate,
+            reason,
+            duplicate_of: Some(duplicate_of),
+        }
+    }
+
+    pub fn reopen(id: DoitId) -> Self {
+        Self {
+            id,
+            status: DoitStatus::Pending,
+            reason: None,
+            duplicate_of: None,
+        }
+    }
+}
@@ -1,11 +1,17 @@
 use crate::{
-    entities::label::Label,
+    entities::{
+        label::{Label, LabelId},
+        user::UserId,
+    },
+    services::occurrence,
     value_object,
     value_objects::{
-        datetime::{DateTime, MonthlyTime, Time, WeeklyTime},
+        datetime::{DateTime, MonthlyTime, Time, Timezone, WeeklyTime},
         error::ErrorCode,
+        recurrence::RecurrenceRule,
     },
 };
+use chrono::Days;
 use getset::Getters;
 use uuid::Uuid;
 
@@ -20,10 +26,23 @@ pub struct Todo {
     #[getset(get = "pub")]
     is_public: TodoPublishment,
     #[getset(get = "pub")]
+    parent_id: Option<TodoId>,
+    #[getset(get = "pub")]
     labels: Vec<Label>,
+    // この todo の前に終わらせる必要のある todo。論理削除されたものは含まない
+    #[getset(get = "pub")]
+    blockers: Vec<TodoBlocker>,
     #[getset(get = "pub")]
     schedules: Vec<TodoSchedule>,
     #[getset(get = "pub")]
+    status: TodoStatus,
+    // 子の todo から求める。リポジトリで集計する
+    #[getset(get = "pub")]
+    progress: TodoProgress,
+    // 直近の回 (Todo::recent_occurrences_since 以降に終わる回) の完了記録のみを持つ
+    #[getset(get = "pub")]
+    occurrence_completions: Vec<TodoOccurrenceCompletion>,
+    #[getset(get = "pub")]
     started_at: Option<DateTime>,
     #[getset(get = "pub")]
     deadlined_at: Option<DateTime>,
@@ -57,29 +76,354 @@ impl TryFrom<String> for TodoId {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TodoPublishment {
     Public,
     Private(Option<String>), // alternative name
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TodoSchedule {
     // (starts_at, ends_at)
     Once(DateTime, DateTime),
-    Daily(Time, Time),
-    Weekly(WeeklyTime, WeeklyTime),
-    Monthly(MonthlyTime, MonthlyTime),
+    // (starts_at, ends_at, 時刻を解釈するタイムゾーン)
+    Daily(Time, Time, Timezone),
+    Weekly(WeeklyTime, WeeklyTime, Timezone),
+    Monthly(MonthlyTime, MonthlyTime, Timezone),
+    // (最初の回の starts_at, 最初の回の ends_at, 繰り返しの規則, 繰り返しを解釈するタイムゾーン)
+    Recurrence(DateTime, DateTime, RecurrenceRule, Timezone),
+}
+
+impl TodoSchedule {
+    // Once は日時そのものなので UTC とする
+    pub fn timezone(&self) -> Timezone {
+        match self {
+            Self::Once(_, _) => Timezone::utc(),
+            Self::Daily(_, _, tz)
+            | Self::Weekly(_, _, tz)
+            | Self::Monthly(_, _, tz)
+            | Self::Recurrence(_, _, _, tz) => tz.clone(),
+        }
+    }
+
+    pub fn try_recurrence(
+        starts_at: DateTime,
+        ends_at: DateTime,
+        rule: RecurrenceRule,
+        timezone: Timezone,
+    ) -> Result<Self, ErrorCode> {
+        if ends_at.clone().value() < starts_at.clone().value() {
+            return Err(ErrorCode::InvalidRecurrenceRule(format!(
+                "ends-before-starts; starts_at={}; ends_at={}",
+                starts_at.value().to_rfc3339(),
+                ends_at.value().to_rfc3339()
+            )));
+        }
+
+        Ok(Self::Recurrence(starts_at, ends_at, rule, timezone))
+    }
+}
+
+// スケジュールの1回分
+#[derive(Debug, Clone, Getters)]
+pub struct TodoOccurrence {
+    #[getset(get = "pub")]
+    starts_at: DateTime,
+    #[getset(get = "pub")]
+    ends_at: DateTime,
+}
+
+impl TodoOccurrence {
+    pub fn new(starts_at: DateTime, ends_at: DateTime) -> Self {
+        Self { starts_at, ends_at }
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct TodoOccurrenceCompletion {
+    #[getset(get = "pub")]
+    occurrence_start: DateTime,
+    #[getset(get = "pub")]
+    occurrence_end: DateTime,
+    #[getset(get = "pub")]
+    completed_at: DateTime,
+}
+
+impl TodoOccurrenceCompletion {
+    pub fn new(
+        occurrence_start: DateTime,
+        occurrence_end: DateTime,
+        completed_at: DateTime,
+    ) -> Self {
+        Self {
+            occurrence_start,
+            occurrence_end,
+            completed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoStatus {
+    NotStarted,
+    OnProgress,
+    Paused,
+    Completed,
+    Cancelled,
+}
+
+impl TodoStatus {
+    pub fn can_transition_to(&self, to: &TodoStatus) -> bool {
+        matches!(
+            (self, to),
+            (
+                Self::NotStarted,
+                Self::OnProgress | Self::Completed | Self::Cancelled
+            ) | (
+                Self::OnProgress,
+                Self::Paused | Self::Completed | Self::Cancelled
+            ) | (
+                Self::Paused,
+                Self::OnProgress | Self::Completed | Self::Cancelled
+            )
+            // NOTE: 完了や中止したものは再開できる
+            | (Self::Completed, Self::OnProgress)
+            | (Self::Cancelled, Self::NotStarted | Self::OnProgress)
+        )
+    }
+
+    pub fn transition_to(&self, to: TodoStatus) -> Result<TodoStatus, ErrorCode> {
+        if self.can_transition_to(&to) {
+            Ok(to)
+        } else {
+            Err(ErrorCode::TodoStatusTransitionInvalid(*self, to))
+        }
+    }
+}
+
+impl std::fmt::Display for TodoStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotStarted => write!(f, "not-started"),
+            Self::OnProgress => write!(f, "on-progress"),
+            Self::Paused => write!(f, "paused"),
+            Self::Completed => write!(f, "completed"),
+            Self::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct TodoBlocker {
+    #[getset(get = "pub")]
+    id: TodoId,
+    #[getset(get = "pub")]
+    status: TodoStatus,
+}
+
+impl TodoBlocker {
+    pub fn new(id: TodoId, status: TodoStatus) -> Self {
+        Self { id, status }
+    }
+}
+
+// 子の todo のうち完了したものの数
+// NOTE: 中止したものと削除したものは数えない
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Getters)]
+pub struct TodoProgress {
+    #[getset(get = "pub")]
+    completed: u32,
+    #[getset(get = "pub")]
+    total: u32,
+}
+
+impl TodoProgress {
+    pub fn new(completed: u32, total: u32) -> Self {
+        Self { completed, total }
+    }
+
+    pub fn has_children(&self) -> bool {
+        self.total > 0
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct TodoStatusTransition {
+    #[getset(get = "pub")]
+    from_status: TodoStatus,
+    #[getset(get = "pub")]
+    to_status: TodoStatus,
+    #[getset(get = "pub")]
+    transitioned_at: DateTime,
+}
+
+impl TodoStatusTransition {
+    pub fn new(from_status: TodoStatus, to_status: TodoStatus, transitioned_at: DateTime) -> Self {
+        Self {
+            from_status,
+            to_status,
+            transitioned_at,
+        }
+    }
+}
+
+// 更新された時点での todo の内容。版は1から始まる
+#[derive(Debug, Clone, Getters)]
+pub struct TodoVersion {
+    #[getset(get = "pub")]
+    todo_id: TodoId,
+    #[getset(get = "pub")]
+    version: u32,
+    #[getset(get = "pub")]
+    name: TodoName,
+    #[getset(get = "pub")]
+    description: TodoDescription,
+    #[getset(get = "pub")]
+    is_public: TodoPublishment,
+    #[getset(get = "pub")]
+    deadlined_at: Option<DateTime>,
+    #[getset(get = "pub")]
+    labels: Vec<Label>,
+    #[getset(get = "pub")]
+    schedules: Vec<TodoSchedule>,
+    // 履歴を残し始める前の内容から作った版では None
+    #[getset(get = "pub")]
+    created_by: Option<UserId>,
+    #[getset(get = "pub")]
+    created_at: DateTime,
+}
+
+// 直前の版からの変更。(変更前, 変更後)
+#[derive(Debug, Clone)]
+pub enum TodoVersionChange {
+    Name(TodoName, TodoName),
+    Description(TodoDescription, TodoDescription),
+    Publishment(TodoPublishment, TodoPublishment),
+    Deadline(Option<DateTime>, Option<DateTime>),
+    Labels(Vec<Label>, Vec<Label>),
+    Schedules(Vec<TodoSchedule>, Vec<TodoSchedule>),
+}
+
+impl TodoVersion {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        todo_id: TodoId,
+        version: u32,
+        name: TodoName,
+        description: TodoDescription,
+        is_public: TodoPublishment,
+        deadlined_at: Option<DateTime>,
+        labels: Vec<Label>,
+        schedules: Vec<TodoSchedule>,
+        created_by: Option<UserId>,
+        created_at: DateTime,
+    ) -> Self {
+        Self {
+            todo_id,
+            version,
+            name,
+            description,
+            is_public,
+            deadlined_at,
+            labels,
+            schedules,
+            created_by,
+            created_at,
+        }
+    }
+
+    pub fn changes_from(&self, previous: &TodoVersion) -> Vec<TodoVersionChange> {
+        let mut changes = Vec::new();
+
+        if self.name != previous.name {
+            changes.push(TodoVersionChange::Name(
+                previous.name.clone(),
+                self.name.clone(),
+            ));
+        }
+        if self.description != previous.description {
+            changes.push(TodoVersionChange::Description(
+                previous.description.clone(),
+                self.description.clone(),
+            ));
+        }
+        if self.is_public != previous.is_public {
+            changes.push(TodoVersionChange::Publishment(
+                previous.is_public.clone(),
+                self.is_public.clone(),
+            ));
+        }
+        if self.deadlined_at != previous.deadlined_at {
+            changes.push(TodoVersionChange::Deadline(
+                previous.deadlined_at.clone(),
+                self.deadlined_at.clone(),
+            ));
+        }
+        // NOTE: ラベルは付いているものの集合として比べる
+        if label_ids(&self.labels) != label_ids(&previous.labels) {
+            changes.push(TodoVersionChange::Labels(
+                previous.labels.clone(),
+                self.labels.clone(),
+            ));
+        }
+        if self.schedules != previous.schedules {
+            changes.push(TodoVersionChange::Schedules(
+                previous.schedules.clone(),
+                self.schedules.clone(),
+            ));
+        }
+
+        changes
+    }
+
+    // 現在の todo をこの版の内容に戻すためのコマンド
+    pub fn revert_command(&self, current: &Todo) -> TodoUpdateCommand {
+        let current_label_ids = label_ids(current.labels());
+        let version_label_ids = label_ids(&self.labels);
+
+        TodoUpdateCommand::new(
+            current.id().clone(),
+            Some(self.name.clone()),
+            Some(self.description.clone()),
+            Some(self.is_public.clone()),
+            Some(self.deadlined_at.clone()),
+            None,
+            None,
+            version_label_ids
+                .difference(&current_label_ids)
+                .cloned()
+                .map(LabelId::new)
+                .collect(),
+            current_label_ids
+                .difference(&version_label_ids)
+                .cloned()
+                .map(LabelId::new)
+                .collect(),
+            Vec::new(),
+            Vec::new(),
+            Some(self.schedules.clone()),
+        )
+    }
+}
+
+fn label_ids(labels: &[Label]) -> std::collections::BTreeSet<Uuid> {
+    labels.iter().map(|l| l.id().clone().value()).collect()
 }
 
 impl Todo {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: TodoId,
         name: TodoName,
         description: TodoDescription,
         is_public: TodoPublishment,
+        parent_id: Option<TodoId>,
         labels: Vec<Label>,
+        blockers: Vec<TodoBlocker>,
         schedules: Vec<TodoSchedule>,
+        status: TodoStatus,
+        progress: TodoProgress,
+        occurrence_completions: Vec<TodoOccurrenceCompletion>,
         started_at: Option<DateTime>,
         deadlined_at: Option<DateTime>,
         ended_at: Option<DateTime>,
@@ -92,8 +436,13 @@ impl Todo {
             name,
             description,
             is_public,
+            parent_id,
             labels,
+            blockers,
             schedules,
+            status,
+            progress,
+            occurrence_completions,
             started_at,
             deadlined_at,
             ended_at,
@@ -107,6 +456,7 @@ impl Todo {
         name: TodoName,
         description: TodoDescription,
         is_public: TodoPublishment,
+        parent_id: Option<TodoId>,
         labels: Vec<Label>,
         schedules: Vec<TodoSchedule>,
         deadlined_at: Option<DateTime>,
@@ -116,8 +466,13 @@ impl Todo {
             name,
             description,
             is_public,
+            parent_id,
             labels,
+            blockers: Vec::new(),
             schedules,
+            status: TodoStatus::NotStarted,
+            progress: TodoProgress::default(),
+            occurrence_completions: Vec::new(),
             started_at: None,
             deadlined_at,
             ended_at: None,
@@ -127,9 +482,74 @@ impl Todo {
         }
     }
 
+    // 取り込みなどで、作成時点での status を指定する
+    pub fn with_status(self, status: TodoStatus, at: DateTime) -> Self {
+        let (started_at, ended_at) = match status {
+            TodoStatus::NotStarted => (None, None),
+            TodoStatus::OnProgress | TodoStatus::Paused => (Some(at), None),
+            TodoStatus::Completed | TodoStatus::Cancelled => (None, Some(at)),
+        };
+
+        Self {
+            status,
+            started_at,
+            ended_at,
+            ..self
+        }
+    }
+
     pub fn is_alive(&self) -> bool {
         self.deleted_at.is_none() && self.ended_at.is_none()
     }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    // 完了していない blocker があるか
+    pub fn is_blocked(&self) -> bool {
+        self.blockers
+            .iter()
+            .any(|blocker| *blocker.status() != TodoStatus::Completed)
+    }
+
+    // 「直近の回」とみなす期間
+    pub const RECENT_OCCURRENCE_DAYS: u64 = 7;
+
+    pub fn recent_occurrences_since(now: &DateTime) -> DateTime {
+        DateTime::new(now.clone().value() - Days::new(Self::RECENT_OCCURRENCE_DAYS))
+    }
+
+    // 現在進行中の回と、直近に終わった回
+    pub fn recent_occurrences(&self, now: &DateTime) -> Vec<(TodoOccurrence, Option<DateTime>)> {
+        let since = Self::recent_occurrences_since(now);
+        let until = DateTime::new(now.clone().value() + chrono::Duration::seconds(1));
+
+        occurrence::expand_todo(self, &since, &until)
+            .into_iter()
+            .map(|occurrence| {
+                let completed_at = self.completed_at_of(occurrence.starts_at());
+
+                (occurrence, completed_at)
+            })
+            .collect()
+    }
+
+    // occurrence_start に始まる回があればそれを返す
+    pub fn find_occurrence(&self, occurrence_start: &DateTime) -> Option<TodoOccurrence> {
+        let until = DateTime::new(occurrence_start.clone().value() + chrono::Duration::seconds(1));
+
+        occurrence::expand_todo(self, occurrence_start, &until)
+            .into_iter()
+            .find(|occurrence| occurrence.starts_at() == occurrence_start)
+    }
+
+    fn completed_at_of(&self, occurrence_start: &DateTime) -> Option<DateTime> {
+        self.occurrence_completions
+            .iter()
+            .find(|completion| completion.occurrence_start() == occurrence_start)
+            .map(|completion| completion.completed_at().clone())
+    }
 }
 
 // None fieald will not be updated
@@ -145,24 +565,42 @@ pub struct TodoUpdateCommand {
     is_public: Option<TodoPublishment>,
     #[getset(get = "pub")]
     deadlined_at: Option<Option<DateTime>>,
+    // 循環しないかは呼び出し側で検証する
     #[getset(get = "pub")]
-    status: Option<TodoUpdateProgressStatus>,
-}
-
-#[derive(Debug, Clone)]
-pub enum TodoUpdateProgressStatus {
-    OnProgress,
-    Completed,
+    parent_id: Option<Option<TodoId>>,
+    // 遷移できるかは呼び出し側で検証する
+    #[getset(get = "pub")]
+    status: Option<TodoStatus>,
+    // remove_labels を外してから add_labels を付ける
+    #[getset(get = "pub")]
+    add_labels: Vec<LabelId>,
+    #[getset(get = "pub")]
+    remove_labels: Vec<LabelId>,
+    // remove_blockers を外してから add_blockers を付ける。循環しないかは呼び出し側で検証する
+    #[getset(get = "pub")]
+    add_blockers: Vec<TodoId>,
+    #[getset(get = "pub")]
+    remove_blockers: Vec<TodoId>,
+    // Some の場合は schedules をすべて置き換える
+    #[getset(get = "pub")]
+    schedules: Option<Vec<TodoSchedule>>,
 }
 
 impl TodoUpdateCommand {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: TodoId,
         name: Option<TodoName>,
         description: Option<TodoDescription>,
         is_public: Option<TodoPublishment>,
         deadlined_at: Option<Option<DateTime>>,
-        status: Option<TodoUpdateProgressStatus>,
+        parent_id: Option<Option<TodoId>>,
+        status: Option<TodoStatus>,
+        add_labels: Vec<LabelId>,
+        remove_labels: Vec<LabelId>,
+        add_blockers: Vec<TodoId>,
+        remove_blockers: Vec<TodoId>,
+        schedules: Option<Vec<TodoSchedule>>,
     ) -> Self {
         Self {
             id,
@@ -170,7 +608,13 @@ impl TodoUpdateCommand {
             description,
             is_public,
             deadlined_at,
+            parent_id,
             status,
+            add_labels,
+            remove_labels,
+            add_blockers,
+            remove_blockers,
+            schedules,
         }
     }
 
@@ -179,6 +623,60 @@ impl TodoUpdateCommand {
             && self.description.is_none()
             && self.is_public.is_none()
             && self.deadlined_at.is_none()
+            && self.parent_id.is_none()
             && self.status.is_none()
+            && self.add_labels.is_empty()
+            && self.remove_labels.is_empty()
+            && self.add_blockers.is_empty()
+            && self.remove_blockers.is_empty()
+            && self.schedules.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_transitions() {
+        use TodoStatus::*;
+
+        // (変更前, 変更後, 遷移できるか)。すべての組み合わせを並べる
+        let cases = [
+            (NotStarted, NotStarted, false),
+            (NotStarted, OnProgress, true),
+            (NotStarted, Paused, false),
+            (NotStarted, Completed, true),
+            (NotStarted, Cancelled, true),
+            (OnProgress, NotStarted, false),
+            (OnProgress, OnProgress, false),
+            (OnProgress, Paused, true),
+            (OnProgress, Completed, true),
+            (OnProgress, Cancelled, true),
+            (Paused, NotStarted, false),
+            (Paused, OnProgress, true),
+            (Paused, Paused, false),
+            (Paused, Completed, true),
+            (Paused, Cancelled, true),
+            (Completed, NotStarted, false),
+            (Completed, OnProgress, true),
+            (Completed, Paused, false),
+            (Completed, Completed, false),
+            (Completed, Cancelled, false),
+            (Cancelled, NotStarted, true),
+            (Cancelled, OnProgress, true),
+            (Cancelled, Paused, false),
+            (Cancelled, Completed, false),
+            (Cancelled, Cancelled, false),
+        ];
+
+        for (from, to, allowed) in cases {
+            assert_eq!(from.can_transition_to(&to), allowed, "{from:?} -> {to:?}");
+            assert_eq!(
+                from.transition_to(to).is_ok(),
+                allowed,
+                "{from:?} -> {to:?}"
+            );
+        }
     }
 }
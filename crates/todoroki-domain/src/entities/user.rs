@@ -1,4 +1,10 @@
-use crate::{value_object, value_objects::datetime::DateTime};
+use crate::{
+    value_object,
+    value_objects::{
+        datetime::{DateTime, Timezone},
+        error::ErrorCode,
+    },
+};
 use getset::Getters;
 use uuid::Uuid;
 
@@ -12,6 +18,9 @@ pub struct User {
     name: UserName,
     #[getset(get = "pub")]
     email: UserEmail,
+    // スケジュールでタイムゾーンを指定しなかった場合に使う
+    #[getset(get = "pub")]
+    timezone: Timezone,
     #[getset(get = "pub")]
     created_at: DateTime,
     #[getset(get = "pub")]
@@ -21,6 +30,8 @@ pub struct User {
 value_object!(UserId(Uuid));
 value_object!(UserName(String));
 value_object!(UserEmail(String));
+// カレンダーの購読 URL に埋め込む秘密のトークン
+value_object!(UserCalendarToken(String));
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UserRole {
@@ -34,12 +45,34 @@ impl UserId {
     }
 }
 
+impl UserCalendarToken {
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}{}",
+            Uuid::new_v4().as_simple(),
+            Uuid::new_v4().as_simple()
+        ))
+    }
+}
+
+impl TryFrom<String> for UserId {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            Uuid::parse_str(&value).map_err(|_| ErrorCode::InvalidUuidFormat(value))?,
+        ))
+    }
+}
+
 impl User {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: UserId,
         role: UserRole,
         name: UserName,
         email: UserEmail,
+        timezone: Timezone,
         created_at: DateTime,
         updated_at: DateTime,
     ) -> User {
@@ -48,17 +81,19 @@ impl User {
             role,
             name,
             email,
+            timezone,
             created_at,
             updated_at,
         }
     }
 
-    pub fn generate(role: UserRole, name: UserName, email: UserEmail) -> Self {
+    pub fn generate(role: UserRole, name: UserName, email: UserEmail, timezone: Timezone) -> Self {
         Self {
             id: UserId::generate(),
             role,
             name,
             email,
+            timezone,
             created_at: DateTime::now(),
             updated_at: DateTime::now(),
         }
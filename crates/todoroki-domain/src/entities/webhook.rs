@@ -0,0 +1,471 @@
+use crate::{
+    entities::{audit::Auditable, user::UserId},
+    value_object,
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use getset::Getters;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+// 最初の試行を含めた最大の試行回数
+const WEBHOOK_DELIVERY_MAX_ATTEMPTS: u32 = 8;
+// 再試行までの間隔。失敗するたびに倍にする
+const WEBHOOK_DELIVERY_BASE_BACKOFF_SECS: i64 = 30;
+// 1回の送信のタイムアウト
+pub const WEBHOOK_DELIVERY_TIMEOUT_SECS: u64 = 10;
+// 取り出した配送を他から取り出されないようにしておく時間
+// NOTE: 配送は1件ずつ取り出して送信するので、送信のタイムアウトより十分に長ければよい
+const WEBHOOK_DELIVERY_LEASE_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Getters)]
+pub struct Webhook {
+    #[getset(get = "pub")]
+    id: WebhookId,
+    #[getset(get = "pub")]
+    url: WebhookUrl,
+    // 送信する payload の署名に使う
+    #[getset(get = "pub")]
+    secret: WebhookSecret,
+    #[getset(get = "pub")]
+    event_types: Vec<WebhookEventType>,
+    #[getset(get = "pub")]
+    created_by: UserId,
+    #[getset(get = "pub")]
+    created_at: DateTime,
+    #[getset(get = "pub")]
+    updated_at: DateTime,
+}
+
+value_object!(WebhookId(Uuid));
+value_object!(WebhookUrl(String));
+value_object!(WebhookSecret(String));
+
+impl WebhookId {
+    pub(crate) fn generate() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl TryFrom<String> for WebhookId {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            Uuid::parse_str(&value).map_err(|_| ErrorCode::InvalidUuidFormat(value))?,
+        ))
+    }
+}
+
+impl TryFrom<String> for WebhookUrl {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.starts_with("https://") || value.starts_with("http://") {
+            Ok(Self(value))
+        } else {
+            Err(ErrorCode::InvalidWebhookUrl(value))
+        }
+    }
+}
+
+impl WebhookSecret {
+    pub fn generate() -> Self {
+        Self(format!(
+            "{}{}",
+            Uuid::new_v4().as_simple(),
+            Uuid::new_v4().as_simple()
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEventType {
+    TodoCreated,
+    TodoUpdated,
+    TodoCompleted,
+    TodoDeleted,
+    DoitCreated,
+    DoitUpdated,
+    DoitAccepted,
+    LabelCreated,
+}
+
+impl std::fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TodoCreated => write!(f, "todo.created"),
+            Self::TodoUpdated => write!(f, "todo.updated"),
+            Self::TodoCompleted => write!(f, "todo.completed"),
+            Self::TodoDeleted => write!(f, "todo.deleted"),
+            Self::DoitCreated => write!(f, "doit.created"),
+            Self::DoitUpdated => write!(f, "doit.updated"),
+            Self::DoitAccepted => write!(f, "doit.accepted"),
+            Self::LabelCreated => write!(f, "label.created"),
+        }
+    }
+}
+
+impl TryFrom<String> for WebhookEventType {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "todo.created" => Ok(Self::TodoCreated),
+            "todo.updated" => Ok(Self::TodoUpdated),
+            "todo.completed" => Ok(Self::TodoCompleted),
+            "todo.deleted" => Ok(Self::TodoDeleted),
+            "doit.created" => Ok(Self::DoitCreated),
+            "doit.updated" => Ok(Self::DoitUpdated),
+            "doit.accepted" => Ok(Self::DoitAccepted),
+            "label.created" => Ok(Self::LabelCreated),
+            _ => Err(ErrorCode::InvalidWebhookEventType(value)),
+        }
+    }
+}
+
+impl Webhook {
+    pub fn new(
+        id: WebhookId,
+        url: WebhookUrl,
+        secret: WebhookSecret,
+        event_types: Vec<WebhookEventType>,
+        created_by: UserId,
+        created_at: DateTime,
+        updated_at: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            url,
+            secret,
+            event_types,
+            created_by,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn generate(
+        url: WebhookUrl,
+        event_types: Vec<WebhookEventType>,
+        created_by: UserId,
+    ) -> Self {
+        Self {
+            id: WebhookId::generate(),
+            url,
+            secret: WebhookSecret::generate(),
+            event_types,
+            created_by,
+            created_at: DateTime::now(),
+            updated_at: DateTime::now(),
+        }
+    }
+}
+
+// 登録されたすべての webhook に配送される出来事
+#[derive(Debug, Clone, Getters)]
+pub struct WebhookEvent {
+    #[getset(get = "pub")]
+    id: WebhookEventId,
+    #[getset(get = "pub")]
+    event_type: WebhookEventType,
+    #[getset(get = "pub")]
+    data: Value,
+    #[getset(get = "pub")]
+    occurred_at: DateTime,
+}
+
+value_object!(WebhookEventId(Uuid));
+
+impl WebhookEvent {
+    pub fn new(
+        id: WebhookEventId,
+        event_type: WebhookEventType,
+        data: Value,
+        occurred_at: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            event_type,
+            data,
+            occurred_at,
+        }
+    }
+
+    // NOTE: data は監査ログと同じ内容に id を加えたもの
+    pub fn generate<T: Auditable>(event_type: WebhookEventType, entity: &T) -> Self {
+        let mut data = entity.audit_snapshot();
+        if let Value::Object(map) = &mut data {
+            map.insert("id".to_string(), json!(entity.audit_id()));
+        }
+
+        Self {
+            id: WebhookEventId(Uuid::new_v4()),
+            event_type,
+            data,
+            occurred_at: DateTime::now(),
+        }
+    }
+
+    // 送信する body
+    pub fn payload(&self) -> Value {
+        json!({
+            "id": self.id.clone().value(),
+            "type": self.event_type.to_string(),
+            "occurred_at": self.occurred_at.clone().value().to_rfc3339(),
+            "data": self.data,
+        })
+    }
+}
+
+// 変更前と変更後の内容から、配送するイベントを決める
+pub type WebhookEventsFn<T> = fn(&T, &T) -> Vec<WebhookEvent>;
+
+#[derive(Debug, Clone, Getters)]
+pub struct WebhookDelivery {
+    #[getset(get = "pub")]
+    id: WebhookDeliveryId,
+    #[getset(get = "pub")]
+    webhook_id: WebhookId,
+    #[getset(get = "pub")]
+    event: WebhookEvent,
+    #[getset(get = "pub")]
+    status: WebhookDeliveryStatus,
+    // これまでの試行回数
+    #[getset(get = "pub")]
+    attempts: u32,
+    #[getset(get = "pub")]
+    next_attempt_at: DateTime,
+    #[getset(get = "pub")]
+    created_at: DateTime,
+    #[getset(get = "pub")]
+    updated_at: DateTime,
+}
+
+value_object!(WebhookDeliveryId(Uuid));
+
+impl TryFrom<String> for WebhookDeliveryId {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            Uuid::parse_str(&value).map_err(|_| ErrorCode::InvalidUuidFormat(value))?,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Succeeded,
+    // 最大の試行回数まで失敗した
+    Failed,
+}
+
+impl std::fmt::Display for WebhookDeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl WebhookDelivery {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: WebhookDeliveryId,
+        webhook_id: WebhookId,
+        event: WebhookEvent,
+        status: WebhookDeliveryStatus,
+        attempts: u32,
+        next_attempt_at: DateTime,
+        created_at: DateTime,
+        updated_at: DateTime,
+    ) -> Self {
+        Self {
+            id,
+            webhook_id,
+            event,
+            status,
+            attempts,
+            next_attempt_at,
+            created_at,
+            updated_at,
+        }
+    }
+
+    // now に取り出した配送を、次に取り出せるようになる時刻
+    pub fn lease_until(now: &DateTime) -> DateTime {
+        DateTime::new(now.clone().value() + chrono::Duration::seconds(WEBHOOK_DELIVERY_LEASE_SECS))
+    }
+
+    // now の時点で取り出せるか。送信中のものは lease_until を過ぎるまで取り出せない
+    pub fn is_due(&self, now: &DateTime) -> bool {
+        self.status == WebhookDeliveryStatus::Pending
+            && self.next_attempt_at.clone().value() <= now.clone().value()
+    }
+
+    // 試行の結果を反映する。失敗した場合は指数的に間隔をあけて再試行する
+    pub fn attempted(mut self, attempt: &WebhookDeliveryAttempt) -> Self {
+        self.attempts = *attempt.attempt();
+
+        if attempt.is_succeeded() {
+            self.status = WebhookDeliveryStatus::Succeeded;
+        } else if self.attempts >= WEBHOOK_DELIVERY_MAX_ATTEMPTS {
+            self.status = WebhookDeliveryStatus::Failed;
+        } else {
+            let backoff = WEBHOOK_DELIVERY_BASE_BACKOFF_SECS << (self.attempts - 1);
+            self.next_attempt_at = DateTime::new(
+                attempt.attempted_at().clone().value() + chrono::Duration::seconds(backoff),
+            );
+        }
+
+        self
+    }
+}
+
+// 1回の送信の記録
+#[derive(Debug, Clone, Getters)]
+pub struct WebhookDeliveryAttempt {
+    #[getset(get = "pub")]
+    delivery_id: WebhookDeliveryId,
+    // 1から始まる
+    #[getset(get = "pub")]
+    attempt: u32,
+    // 応答がなかった場合は None
+    #[getset(get = "pub")]
+    status_code: Option<u16>,
+    #[getset(get = "pub")]
+    error: Option<String>,
+    #[getset(get = "pub")]
+    attempted_at: DateTime,
+}
+
+impl WebhookDeliveryAttempt {
+    pub fn new(
+        delivery_id: WebhookDeliveryId,
+        attempt: u32,
+        status_code: Option<u16>,
+        error: Option<String>,
+        attempted_at: DateTime,
+    ) -> Self {
+        Self {
+            delivery_id,
+            attempt,
+            status_code,
+            error,
+            attempted_at,
+        }
+    }
+
+    pub fn is_succeeded(&self) -> bool {
+        matches!(self.status_code, Some(200..=299))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery() -> WebhookDelivery {
+        let event = WebhookEvent::new(
+            WebhookEventId::new(Uuid::new_v4()),
+            WebhookEventType::TodoCreated,
+            json!({}),
+            DateTime::now(),
+        );
+
+        WebhookDelivery::new(
+            WebhookDeliveryId::new(Uuid::new_v4()),
+            WebhookId::new(Uuid::new_v4()),
+            event,
+            WebhookDeliveryStatus::Pending,
+            0,
+            DateTime::now(),
+            DateTime::now(),
+            DateTime::now(),
+        )
+    }
+
+    fn attempt(delivery: &WebhookDelivery, status_code: Option<u16>) -> WebhookDeliveryAttempt {
+        WebhookDeliveryAttempt::new(
+            delivery.id().clone(),
+            delivery.attempts() + 1,
+            status_code,
+            None,
+            DateTime::from_timestamp(1_700_000_000).unwrap(),
+        )
+    }
+
+    fn seconds_until_next(delivery: &WebhookDelivery, attempt: &WebhookDeliveryAttempt) -> i64 {
+        (delivery.next_attempt_at().clone().value() - attempt.attempted_at().clone().value())
+            .num_seconds()
+    }
+
+    #[test]
+    fn succeeded_on_2xx() {
+        let delivery = delivery();
+        let attempt = attempt(&delivery, Some(204));
+
+        let delivery = delivery.attempted(&attempt);
+
+        assert_eq!(*delivery.status(), WebhookDeliveryStatus::Succeeded);
+        assert_eq!(*delivery.attempts(), 1);
+    }
+
+    #[test]
+    fn backoff_doubles_on_each_failure() {
+        let mut delivery = delivery();
+        let mut backoffs = Vec::new();
+
+        for status_code in [Some(500), None, Some(404)] {
+            let attempt = attempt(&delivery, status_code);
+            delivery = delivery.attempted(&attempt);
+
+            assert_eq!(*delivery.status(), WebhookDeliveryStatus::Pending);
+            backoffs.push(seconds_until_next(&delivery, &attempt));
+        }
+
+        assert_eq!(backoffs, vec![30, 60, 120]);
+    }
+
+    #[test]
+    fn in_flight_delivery_is_not_claimed_again() {
+        let claimed_at = DateTime::from_timestamp(1_700_000_000).unwrap();
+        // 取り出した配送は next_attempt_at が lease_until になる
+        let delivery = WebhookDelivery {
+            next_attempt_at: WebhookDelivery::lease_until(&claimed_at),
+            ..delivery()
+        };
+
+        // 送信がタイムアウトするまでは送信中なので取り出せない
+        let timed_out_at = DateTime::new(
+            claimed_at.clone().value()
+                + chrono::Duration::seconds(WEBHOOK_DELIVERY_TIMEOUT_SECS as i64),
+        );
+        assert!(!delivery.is_due(&claimed_at));
+        assert!(!delivery.is_due(&timed_out_at));
+
+        // 記録されないまま lease が切れた場合は取り出し直せる
+        assert!(delivery.is_due(&WebhookDelivery::lease_until(&claimed_at)));
+    }
+
+    #[test]
+    fn failed_after_max_attempts() {
+        let mut delivery = delivery();
+
+        for _ in 1..WEBHOOK_DELIVERY_MAX_ATTEMPTS {
+            let attempt = attempt(&delivery, Some(500));
+            delivery = delivery.attempted(&attempt);
+
+            assert_eq!(*delivery.status(), WebhookDeliveryStatus::Pending);
+        }
+
+        let attempt = attempt(&delivery, Some(500));
+        let delivery = delivery.attempted(&attempt);
+
+        assert_eq!(*delivery.status(), WebhookDeliveryStatus::Failed);
+        assert_eq!(*delivery.attempts(), WEBHOOK_DELIVERY_MAX_ATTEMPTS);
+    }
+}
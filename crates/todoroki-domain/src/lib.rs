@@ -1,5 +1,6 @@
 pub mod entities;
 pub mod repositories;
+pub mod services;
 pub mod value_objects;
 
 pub fn add(left: u64, right: u64) -> u64 {
@@ -1,8 +1,15 @@
+pub mod audit;
+pub mod change_stream;
+pub mod comment;
 pub mod doit;
 pub mod label;
+pub mod pagination;
+pub mod search;
 pub mod todo;
 pub mod user;
 pub mod user_auth;
+pub mod webhook;
+pub mod webhook_sender;
 
 pub trait Repositories: Send + Sync + 'static {
     type TodoRepositoryImpl: todo::TodoRepository;
@@ -10,10 +17,20 @@ pub trait Repositories: Send + Sync + 'static {
     type LabelRepositoryImpl: label::LabelRepository;
     type UserRepositoryImpl: user::UserRepository;
     type UserAuthRepositoryImpl: user_auth::UserAuthRepository;
+    type CommentRepositoryImpl: comment::CommentRepository;
+    type AuditRepositoryImpl: audit::AuditRepository;
+    type WebhookRepositoryImpl: webhook::WebhookRepository;
+    type WebhookSenderRepositoryImpl: webhook_sender::WebhookSenderRepository;
+    type ChangeStreamRepositoryImpl: change_stream::ChangeStreamRepository;
 
     fn todo_repository(&self) -> &Self::TodoRepositoryImpl;
     fn doit_repository(&self) -> &Self::DoitRepositoryImpl;
     fn label_repository(&self) -> &Self::LabelRepositoryImpl;
     fn user_repository(&self) -> &Self::UserRepositoryImpl;
     fn user_auth_repository(&self) -> &Self::UserAuthRepositoryImpl;
+    fn comment_repository(&self) -> &Self::CommentRepositoryImpl;
+    fn audit_repository(&self) -> &Self::AuditRepositoryImpl;
+    fn webhook_repository(&self) -> &Self::WebhookRepositoryImpl;
+    fn webhook_sender_repository(&self) -> &Self::WebhookSenderRepositoryImpl;
+    fn change_stream_repository(&self) -> &Self::ChangeStreamRepositoryImpl;
 }
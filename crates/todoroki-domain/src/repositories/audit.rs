@@ -0,0 +1,71 @@
+use getset::Getters;
+use thiserror;
+
+use crate::{
+    entities::{
+        audit::{AuditEntity, AuditEvent},
+        user::UserId,
+    },
+    repositories::pagination::{Page, PageRequest},
+    value_objects::datetime::DateTime,
+};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AuditRepositoryError {
+    #[error("Internal Error: {0:?}")]
+    InternalError(String),
+}
+
+// None のフィールドでは絞り込まない
+#[derive(Debug, Clone, Default, Getters)]
+pub struct AuditFilter {
+    #[getset(get = "pub")]
+    entity: Option<AuditEntity>,
+    #[getset(get = "pub")]
+    actor_id: Option<UserId>,
+    // [occurred_from, occurred_to)
+    #[getset(get = "pub")]
+    occurred_from: Option<DateTime>,
+    #[getset(get = "pub")]
+    occurred_to: Option<DateTime>,
+}
+
+impl AuditFilter {
+    pub fn new(
+        entity: Option<AuditEntity>,
+        actor_id: Option<UserId>,
+        occurred_from: Option<DateTime>,
+        occurred_to: Option<DateTime>,
+    ) -> Self {
+        Self {
+            entity,
+            actor_id,
+            occurred_from,
+            occurred_to,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum AuditSortKey {
+    #[default]
+    OccurredAt,
+}
+
+impl std::fmt::Display for AuditSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OccurredAt => write!(f, "occurred_at"),
+        }
+    }
+}
+
+// NOTE: 記録は各リポジトリでの変更と同じトランザクションで行う
+#[allow(async_fn_in_trait)]
+pub trait AuditRepository: Send + Sync + 'static {
+    async fn list(
+        &self,
+        filter: AuditFilter,
+        page: PageRequest<AuditSortKey>,
+    ) -> Result<Page<AuditEvent>, AuditRepositoryError>;
+}
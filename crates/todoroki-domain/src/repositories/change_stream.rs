@@ -0,0 +1,28 @@
+use thiserror;
+
+use crate::entities::change::{ChangeEvent, ChangeEventId};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ChangeStreamRepositoryError {
+    #[error("Internal Error: {0:?}")]
+    InternalError(String),
+}
+
+// NOTE: 変更は todo / doit / label の各リポジトリへの書き込みから配信される
+#[allow(async_fn_in_trait)]
+pub trait ChangeStreamRepository: Send + Sync + 'static {
+    type Subscription: ChangeSubscription;
+
+    // after より後の変更から購読する。None の場合はこれから起きる変更だけを受け取る
+    // NOTE: 保持している件数には上限があるため、古すぎる after からは途中の変更を受け取れないことがある
+    async fn subscribe(
+        &self,
+        after: Option<ChangeEventId>,
+    ) -> Result<Self::Subscription, ChangeStreamRepositoryError>;
+}
+
+#[allow(async_fn_in_trait)]
+pub trait ChangeSubscription: Send + 'static {
+    // 次の変更を待つ。購読を続けられなくなった場合は None
+    async fn next(&mut self) -> Option<ChangeEvent>;
+}
@@ -0,0 +1,27 @@
+use thiserror;
+
+use crate::entities::comment::{Comment, CommentBody, CommentId, CommentTarget};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum CommentRepositoryError {
+    #[error("Internal Error: {0:?}")]
+    InternalError(String),
+}
+
+#[allow(async_fn_in_trait)]
+pub trait CommentRepository: Send + Sync + 'static {
+    async fn create(&self, comment: Comment) -> Result<CommentId, CommentRepositoryError>;
+
+    async fn update(&self, id: CommentId, body: CommentBody) -> Result<(), CommentRepositoryError>;
+
+    async fn get_by_id(&self, id: CommentId) -> Result<Option<Comment>, CommentRepositoryError>;
+
+    // 作成日時の古い順。スレッドを保つため論理削除したものも含める
+    async fn list_by_target(
+        &self,
+        target: CommentTarget,
+    ) -> Result<Vec<Comment>, CommentRepositoryError>;
+
+    // 論理削除
+    async fn delete_by_id(&self, id: CommentId) -> Result<(), CommentRepositoryError>;
+}
@@ -1,6 +1,21 @@
+use getset::Getters;
 use thiserror;
 
-use crate::entities::doit::{Doit, DoitId, DoitUpdateCommand};
+use crate::{
+    entities::{
+        audit::AuditActor,
+        doit::{Doit, DoitAcceptance, DoitId, DoitReviewCommand, DoitStatus, DoitUpdateCommand},
+        todo::TodoId,
+        user::UserId,
+        webhook::{WebhookEvent, WebhookEventsFn},
+    },
+    repositories::{
+        label::LabelCondition,
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
+    },
+    value_objects::datetime::DateTime,
+};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum DoitRepositoryError {
@@ -8,15 +23,127 @@ pub enum DoitRepositoryError {
     InternalError(String),
 }
 
+// None のフィールドでは絞り込まない
+#[derive(Debug, Clone, Default, Getters)]
+pub struct DoitFilter {
+    #[getset(get = "pub")]
+    labels: Option<LabelCondition>,
+    #[getset(get = "pub")]
+    is_public: Option<bool>,
+    // [deadlined_from, deadlined_to)
+    #[getset(get = "pub")]
+    deadlined_from: Option<DateTime>,
+    #[getset(get = "pub")]
+    deadlined_to: Option<DateTime>,
+    #[getset(get = "pub")]
+    created_by: Option<UserId>,
+    #[getset(get = "pub")]
+    affects_to: Option<DoitAffectsToFilter>,
+    #[getset(get = "pub")]
+    status: Option<DoitStatus>,
+}
+
+#[derive(Debug, Clone)]
+pub enum DoitAffectsToFilter {
+    Unlinked,     // affects_to がない
+    Linked,       // affects_to がある
+    Todo(TodoId), // affects_to が指定の Todo
+}
+
+impl DoitFilter {
+    pub fn new(
+        labels: Option<LabelCondition>,
+        is_public: Option<bool>,
+        deadlined_from: Option<DateTime>,
+        deadlined_to: Option<DateTime>,
+        created_by: Option<UserId>,
+        affects_to: Option<DoitAffectsToFilter>,
+        status: Option<DoitStatus>,
+    ) -> Self {
+        Self {
+            labels,
+            is_public,
+            deadlined_from,
+            deadlined_to,
+            created_by,
+            affects_to,
+            status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum DoitSortKey {
+    CreatedAt,
+    #[default]
+    UpdatedAt,
+    Deadline, // deadlined_at がないものは常に末尾
+    Name,     // 非公開のものは代替名で並べる
+    Votes,    // 投票数
+}
+
+impl std::fmt::Display for DoitSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreatedAt => write!(f, "created_at"),
+            Self::UpdatedAt => write!(f, "updated_at"),
+            Self::Deadline => write!(f, "deadline"),
+            Self::Name => write!(f, "name"),
+            Self::Votes => write!(f, "votes"),
+        }
+    }
+}
+
 #[allow(async_fn_in_trait)]
 pub trait DoitRepository: Send + Sync + 'static {
-    async fn create(&self, doit: Doit) -> Result<DoitId, DoitRepositoryError>;
+    async fn create(
+        &self,
+        doit: Doit,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<DoitId, DoitRepositoryError>;
 
-    async fn update(&self, cmd: DoitUpdateCommand) -> Result<(), DoitRepositoryError>;
+    async fn update(
+        &self,
+        cmd: DoitUpdateCommand,
+        actor: AuditActor,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<(), DoitRepositoryError>;
 
     async fn get_by_id(&self, id: DoitId) -> Result<Option<Doit>, DoitRepositoryError>;
 
-    async fn list(&self) -> Result<Vec<Doit>, DoitRepositoryError>;
+    async fn review(
+        &self,
+        cmd: DoitReviewCommand,
+        actor: AuditActor,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<(), DoitRepositoryError>;
+
+    // NOTE: 1人1票なので、既に投票していれば何もしない
+    async fn vote(&self, id: DoitId, user_id: UserId) -> Result<(), DoitRepositoryError>;
+
+    async fn unvote(&self, id: DoitId, user_id: UserId) -> Result<(), DoitRepositoryError>;
+
+    // Todo の作成と affects_to の設定を同じトランザクションで行う
+    // NOTE: 既に受け入れ済みの場合は何もせず None を返す
+    // todo_events は作成した Todo のイベントで、既存の Todo に紐づける場合は空にする
+    async fn accept(
+        &self,
+        id: DoitId,
+        acceptance: DoitAcceptance,
+        actor: AuditActor,
+        todo_events: Vec<WebhookEvent>,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<Option<TodoId>, DoitRepositoryError>;
+
+    async fn list(
+        &self,
+        filter: DoitFilter,
+        page: PageRequest<DoitSortKey>,
+    ) -> Result<Page<Doit>, DoitRepositoryError>;
+
+    // 関連度の高い順
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Doit>, DoitRepositoryError>;
 
     async fn delete_by_id(&self, id: DoitId) -> Result<(), DoitRepositoryError>;
 }
@@ -1,6 +1,25 @@
 use thiserror;
 
-use crate::entities::label::{Label, LabelId};
+use crate::{
+    entities::{
+        audit::AuditActor,
+        label::{Label, LabelId},
+        webhook::WebhookEvent,
+    },
+    repositories::{
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
+    },
+};
+
+// Todo や Doit をラベルで絞り込む際の条件
+#[derive(Debug, Clone)]
+pub enum LabelCondition {
+    // いずれかのラベルが付いている
+    Any(Vec<LabelId>),
+    // すべてのラベルが付いている
+    All(Vec<LabelId>),
+}
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum LabelRepositoryError {
@@ -8,15 +27,44 @@ pub enum LabelRepositoryError {
     InternalError(String),
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LabelSortKey {
+    CreatedAt,
+    #[default]
+    UpdatedAt,
+    Name,
+}
+
+impl std::fmt::Display for LabelSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreatedAt => write!(f, "created_at"),
+            Self::UpdatedAt => write!(f, "updated_at"),
+            Self::Name => write!(f, "name"),
+        }
+    }
+}
+
 #[allow(async_fn_in_trait)]
 pub trait LabelRepository: Send + Sync + 'static {
-    async fn create(&self, label: Label) -> Result<LabelId, LabelRepositoryError>;
+    async fn create(
+        &self,
+        label: Label,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<LabelId, LabelRepositoryError>;
 
     // async fn update(&self, cmd: LabelUpdateCommand) -> Result<(), LabelRepositoryError>;
 
-    // async fn get_by_id(&self, id: LabelId) -> Result<Option<Label>, LabelRepositoryError>;
+    async fn get_by_id(&self, id: LabelId) -> Result<Option<Label>, LabelRepositoryError>;
+
+    async fn list(
+        &self,
+        page: PageRequest<LabelSortKey>,
+    ) -> Result<Page<Label>, LabelRepositoryError>;
 
-    async fn list(&self) -> Result<Vec<Label>, LabelRepositoryError>;
+    // 関連度の高い順
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Label>, LabelRepositoryError>;
 
     async fn delete_by_id(&self, id: LabelId) -> Result<(), LabelRepositoryError>;
 }
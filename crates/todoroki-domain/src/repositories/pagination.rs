@@ -0,0 +1,223 @@
+use std::fmt::Display;
+
+use getset::Getters;
+use uuid::Uuid;
+
+use crate::value_objects::error::ErrorCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Asc => write!(f, "asc"),
+            Self::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+// 一覧取得時の並び順とページ位置
+#[derive(Debug, Clone, Getters)]
+pub struct PageRequest<K> {
+    #[getset(get = "pub")]
+    sort: K,
+    #[getset(get = "pub")]
+    order: SortOrder,
+    // None の場合は件数を制限しない
+    #[getset(get = "pub")]
+    limit: Option<u32>,
+    #[getset(get = "pub")]
+    cursor: Option<PageCursor>,
+}
+
+// NOTE: 並び替えキーは Display の表現で cursor に記録する
+impl<K: Display> PageRequest<K> {
+    pub const MAX_LIMIT: u32 = 200;
+
+    // NOTE: cursor は取得したときと同じ並び順でなければ続きを指せないため、異なれば弾く
+    pub fn try_new(
+        sort: K,
+        order: SortOrder,
+        limit: u32,
+        cursor: Option<PageCursor>,
+    ) -> Result<Self, ErrorCode> {
+        if let Some(cursor) = &cursor {
+            if cursor.sort != sort.to_string() || cursor.order != order {
+                return Err(ErrorCode::CursorSortMismatch(
+                    format!("{}:{}", cursor.sort, cursor.order),
+                    format!("{sort}:{order}"),
+                ));
+            }
+        }
+
+        Ok(Self {
+            sort,
+            order,
+            limit: Some(limit.clamp(1, Self::MAX_LIMIT)),
+            cursor,
+        })
+    }
+
+    // 並び替えキーと ID から、このページの並び順での cursor を作る
+    pub fn cursor_for(&self, key: String, id: Uuid) -> PageCursor {
+        PageCursor {
+            sort: self.sort.to_string(),
+            order: self.order,
+            key,
+            id,
+        }
+    }
+}
+
+impl<K> PageRequest<K> {
+    pub fn is_desc(&self) -> bool {
+        self.order == SortOrder::Desc
+    }
+}
+
+impl<K: Default> Default for PageRequest<K> {
+    // 全件を既定の並び順で取得する
+    fn default() -> Self {
+        Self {
+            sort: K::default(),
+            order: SortOrder::default(),
+            limit: None,
+            cursor: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Getters)]
+pub struct Page<T> {
+    #[getset(get = "pub")]
+    items: Vec<T>,
+    #[getset(get = "pub")]
+    next_cursor: Option<PageCursor>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<PageCursor>) -> Self {
+        Self { items, next_cursor }
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    pub fn try_map<U, E>(self, f: impl FnMut(T) -> Result<U, E>) -> Result<Page<U>, E> {
+        Ok(Page {
+            items: self.items.into_iter().map(f).collect::<Result<_, _>>()?,
+            next_cursor: self.next_cursor,
+        })
+    }
+}
+
+// 直前のページの最後の要素の (並び替えキー, ID) と、そのページの並び順
+// キーの表現はリポジトリの実装が決める
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct PageCursor {
+    #[getset(get = "pub")]
+    sort: String,
+    #[getset(get = "pub")]
+    order: SortOrder,
+    #[getset(get = "pub")]
+    key: String,
+    #[getset(get = "pub")]
+    id: Uuid,
+}
+
+impl PageCursor {
+    // クライアントにとっては不透明な文字列にする
+    pub fn encode(&self) -> String {
+        format!(
+            "{}{}:{}:{}",
+            self.id.as_simple(),
+            self.sort,
+            self.order,
+            self.key
+        )
+        .bytes()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+    }
+}
+
+impl TryFrom<String> for PageCursor {
+    type Error = ErrorCode;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || ErrorCode::InvalidCursorFormat(value.clone());
+
+        if !value.len().is_multiple_of(2) || !value.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        if !decoded.is_char_boundary(32) {
+            return Err(invalid());
+        }
+
+        let (id, rest) = decoded.split_at(32);
+        let (sort, rest) = rest.split_once(':').ok_or_else(invalid)?;
+        let (order, key) = rest.split_once(':').ok_or_else(invalid)?;
+
+        Ok(Self {
+            sort: sort.to_string(),
+            order: match order {
+                "asc" => SortOrder::Asc,
+                "desc" => SortOrder::Desc,
+                _ => return Err(invalid()),
+            },
+            key: key.to_string(),
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::todo::TodoSortKey;
+
+    fn cursor(sort: TodoSortKey, order: SortOrder) -> PageCursor {
+        PageRequest::try_new(sort, order, 10, None)
+            .unwrap()
+            .cursor_for("name:with:colons".to_string(), Uuid::new_v4())
+    }
+
+    #[test]
+    fn cursor_round_trips_sort_and_order() {
+        let cursor = cursor(TodoSortKey::Name, SortOrder::Asc);
+
+        assert_eq!(PageCursor::try_from(cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn rejects_cursor_from_other_sort_or_order() {
+        let cursor = cursor(TodoSortKey::Name, SortOrder::Asc);
+
+        for (sort, order) in [
+            (TodoSortKey::CreatedAt, SortOrder::Asc),
+            (TodoSortKey::Name, SortOrder::Desc),
+        ] {
+            assert!(matches!(
+                PageRequest::try_new(sort, order, 10, Some(cursor.clone())),
+                Err(ErrorCode::CursorSortMismatch(_, _))
+            ));
+        }
+
+        assert!(PageRequest::try_new(TodoSortKey::Name, SortOrder::Asc, 10, Some(cursor)).is_ok());
+    }
+}
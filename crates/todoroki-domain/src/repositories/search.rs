@@ -0,0 +1,26 @@
+use getset::Getters;
+
+// 全文検索の条件
+#[derive(Debug, Clone, Getters)]
+pub struct SearchQuery {
+    #[getset(get = "pub")]
+    text: String,
+    // 非公開のものを本来の name や description でも検索するか
+    // false の場合、非公開のものは代替名とラベル名でのみ検索される
+    #[getset(get = "pub")]
+    include_private: bool,
+    #[getset(get = "pub")]
+    limit: u32,
+}
+
+impl SearchQuery {
+    pub const MAX_LIMIT: u32 = 100;
+
+    pub fn new(text: String, include_private: bool, limit: u32) -> Self {
+        Self {
+            text,
+            include_private,
+            limit: limit.clamp(1, Self::MAX_LIMIT),
+        }
+    }
+}
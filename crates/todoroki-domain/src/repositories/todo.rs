@@ -1,22 +1,192 @@
+use getset::Getters;
 use thiserror;
 
-use crate::entities::todo::{Todo, TodoId, TodoUpdateCommand};
+use crate::{
+    entities::{
+        audit::AuditActor,
+        todo::{
+            Todo, TodoId, TodoOccurrence, TodoStatus, TodoStatusTransition, TodoUpdateCommand,
+            TodoVersion,
+        },
+        webhook::{WebhookEvent, WebhookEventsFn},
+    },
+    repositories::{
+        label::LabelCondition,
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
+    },
+    value_objects::datetime::DateTime,
+};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum TodoRepositoryError {
     #[error("Internal Error: {0:?}")]
     InternalError(String),
+    // 検証した後に他のリクエストで status が変えられ、遷移できなくなった
+    #[error("Status Transition Invalid: {0} -> {1}")]
+    StatusTransitionInvalid(TodoStatus, TodoStatus),
+    // 検証した後に他のリクエストで親が付け替えられ、循環するようになった
+    #[error("Hierarchy Cycle: {0:?} -> {1:?}")]
+    HierarchyCycle(TodoId, TodoId),
+    // 検証した後に他のリクエストで blocker が加えられ、循環するようになった
+    #[error("Dependency Cycle: {0:?} -> {1:?}")]
+    DependencyCycle(TodoId, TodoId),
+    // 検証した後に他のリクエストで削除された
+    #[error("Todo Not Found: {0:?}")]
+    TodoNotFound(TodoId),
 }
 
+// None のフィールドでは絞り込まない
+#[derive(Debug, Clone, Default, Getters)]
+pub struct TodoFilter {
+    #[getset(get = "pub")]
+    labels: Option<LabelCondition>,
+    #[getset(get = "pub")]
+    status: Option<TodoStatus>,
+    #[getset(get = "pub")]
+    is_public: Option<bool>,
+    // [deadlined_from, deadlined_to)
+    #[getset(get = "pub")]
+    deadlined_from: Option<DateTime>,
+    #[getset(get = "pub")]
+    deadlined_to: Option<DateTime>,
+    // この todo の子のみ
+    #[getset(get = "pub")]
+    parent_id: Option<TodoId>,
+}
+
+impl TodoFilter {
+    pub fn new(
+        labels: Option<LabelCondition>,
+        status: Option<TodoStatus>,
+        is_public: Option<bool>,
+        deadlined_from: Option<DateTime>,
+        deadlined_to: Option<DateTime>,
+        parent_id: Option<TodoId>,
+    ) -> Self {
+        Self {
+            labels,
+            status,
+            is_public,
+            deadlined_from,
+            deadlined_to,
+            parent_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum TodoSortKey {
+    CreatedAt,
+    #[default]
+    UpdatedAt,
+    Deadline, // scheduled_at がないものは常に末尾
+    Name,     // 非公開のものは代替名で並べる
+}
+
+impl std::fmt::Display for TodoSortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreatedAt => write!(f, "created_at"),
+            Self::UpdatedAt => write!(f, "updated_at"),
+            Self::Deadline => write!(f, "deadline"),
+            Self::Name => write!(f, "name"),
+        }
+    }
+}
+
+// 変更するものは、actor による変更として同じトランザクションで監査ログにも記録する
+// NOTE: webhook のイベントは use-case で決めたものを渡し、同じトランザクションで配送を積む
 #[allow(async_fn_in_trait)]
 pub trait TodoRepository: Send + Sync + 'static {
-    async fn create(&self, todo: Todo) -> Result<TodoId, TodoRepositoryError>;
+    async fn create(
+        &self,
+        todo: Todo,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<TodoId, TodoRepositoryError>;
+
+    // status が変わる場合は遷移を記録し、変更後の内容を次の版として記録する
+    // NOTE: 更新時点の status から遷移できない場合は何も変更せず StatusTransitionInvalid を返す
+    // NOTE: 親や blocker を変える場合も、更新時点の内容で循環や削除を検証しなおす
+    // completes_descendants なら、完了にできる子孫もすべて同じトランザクションで完了にする
+    async fn update(
+        &self,
+        cmd: TodoUpdateCommand,
+        completes_descendants: bool,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError>;
+
+    // 論理削除されたものも含めて取得する
+    async fn get_by_id(&self, id: TodoId) -> Result<Option<Todo>, TodoRepositoryError>;
+
+    // 論理削除されたものは含まない
+    async fn list(
+        &self,
+        filter: TodoFilter,
+        page: PageRequest<TodoSortKey>,
+    ) -> Result<Page<Todo>, TodoRepositoryError>;
+
+    async fn list_deleted(&self) -> Result<Vec<Todo>, TodoRepositoryError>;
+
+    // 親から根までたどった祖先の ID を返す。論理削除されたものも含む
+    async fn list_ancestor_ids(&self, id: TodoId) -> Result<Vec<TodoId>, TodoRepositoryError>;
+
+    // blocker の blocker… と間接的なものまでたどった ID を返す。論理削除されたものも含む
+    async fn list_transitive_blocker_ids(
+        &self,
+        id: TodoId,
+    ) -> Result<Vec<TodoId>, TodoRepositoryError>;
+
+    // 論理削除されたものは含まない。関連度の高い順
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Todo>, TodoRepositoryError>;
+
+    async fn delete_by_id(
+        &self,
+        id: TodoId,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError>;
+
+    // 古い順
+    async fn list_status_transitions(
+        &self,
+        id: TodoId,
+    ) -> Result<Vec<TodoStatusTransition>, TodoRepositoryError>;
+
+    async fn restore_by_id(
+        &self,
+        id: TodoId,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError>;
 
-    async fn update(&self, cmd: TodoUpdateCommand) -> Result<(), TodoRepositoryError>;
+    // 既に完了済みの場合は何もしない
+    async fn complete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence: TodoOccurrence,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError>;
 
-    // async fn get_by_id(&self, id: TodoId) -> Result<Option<Todo>, TodoRepositoryError>;
+    // 完了していない場合は何もしない
+    async fn uncomplete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence_start: DateTime,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError>;
 
-    async fn list(&self) -> Result<Vec<Todo>, TodoRepositoryError>;
+    // 古い順
+    // NOTE: 版は update でのみ記録する。削除や復元、回の完了では内容が変わらないため記録しない
+    async fn list_versions(&self, id: TodoId) -> Result<Vec<TodoVersion>, TodoRepositoryError>;
 
-    async fn delete_by_id(&self, id: TodoId) -> Result<(), TodoRepositoryError>;
+    async fn get_version(
+        &self,
+        id: TodoId,
+        version: u32,
+    ) -> Result<Option<TodoVersion>, TodoRepositoryError>;
 }
@@ -1,6 +1,12 @@
 use thiserror;
 
-use crate::entities::user::{User, UserEmail, UserId};
+use crate::{
+    entities::{
+        audit::AuditActor,
+        user::{User, UserCalendarToken, UserEmail, UserId},
+    },
+    value_objects::datetime::Timezone,
+};
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum UserRepositoryError {
@@ -10,9 +16,28 @@ pub enum UserRepositoryError {
 
 #[allow(async_fn_in_trait)]
 pub trait UserRepository: Send + Sync + 'static {
-    async fn create(&self, user: User) -> Result<UserId, UserRepositoryError>;
+    async fn create(&self, user: User, actor: AuditActor) -> Result<UserId, UserRepositoryError>;
 
     async fn get_by_id(&self, id: UserId) -> Result<Option<User>, UserRepositoryError>;
 
     async fn get_by_email(&self, email: UserEmail) -> Result<Option<User>, UserRepositoryError>;
+
+    async fn get_by_calendar_token(
+        &self,
+        token: UserCalendarToken,
+    ) -> Result<Option<User>, UserRepositoryError>;
+
+    // None の場合はトークンを無効にする
+    async fn update_calendar_token(
+        &self,
+        id: UserId,
+        token: Option<UserCalendarToken>,
+    ) -> Result<(), UserRepositoryError>;
+
+    async fn update_timezone(
+        &self,
+        id: UserId,
+        timezone: Timezone,
+        actor: AuditActor,
+    ) -> Result<(), UserRepositoryError>;
 }
@@ -0,0 +1,72 @@
+use thiserror;
+
+use crate::{
+    entities::webhook::{
+        Webhook, WebhookDelivery, WebhookDeliveryAttempt, WebhookDeliveryId, WebhookId,
+    },
+    repositories::pagination::{Page, PageRequest},
+    value_objects::datetime::DateTime,
+};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum WebhookRepositoryError {
+    #[error("Internal Error: {0:?}")]
+    InternalError(String),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub enum WebhookDeliverySortKey {
+    #[default]
+    CreatedAt,
+}
+
+impl std::fmt::Display for WebhookDeliverySortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreatedAt => write!(f, "created_at"),
+        }
+    }
+}
+
+// NOTE: 配送は use-case で決めたイベントについて、各リポジトリでの変更と同じトランザクションで積む
+#[allow(async_fn_in_trait)]
+pub trait WebhookRepository: Send + Sync + 'static {
+    async fn create(&self, webhook: Webhook) -> Result<WebhookId, WebhookRepositoryError>;
+
+    async fn list(&self) -> Result<Vec<Webhook>, WebhookRepositoryError>;
+
+    async fn get_by_id(&self, id: WebhookId) -> Result<Option<Webhook>, WebhookRepositoryError>;
+
+    // 配送待ちのものも含めて削除する
+    async fn delete_by_id(&self, id: WebhookId) -> Result<(), WebhookRepositoryError>;
+
+    // 送信時刻を過ぎた配送を1件取り出す。取り出したものは lease_until まで他から取り出されない
+    async fn claim_due_delivery(
+        &self,
+        lease_until: DateTime,
+    ) -> Result<Option<(Webhook, WebhookDelivery)>, WebhookRepositoryError>;
+
+    // 試行の記録と、それを反映した配送の状態を保存する
+    async fn record_attempt(
+        &self,
+        delivery: WebhookDelivery,
+        attempt: WebhookDeliveryAttempt,
+    ) -> Result<(), WebhookRepositoryError>;
+
+    async fn list_deliveries(
+        &self,
+        webhook_id: WebhookId,
+        page: PageRequest<WebhookDeliverySortKey>,
+    ) -> Result<Page<WebhookDelivery>, WebhookRepositoryError>;
+
+    async fn get_delivery_by_id(
+        &self,
+        id: WebhookDeliveryId,
+    ) -> Result<Option<WebhookDelivery>, WebhookRepositoryError>;
+
+    // 古い順
+    async fn list_attempts(
+        &self,
+        delivery_id: WebhookDeliveryId,
+    ) -> Result<Vec<WebhookDeliveryAttempt>, WebhookRepositoryError>;
+}
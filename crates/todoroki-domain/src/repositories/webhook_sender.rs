@@ -0,0 +1,19 @@
+use thiserror;
+
+use crate::entities::webhook::{Webhook, WebhookDelivery, WebhookDeliveryAttempt};
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum WebhookSenderRepositoryError {
+    #[error("Internal Error: {0:?}")]
+    InternalError(String),
+}
+
+#[allow(async_fn_in_trait)]
+pub trait WebhookSenderRepository: Send + Sync + 'static {
+    // NOTE: 送信先の失敗や応答がないことはエラーにせず、試行の記録として返す
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookDeliveryAttempt, WebhookSenderRepositoryError>;
+}
@@ -0,0 +1,124 @@
+use crate::{
+    entities::todo::{Todo, TodoId, TodoStatus},
+    value_objects::error::ErrorCode,
+};
+
+// id の blocker に blocker を加えても循環しないか検証する
+// NOTE: blocker_blockers は blocker が間接的に待っているものまで含めた ID
+pub fn validate_blocker(
+    id: &TodoId,
+    blocker: &Todo,
+    blocker_blockers: &[TodoId],
+) -> Result<(), ErrorCode> {
+    if blocker.is_deleted() {
+        return Err(ErrorCode::TodoNotFound(blocker.id().clone()));
+    }
+
+    if id == blocker.id() || blocker_blockers.contains(id) {
+        return Err(ErrorCode::TodoDependencyCycle(
+            id.clone(),
+            blocker.id().clone(),
+        ));
+    }
+
+    Ok(())
+}
+
+// blocker が完了していない todo は始められない
+pub fn validate_start(todo: &Todo, to: &TodoStatus) -> Result<(), ErrorCode> {
+    if *to != TodoStatus::OnProgress || !todo.is_blocked() {
+        return Ok(());
+    }
+
+    Err(ErrorCode::TodoBlocked(
+        todo.id().clone(),
+        todo.blockers()
+            .iter()
+            .filter(|blocker| *blocker.status() != TodoStatus::Completed)
+            .map(|blocker| blocker.id().clone())
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::todo::{TodoDescription, TodoName, TodoProgress, TodoPublishment},
+        value_objects::datetime::DateTime,
+    };
+
+    fn todo(deleted: bool) -> Todo {
+        Todo::new(
+            TodoId::new(uuid::Uuid::new_v4()),
+            TodoName::new("todo".to_string()),
+            TodoDescription::new(String::new()),
+            TodoPublishment::Public,
+            None,
+            vec![],
+            vec![],
+            vec![],
+            TodoStatus::NotStarted,
+            TodoProgress::new(0, 0),
+            vec![],
+            None,
+            None,
+            None,
+            DateTime::now(),
+            DateTime::now(),
+            deleted.then(DateTime::now),
+        )
+    }
+
+    fn is_cycle(result: Result<(), ErrorCode>) -> bool {
+        matches!(result, Err(ErrorCode::TodoDependencyCycle(_, _)))
+    }
+
+    #[test]
+    fn accepts_unrelated_blocker() {
+        let (id, blocker) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+
+        assert!(validate_blocker(&id, &blocker, &[todo(false).id().clone()]).is_ok());
+    }
+
+    #[test]
+    fn rejects_self_blocker() {
+        let blocker = todo(false);
+
+        assert!(is_cycle(validate_blocker(blocker.id(), &blocker, &[])));
+    }
+
+    #[test]
+    fn rejects_two_cycle() {
+        // blocker が id を待っているとき、id が blocker を待つようにする
+        let (id, blocker) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+
+        assert!(is_cycle(validate_blocker(
+            &id,
+            &blocker,
+            std::slice::from_ref(&id)
+        )));
+    }
+
+    #[test]
+    fn rejects_deep_cycle() {
+        let (id, blocker) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+        let blocker_blockers = [
+            todo(false).id().clone(),
+            todo(false).id().clone(),
+            id.clone(),
+        ];
+
+        assert!(is_cycle(validate_blocker(&id, &blocker, &blocker_blockers)));
+    }
+
+    #[test]
+    fn rejects_deleted_blocker() {
+        let (id, blocker) = (TodoId::new(uuid::Uuid::new_v4()), todo(true));
+
+        assert!(matches!(
+            validate_blocker(&id, &blocker, &[]),
+            Err(ErrorCode::TodoNotFound(blocker_id)) if blocker_id == *blocker.id()
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    entities::todo::{Todo, TodoId},
+    value_objects::error::ErrorCode,
+};
+
+// id の親を parent にしても循環しないか検証する
+// NOTE: parent_ancestors は parent の祖先 (parent 自身は含まない)
+pub fn validate_parent(
+    id: &TodoId,
+    parent: &Todo,
+    parent_ancestors: &[TodoId],
+) -> Result<(), ErrorCode> {
+    if parent.is_deleted() {
+        return Err(ErrorCode::TodoNotFound(parent.id().clone()));
+    }
+
+    if id == parent.id() || parent_ancestors.contains(id) {
+        return Err(ErrorCode::TodoHierarchyCycle(
+            id.clone(),
+            parent.id().clone(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        entities::todo::{TodoDescription, TodoName, TodoProgress, TodoPublishment, TodoStatus},
+        value_objects::datetime::DateTime,
+    };
+
+    fn todo(deleted: bool) -> Todo {
+        Todo::new(
+            TodoId::new(uuid::Uuid::new_v4()),
+            TodoName::new("todo".to_string()),
+            TodoDescription::new(String::new()),
+            TodoPublishment::Public,
+            None,
+            vec![],
+            vec![],
+            vec![],
+            TodoStatus::NotStarted,
+            TodoProgress::new(0, 0),
+            vec![],
+            None,
+            None,
+            None,
+            DateTime::now(),
+            DateTime::now(),
+            deleted.then(DateTime::now),
+        )
+    }
+
+    fn is_cycle(result: Result<(), ErrorCode>) -> bool {
+        matches!(result, Err(ErrorCode::TodoHierarchyCycle(_, _)))
+    }
+
+    #[test]
+    fn accepts_parent_outside_own_subtree() {
+        let (id, parent) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+        let grandparent = todo(false);
+
+        assert!(validate_parent(&id, &parent, &[grandparent.id().clone()]).is_ok());
+    }
+
+    #[test]
+    fn rejects_self_parent() {
+        let parent = todo(false);
+
+        assert!(is_cycle(validate_parent(parent.id(), &parent, &[])));
+    }
+
+    #[test]
+    fn rejects_two_cycle() {
+        // parent の親が id であるとき、id の親を parent にする
+        let (id, parent) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+
+        assert!(is_cycle(validate_parent(
+            &id,
+            &parent,
+            std::slice::from_ref(&id)
+        )));
+    }
+
+    #[test]
+    fn rejects_deep_cycle() {
+        let (id, parent) = (TodoId::new(uuid::Uuid::new_v4()), todo(false));
+        let ancestors = [
+            todo(false).id().clone(),
+            todo(false).id().clone(),
+            id.clone(),
+        ];
+
+        assert!(is_cycle(validate_parent(&id, &parent, &ancestors)));
+    }
+
+    #[test]
+    fn rejects_deleted_parent() {
+        let (id, parent) = (TodoId::new(uuid::Uuid::new_v4()), todo(true));
+
+        assert!(matches!(
+            validate_parent(&id, &parent, &[]),
+            Err(ErrorCode::TodoNotFound(parent_id)) if parent_id == *parent.id()
+        ));
+    }
+}
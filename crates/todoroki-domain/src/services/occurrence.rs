@@ -0,0 +1,528 @@
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime};
+
+use crate::{
+    entities::todo::{Todo, TodoOccurrence, TodoSchedule},
+    value_objects::{
+        datetime::{DateTime, Timezone},
+        error::ErrorCode,
+        recurrence::{RecurrenceFrequency, RecurrenceRule, RecurrenceWeekday},
+    },
+};
+
+// 一度に展開できる期間の上限
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+// RRULE を最初の回から辿る際に調べる期間 (FREQ の単位) の数の上限
+pub const MAX_RECURRENCE_PERIODS: u32 = 100_000;
+
+pub fn validate_range(from: &DateTime, to: &DateTime) -> Result<(), ErrorCode> {
+    let duration = to.clone().value() - from.clone().value();
+
+    if duration <= chrono::Duration::zero() || chrono::Duration::days(MAX_RANGE_DAYS) < duration {
+        return Err(ErrorCode::OccurrenceRangeInvalid(from.clone(), to.clone()));
+    }
+
+    Ok(())
+}
+
+// todo のすべてのスケジュールを展開し、開始日時の昇順で返す
+pub fn expand_todo(todo: &Todo, from: &DateTime, to: &DateTime) -> Vec<TodoOccurrence> {
+    let mut occurrences = todo
+        .schedules()
+        .iter()
+        .flat_map(|schedule| expand_schedule(schedule, from, to))
+        .collect::<Vec<_>>();
+
+    occurrences.sort_by_key(|occurrence| occurrence.starts_at().clone().value());
+
+    occurrences
+}
+
+// [from, to) と重なる回を開始日時の昇順で返す
+// NOTE: 繰り返しはスケジュールのタイムゾーンでの日付と時刻で数える
+// NOTE: 月に存在しない日付 (2月30日など) はその月の末日に丸める
+pub fn expand_schedule(
+    schedule: &TodoSchedule,
+    from: &DateTime,
+    to: &DateTime,
+) -> Vec<TodoOccurrence> {
+    let tz = schedule.timezone();
+    let (local_from, local_to) = (tz.to_local(from), tz.to_local(to));
+    let (from, to) = (from.clone().value(), to.clone().value());
+
+    let to_utc = |(start, end): (NaiveDateTime, NaiveDateTime)| {
+        (tz.to_utc(start).value(), tz.to_utc(end).value())
+    };
+
+    let candidates: Vec<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> =
+        match schedule {
+            TodoSchedule::Once(starts_at, ends_at) => {
+                vec![(starts_at.clone().value(), ends_at.clone().value())]
+            }
+            TodoSchedule::Daily(starts_at, ends_at, _) => {
+                let (starts_at, ends_at) = (starts_at.clone().value(), ends_at.clone().value());
+
+                days_between(local_from.date() - Days::new(1), local_to.date())
+                    .map(|date| {
+                        let start = date.and_time(starts_at);
+                        let end = date.and_time(ends_at);
+
+                        (
+                            start,
+                            if end <= start {
+                                end + Days::new(1)
+                            } else {
+                                end
+                            },
+                        )
+                    })
+                    .map(to_utc)
+                    .collect()
+            }
+            TodoSchedule::Weekly(starts_at, ends_at, _) => {
+                let offset = chrono::Duration::days(
+                    (ends_at.weekday().num_days_from_monday() as i64
+                        - starts_at.weekday().num_days_from_monday() as i64)
+                        .rem_euclid(7),
+                ) + (ends_at.time() - starts_at.time());
+                let duration = if offset <= chrono::Duration::zero() {
+                    offset + chrono::Duration::weeks(1)
+                } else {
+                    offset
+                };
+
+                days_between(local_from.date() - Days::new(7), local_to.date())
+                    .filter(|date| date.weekday() == starts_at.weekday())
+                    .map(|date| {
+                        let start = date.and_time(starts_at.time());
+
+                        (start, start + duration)
+                    })
+                    .map(to_utc)
+                    .collect()
+            }
+            TodoSchedule::Monthly(starts_at, ends_at, _) => {
+                let first = first_day_of_month(local_from.date()) - Months::new(1);
+
+                (0..)
+                    .map_while(|i| {
+                        first
+                            .checked_add_months(Months::new(i))
+                            .filter(|month| *month <= local_to.date())
+                    })
+                    .map(|month| {
+                        let start =
+                            date_in_month(month, starts_at.date()).and_time(starts_at.time());
+                        let end = date_in_month(month, ends_at.date()).and_time(ends_at.time());
+                        let end = if end <= start {
+                            date_in_month(month + Months::new(1), ends_at.date())
+                                .and_time(ends_at.time())
+                        } else {
+                            end
+                        };
+
+                        (start, end)
+                    })
+                    .map(to_utc)
+                    .collect()
+            }
+            TodoSchedule::Recurrence(starts_at, ends_at, rule, _) => {
+                let duration = ends_at.clone().value() - starts_at.clone().value();
+
+                recurrence_starts(tz.to_local(starts_at), rule, &tz, local_to)
+                    .into_iter()
+                    .map(|start| {
+                        let start = tz.to_utc(start).value();
+
+                        (start, start + duration)
+                    })
+                    .collect()
+            }
+        };
+
+    candidates
+        .into_iter()
+        .filter(|(start, end)| *start < to && from < *end)
+        .map(|(start, end)| TodoOccurrence::new(DateTime::new(start), DateTime::new(end)))
+        .collect()
+}
+
+// RRULE に従う回の開始日時 (タイムゾーンでの日時) のうち、to より前のものを昇順で返す
+// NOTE: DTSTART (最初の回の開始日時) は規則に合う場合のみ回として数える
+fn recurrence_starts(
+    starts_at: NaiveDateTime,
+    rule: &RecurrenceRule,
+    tz: &Timezone,
+    to: NaiveDateTime,
+) -> Vec<NaiveDateTime> {
+    let until = rule.until().as_ref().map(|until| tz.to_local(until));
+    let exdates = rule
+        .exdates()
+        .iter()
+        .map(|exdate| tz.to_local(exdate))
+        .collect::<Vec<_>>();
+
+    let mut starts = Vec::new();
+    let mut count = 0;
+
+    for i in 0..MAX_RECURRENCE_PERIODS {
+        let Some((period_start, dates)) = recurrence_period(starts_at.date(), rule, i) else {
+            break;
+        };
+
+        if to.date() < period_start || until.is_some_and(|until| until.date() < period_start) {
+            break;
+        }
+
+        for date in dates {
+            let start = date.and_time(starts_at.time());
+
+            if start < starts_at {
+                continue;
+            }
+            if to <= start || until.is_some_and(|until| until < start) {
+                return starts;
+            }
+
+            // NOTE: EXDATE で除外した回も COUNT には数える
+            count += 1;
+            if rule.count().is_some_and(|max| max < count) {
+                return starts;
+            }
+
+            if !exdates.contains(&start) {
+                starts.push(start);
+            }
+        }
+    }
+
+    starts
+}
+
+// i 番目の期間の初日と、その期間で規則に合う日付を昇順で返す
+fn recurrence_period(
+    anchor: NaiveDate,
+    rule: &RecurrenceRule,
+    i: u32,
+) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+    let step = i.checked_mul(*rule.interval())?;
+
+    let (period_start, mut dates) = match rule.frequency() {
+        RecurrenceFrequency::Daily => {
+            let date = anchor.checked_add_days(Days::new(step as u64))?;
+
+            (date, vec![date])
+        }
+        RecurrenceFrequency::Weekly => {
+            let offset = (anchor.weekday().num_days_from_monday() + 7
+                - rule.week_start().num_days_from_monday())
+                % 7;
+            let week_start =
+                (anchor - Days::new(offset as u64)).checked_add_days(Days::new(step as u64 * 7))?;
+            let weekdays = if rule.by_day().is_empty() {
+                vec![anchor.weekday()]
+            } else {
+                rule.by_day().iter().map(|d| *d.weekday()).collect()
+            };
+
+            (
+                week_start,
+                days_between(week_start, week_start + Days::new(6))
+                    .filter(|date| weekdays.contains(&date.weekday()))
+                    .collect(),
+            )
+        }
+        RecurrenceFrequency::Monthly => {
+            let month = first_day_of_month(anchor).checked_add_months(Months::new(step))?;
+
+            (month, dates_in_month(month, anchor, rule))
+        }
+        RecurrenceFrequency::Yearly => {
+            let year = NaiveDate::from_ymd_opt(anchor.year().checked_add(step as i32)?, 1, 1)?;
+
+            let dates = if !rule.by_month().is_empty() || !rule.by_month_day().is_empty() {
+                (1..=12)
+                    .filter(|m| rule.by_month().is_empty() || rule.by_month().contains(m))
+                    .filter_map(|m| year.with_month(m))
+                    .flat_map(|month| dates_in_month(month, anchor, rule))
+                    .collect()
+            } else if !rule.by_day().is_empty() {
+                // NOTE: BYMONTH がなければ、序数は年の中での位置とする
+                let last = NaiveDate::from_ymd_opt(year.year(), 12, 31)?;
+
+                days_between(year, last)
+                    .filter(|date| matches_by_day(*date, rule.by_day(), year, last))
+                    .collect()
+            } else {
+                year.with_month(anchor.month())
+                    .and_then(|month| month.with_day(anchor.day()))
+                    .into_iter()
+                    .collect()
+            };
+
+            (year, dates)
+        }
+    };
+
+    // NOTE: DAILY と WEEKLY では BY* は絞り込みとして働く
+    if matches!(
+        rule.frequency(),
+        RecurrenceFrequency::Daily | RecurrenceFrequency::Weekly
+    ) {
+        dates.retain(|date| {
+            (rule.by_month().is_empty() || rule.by_month().contains(&date.month()))
+                && (rule.by_month_day().is_empty()
+                    || rule
+                        .by_month_day()
+                        .iter()
+                        .any(|d| matches_month_day(*date, *d)))
+                && (rule.by_day().is_empty()
+                    || rule.by_day().iter().any(|d| *d.weekday() == date.weekday()))
+        });
+    }
+
+    Some((period_start, select_set_pos(dates, rule.by_set_pos())))
+}
+
+// MONTHLY の1か月分、または YEARLY で月を指定した場合の1か月分の日付
+fn dates_in_month(month: NaiveDate, anchor: NaiveDate, rule: &RecurrenceRule) -> Vec<NaiveDate> {
+    if !rule.by_month().is_empty() && !rule.by_month().contains(&month.month()) {
+        return Vec::new();
+    }
+
+    // NOTE: 月に存在しない日付の回は飛ばす (RFC 5545 の通り)
+    if rule.by_month_day().is_empty() && rule.by_day().is_empty() {
+        return month.with_day(anchor.day()).into_iter().collect();
+    }
+
+    let first = first_day_of_month(month);
+    let last = first + Months::new(1) - Days::new(1);
+
+    days_between(first, last)
+        .filter(|date| {
+            rule.by_month_day().is_empty()
+                || rule
+                    .by_month_day()
+                    .iter()
+                    .any(|d| matches_month_day(*date, *d))
+        })
+        .filter(|date| {
+            rule.by_day().is_empty() || matches_by_day(*date, rule.by_day(), first, last)
+        })
+        .collect()
+}
+
+fn matches_month_day(date: NaiveDate, month_day: i8) -> bool {
+    let last = (first_day_of_month(date) + Months::new(1) - Days::new(1)).day() as i64;
+    let day = if month_day < 0 {
+        last + month_day as i64 + 1
+    } else {
+        month_day as i64
+    };
+
+    date.day() as i64 == day
+}
+
+// [first, last] の中での序数も含めて BYDAY に合うか
+fn matches_by_day(
+    date: NaiveDate,
+    by_day: &[RecurrenceWeekday],
+    first: NaiveDate,
+    last: NaiveDate,
+) -> bool {
+    by_day.iter().any(|d| {
+        *d.weekday() == date.weekday()
+            && match d.ordinal() {
+                None => true,
+                Some(n) if 0 < *n => (date - first).num_days() / 7 + 1 == *n as i64,
+                Some(n) => -((last - date).num_days() / 7 + 1) == *n as i64,
+            }
+    })
+}
+
+fn select_set_pos(dates: Vec<NaiveDate>, by_set_pos: &[i16]) -> Vec<NaiveDate> {
+    if by_set_pos.is_empty() {
+        return dates;
+    }
+
+    let len = dates.len() as i64;
+    let mut selected = by_set_pos
+        .iter()
+        .map(|pos| {
+            if 0 < *pos {
+                *pos as i64 - 1
+            } else {
+                len + *pos as i64
+            }
+        })
+        .filter(|i| (0..len).contains(i))
+        .map(|i| dates[i as usize])
+        .collect::<Vec<_>>();
+
+    selected.sort();
+    selected.dedup();
+
+    selected
+}
+
+fn days_between(from: NaiveDate, to: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    from.iter_days().take_while(move |date| *date <= to)
+}
+
+fn first_day_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn date_in_month(month: NaiveDate, date: u8) -> NaiveDate {
+    let last = (first_day_of_month(month) + Months::new(1) - Days::new(1)).day();
+
+    month.with_day((date as u32).min(last)).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> DateTime {
+        DateTime::new(
+            chrono::DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        )
+    }
+
+    fn recurrence(starts_at: &str, rule: &str, exdates: &[&str], tz: &str) -> TodoSchedule {
+        let starts_at = datetime(starts_at);
+        let ends_at = DateTime::new(starts_at.clone().value() + chrono::Duration::hours(1));
+        let rule = RecurrenceRule::try_new(rule, exdates.iter().map(|d| datetime(d)).collect())
+            .unwrap_or_else(|_| panic!("invalid rule: {rule}"));
+
+        TodoSchedule::Recurrence(starts_at, ends_at, rule, Timezone::new(tz.parse().unwrap()))
+    }
+
+    fn starts(schedule: &TodoSchedule, from: &str, to: &str) -> Vec<String> {
+        expand_schedule(schedule, &datetime(from), &datetime(to))
+            .iter()
+            .map(|o| o.starts_at().clone().value().to_rfc3339())
+            .collect()
+    }
+
+    #[test]
+    fn last_weekday_of_month() {
+        let schedule = recurrence(
+            "2025-01-31T09:00:00Z",
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            &[],
+            "UTC",
+        );
+
+        assert_eq!(
+            starts(&schedule, "2025-01-01T00:00:00Z", "2025-07-01T00:00:00Z"),
+            vec![
+                "2025-01-31T09:00:00+00:00",
+                "2025-02-28T09:00:00+00:00",
+                "2025-03-31T09:00:00+00:00",
+                "2025-04-30T09:00:00+00:00",
+                // 31日は土曜日
+                "2025-05-30T09:00:00+00:00",
+                "2025-06-30T09:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn month_day_31_skips_short_months() {
+        let expected = vec![
+            "2025-01-31T09:00:00+00:00",
+            "2025-03-31T09:00:00+00:00",
+            "2025-05-31T09:00:00+00:00",
+            "2025-07-31T09:00:00+00:00",
+            "2025-08-31T09:00:00+00:00",
+        ];
+
+        for rule in ["FREQ=MONTHLY;BYMONTHDAY=31", "FREQ=MONTHLY"] {
+            let schedule = recurrence("2025-01-31T09:00:00Z", rule, &[], "UTC");
+
+            assert_eq!(
+                starts(&schedule, "2025-01-01T00:00:00Z", "2025-09-01T00:00:00Z"),
+                expected,
+                "{rule}"
+            );
+        }
+    }
+
+    #[test]
+    fn count_includes_exdates() {
+        let schedule = recurrence(
+            "2025-03-01T09:00:00Z",
+            "FREQ=DAILY;COUNT=5",
+            &["2025-03-02T09:00:00Z", "2025-03-04T09:00:00Z"],
+            "UTC",
+        );
+
+        assert_eq!(
+            starts(&schedule, "2025-03-01T00:00:00Z", "2025-04-01T00:00:00Z"),
+            vec![
+                "2025-03-01T09:00:00+00:00",
+                "2025-03-03T09:00:00+00:00",
+                "2025-03-05T09:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_local_time_across_dst() {
+        // 2025-03-09 02:00 に EST (-05:00) から EDT (-04:00) になる
+        let schedule = recurrence(
+            "2025-03-08T14:00:00Z",
+            "FREQ=DAILY",
+            &[],
+            "America/New_York",
+        );
+
+        assert_eq!(
+            starts(&schedule, "2025-03-08T00:00:00Z", "2025-03-11T00:00:00Z"),
+            vec![
+                "2025-03-08T14:00:00+00:00",
+                "2025-03-09T13:00:00+00:00",
+                "2025-03-10T13:00:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn nonexistent_local_time_is_shifted_an_hour_later() {
+        // 2025-03-09 の 02:30 は存在しないので 03:30 (EDT) になる
+        let schedule = recurrence(
+            "2025-03-08T07:30:00Z",
+            "FREQ=DAILY",
+            &[],
+            "America/New_York",
+        );
+
+        assert_eq!(
+            starts(&schedule, "2025-03-08T00:00:00Z", "2025-03-11T00:00:00Z"),
+            vec![
+                "2025-03-08T07:30:00+00:00",
+                "2025-03-09T07:30:00+00:00",
+                "2025-03-10T06:30:00+00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn ambiguous_local_time_is_the_earlier_one() {
+        // 2025-11-02 02:00 に EDT から EST に戻るので 01:30 は2回ある
+        let tz = Timezone::new(chrono_tz::America::New_York);
+        let local = NaiveDate::from_ymd_opt(2025, 11, 2)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+
+        let utc = tz.to_utc(local);
+
+        assert_eq!(utc, datetime("2025-11-02T05:30:00Z"));
+        assert_eq!(tz.to_local(&utc), local);
+        assert_eq!(tz.to_local(&datetime("2025-11-02T06:30:00Z")), local);
+    }
+}
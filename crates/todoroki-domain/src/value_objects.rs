@@ -1,3 +1,4 @@
 pub mod datetime;
 pub mod error;
 pub mod permission;
+pub mod recurrence;
@@ -1,13 +1,125 @@
-use chrono::{Datelike, TimeZone, Timelike};
+use chrono::{Datelike, LocalResult, Offset, TimeZone, Timelike};
+use chrono_tz::OffsetComponents;
 
 use crate::{value_object, value_objects::error::ErrorCode};
 
 value_object!(DateTime(chrono::DateTime<chrono::Utc>));
+// IANA のタイムゾーン (例: Asia/Tokyo)
+value_object!(Timezone(chrono_tz::Tz));
 
 impl DateTime {
     pub fn now() -> Self {
         Self(chrono::Utc::now())
     }
+
+    // UNIX 時間 (秒) から。表せない範囲であれば None
+    pub fn from_timestamp(secs: i64) -> Option<Self> {
+        chrono::DateTime::from_timestamp(secs, 0).map(Self)
+    }
+}
+
+impl Timezone {
+    pub fn utc() -> Self {
+        Self(chrono_tz::UTC)
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    pub fn to_local(&self, value: &DateTime) -> chrono::NaiveDateTime {
+        value.0.with_timezone(&self.0).naive_local()
+    }
+
+    // このタイムゾーンでの日時を UTC にする
+    // NOTE: 夏時間で重複する時刻は早い方、存在しない時刻は1時間後とする
+    pub fn to_utc(&self, local: chrono::NaiveDateTime) -> DateTime {
+        let resolved = match self.0.from_local_datetime(&local) {
+            LocalResult::Single(t) | LocalResult::Ambiguous(t, _) => t,
+            LocalResult::None => self
+                .0
+                .from_local_datetime(&(local + chrono::Duration::hours(1)))
+                .earliest()
+                .unwrap_or_else(|| self.0.from_utc_datetime(&local)),
+        };
+
+        DateTime(resolved.with_timezone(&chrono::Utc))
+    }
+
+    // その時点での UTC からのずれ (秒)
+    pub fn utc_offset(&self, value: &DateTime) -> i32 {
+        self.offset_at(value.0.timestamp()).0
+    }
+
+    pub fn is_dst(&self, value: &DateTime) -> bool {
+        self.offset_at(value.0.timestamp()).1
+    }
+
+    // from から to までに、UTC からのずれか夏時間かどうかが切り替わる時点
+    // NOTE: 1日ごとに調べ、変わっていればその日の中を二分探索する。切り替わりは1日に1回までとみなす
+    pub fn transitions(&self, from: &DateTime, to: &DateTime) -> Vec<DateTime> {
+        const DAY: i64 = 24 * 60 * 60;
+
+        let (from, to) = (from.0.timestamp(), to.0.timestamp());
+        let mut transitions = Vec::new();
+        let mut current = from;
+        let mut offset = self.offset_at(current);
+
+        while current < to {
+            let next = (current + DAY).min(to);
+
+            if self.offset_at(next) != offset {
+                let (mut low, mut high) = (current, next);
+                while high - low > 1 {
+                    let middle = low + (high - low) / 2;
+                    if self.offset_at(middle) == offset {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+
+                if let Some(transition) = DateTime::from_timestamp(high) {
+                    transitions.push(transition);
+                }
+                offset = self.offset_at(next);
+            }
+
+            current = next;
+        }
+
+        transitions
+    }
+
+    fn offset_at(&self, secs: i64) -> (i32, bool) {
+        let Some(utc) = chrono::DateTime::from_timestamp(secs, 0) else {
+            return (0, false);
+        };
+        let offset = self.0.offset_from_utc_datetime(&utc.naive_utc());
+
+        (
+            offset.fix().local_minus_utc(),
+            !offset.dst_offset().is_zero(),
+        )
+    }
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Self::utc()
+    }
+}
+
+impl TryFrom<String> for Timezone {
+    type Error = DateTimeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(
+            value
+                .parse::<chrono_tz::Tz>()
+                .map_err(|_| DateTimeError::InvalidTimezone(value))?,
+        ))
+    }
 }
 
 impl TryFrom<String> for DateTime {
@@ -27,6 +139,7 @@ pub enum DateTimeError {
     InvalidTime(u32, u32, u32),
     InvalidMonthlyTime(u8, u32, u32, u32),
     InvalidEpochDate(i32, u32, u32),
+    InvalidTimezone(String),
 }
 
 impl From<DateTimeError> for ErrorCode {
@@ -46,6 +159,9 @@ impl From<DateTimeError> for ErrorCode {
             DateTimeError::InvalidEpochDate(year, month, date) => Self::InvalidDateTimeFormat(
                 format!("invalid-epoch-date; year={year}; month={month}; date={date}"),
             ),
+            DateTimeError::InvalidTimezone(s) => {
+                Self::InvalidDateTimeFormat(format!("invalid-timezone; string={s}"))
+            }
         }
     }
 }
@@ -121,6 +237,14 @@ impl WeeklyTime {
                 .ok_or(DateTimeError::InvalidTime(hour, min, sec))?,
         })
     }
+
+    pub fn weekday(&self) -> chrono::Weekday {
+        self.weekday
+    }
+
+    pub fn time(&self) -> chrono::NaiveTime {
+        self.time
+    }
 }
 
 impl From<WeeklyTime> for DateTime {
@@ -196,6 +320,14 @@ impl MonthlyTime {
                 .ok_or(DateTimeError::InvalidMonthlyTime(date, hour, min, sec))?,
         })
     }
+
+    pub fn date(&self) -> u8 {
+        self.date
+    }
+
+    pub fn time(&self) -> chrono::NaiveTime {
+        self.time
+    }
 }
 
 impl From<MonthlyTime> for DateTime {
@@ -219,7 +351,7 @@ impl TryFrom<DateTime> for MonthlyTime {
     type Error = DateTimeError;
 
     fn try_from(value: DateTime) -> Result<Self, Self::Error> {
-        if value.0.year() == 1970 && value.0.month() == 1 && (1..31).contains(&value.0.day()) {
+        if value.0.year() == 1970 && value.0.month() == 1 && (1..=31).contains(&value.0.day()) {
             Ok(Self {
                 date: value.0.day() as u8,
                 time: chrono::NaiveTime::from_hms_opt(
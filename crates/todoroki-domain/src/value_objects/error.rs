@@ -5,47 +5,145 @@ use thiserror::Error;
 
 use crate::{
     entities::{
-        doit::DoitId,
+        comment::CommentId,
+        doit::{DoitId, DoitStatus},
         label::LabelId,
-        todo::TodoId,
+        todo::{TodoId, TodoStatus},
         user::{UserEmail, UserId},
+        webhook::{WebhookDeliveryId, WebhookId},
     },
     repositories::{
-        doit::DoitRepositoryError, label::LabelRepositoryError, todo::TodoRepositoryError,
-        user::UserRepositoryError,
+        audit::AuditRepositoryError, change_stream::ChangeStreamRepositoryError,
+        comment::CommentRepositoryError, doit::DoitRepositoryError, label::LabelRepositoryError,
+        todo::TodoRepositoryError, user::UserRepositoryError, webhook::WebhookRepositoryError,
+        webhook_sender::WebhookSenderRepositoryError,
     },
-    value_objects::permission::Permission,
+    value_objects::{datetime::DateTime, permission::Permission},
 };
 
 #[derive(Debug, Clone, Error)]
 pub enum ErrorCode {
     TodoNotFound(TodoId),
+    TodoStatusTransitionInvalid(TodoStatus, TodoStatus),
+    TodoOccurrenceNotFound(TodoId, DateTime),
+    TodoVersionNotFound(TodoId, u32),
+    TodoHierarchyCycle(TodoId, TodoId),
+    TodoDependencyCycle(TodoId, TodoId),
+    TodoBlocked(TodoId, Vec<TodoId>),
     DoitNotFound(DoitId),
+    DoitAlreadyAccepted(DoitId, TodoId),
+    DoitStatusTransitionInvalid(DoitStatus, DoitStatus),
+    DoitDuplicateOfSelf(DoitId),
+    CommentNotFound(CommentId),
+    CommentParentInvalid(CommentId),
     LabelNotFound(LabelId),
+    WebhookNotFound(WebhookId),
+    WebhookDeliveryNotFound(WebhookDeliveryId),
     PermissionDenied(Box<Permission>),
     #[error(transparent)]
     TodoRepositoryInternalError(#[from] TodoRepositoryError),
     #[error(transparent)]
     DoitRepositoryInternalError(#[from] DoitRepositoryError),
     #[error(transparent)]
+    CommentRepositoryInternalError(#[from] CommentRepositoryError),
+    #[error(transparent)]
     LabelRepositoryInternalError(#[from] LabelRepositoryError),
     #[error(transparent)]
     UserRepositoryInternalError(#[from] UserRepositoryError),
+    #[error(transparent)]
+    AuditRepositoryInternalError(#[from] AuditRepositoryError),
+    #[error(transparent)]
+    WebhookRepositoryInternalError(#[from] WebhookRepositoryError),
+    #[error(transparent)]
+    WebhookSenderRepositoryInternalError(#[from] WebhookSenderRepositoryError),
+    #[error(transparent)]
+    ChangeStreamRepositoryInternalError(#[from] ChangeStreamRepositoryError),
     UserAuthTokenVerificationError(String),
     UserNotVerified,
     UserNotFound(UserId),
     UserAlreadyExistsForEmail(UserEmail),
     InvalidDateTimeFormat(String),
+    InvalidRecurrenceRule(String),
     InvalidUuidFormat(String),
     InvalidColorFormat(String),
+    InvalidCursorFormat(String),
+    // (cursor を取得したときの並び順, 指定された並び順)
+    CursorSortMismatch(String, String),
+    InvalidWebhookUrl(String),
+    InvalidWebhookEventType(String),
+    InvalidChangeEventId(String),
+    InvalidWebSocketMessage(String),
+    OccurrenceRangeInvalid(DateTime, DateTime),
 }
 
 impl Display for ErrorCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::TodoNotFound(id) => write!(f, "todo/not-found; id={}", id.clone().value()),
+            Self::TodoStatusTransitionInvalid(from, to) => {
+                write!(f, "todo/status-transition-invalid; from={from}; to={to}")
+            }
+            Self::TodoOccurrenceNotFound(id, occurrence_start) => write!(
+                f,
+                "todo/occurrence-not-found; id={}; occurrence_start={}",
+                id.clone().value(),
+                occurrence_start.clone().value().to_rfc3339()
+            ),
+            Self::TodoVersionNotFound(id, version) => write!(
+                f,
+                "todo/version-not-found; id={}; version={version}",
+                id.clone().value()
+            ),
+            Self::TodoHierarchyCycle(id, parent_id) => write!(
+                f,
+                "todo/hierarchy-cycle; id={}; parent_id={}",
+                id.clone().value(),
+                parent_id.clone().value()
+            ),
+            Self::TodoDependencyCycle(id, blocker_id) => write!(
+                f,
+                "todo/dependency-cycle; id={}; blocker_id={}",
+                id.clone().value(),
+                blocker_id.clone().value()
+            ),
+            Self::TodoBlocked(id, blocker_ids) => write!(
+                f,
+                "todo/blocked; id={}; blocker_ids={}",
+                id.clone().value(),
+                blocker_ids
+                    .iter()
+                    .map(|id| id.clone().value().to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
             Self::DoitNotFound(id) => write!(f, "doit/not-found; id={}", id.clone().value()),
+            Self::DoitAlreadyAccepted(id, todo_id) => write!(
+                f,
+                "doit/already-accepted; id={}, todo_id={}",
+                id.clone().value(),
+                todo_id.clone().value()
+            ),
+            Self::DoitStatusTransitionInvalid(from, to) => {
+                write!(f, "doit/status-transition-invalid; from={from}, to={to}")
+            }
+            Self::DoitDuplicateOfSelf(id) => {
+                write!(f, "doit/duplicate-of-self; id={}", id.clone().value())
+            }
+            Self::CommentNotFound(id) => {
+                write!(f, "comment/not-found; id={}", id.clone().value())
+            }
+            Self::CommentParentInvalid(id) => {
+                write!(
+                    f,
+                    "comment/parent-invalid; parent_id={}",
+                    id.clone().value()
+                )
+            }
             Self::LabelNotFound(id) => write!(f, "label/not-found; id={}", id.clone().value()),
+            Self::WebhookNotFound(id) => write!(f, "webhook/not-found; id={}", id.clone().value()),
+            Self::WebhookDeliveryNotFound(id) => {
+                write!(f, "webhook/delivery-not-found; id={}", id.clone().value())
+            }
             Self::PermissionDenied(perm) => write!(f, "permission/denied; permission={perm}"),
             Self::TodoRepositoryInternalError(e) => {
                 write!(f, "todo/repository-internal-error; error={e}")
@@ -53,12 +151,27 @@ impl Display for ErrorCode {
             Self::DoitRepositoryInternalError(e) => {
                 write!(f, "doit/repository-internal-error; error={e}")
             }
+            Self::CommentRepositoryInternalError(e) => {
+                write!(f, "comment/repository-internal-error; error={e}")
+            }
             Self::LabelRepositoryInternalError(e) => {
                 write!(f, "label/repository-internal-error; error={e}")
             }
             Self::UserRepositoryInternalError(e) => {
                 write!(f, "user/repository-internal-error; error={e}")
             }
+            Self::AuditRepositoryInternalError(e) => {
+                write!(f, "audit/repository-internal-error; error={e}")
+            }
+            Self::WebhookRepositoryInternalError(e) => {
+                write!(f, "webhook/repository-internal-error; error={e}")
+            }
+            Self::WebhookSenderRepositoryInternalError(e) => {
+                write!(f, "webhook/sender-internal-error; error={e}")
+            }
+            Self::ChangeStreamRepositoryInternalError(e) => {
+                write!(f, "change/stream-internal-error; error={e}")
+            }
             Self::UserAuthTokenVerificationError(s) => {
                 write!(f, "user-auth/token-verification-failed; error={s}")
             }
@@ -72,8 +185,28 @@ impl Display for ErrorCode {
                 write!(f, "user/already-exists; email={}", email.clone().value())
             }
             Self::InvalidDateTimeFormat(s) => write!(f, "datetime/invalid-format; error={s}"),
+            Self::InvalidRecurrenceRule(s) => write!(f, "recurrence/invalid-rule; error={s}"),
             Self::InvalidUuidFormat(s) => write!(f, "uuid/invalid-format; string={s}"),
             Self::InvalidColorFormat(s) => write!(f, "color/invalid-format; string={s}"),
+            Self::InvalidCursorFormat(s) => write!(f, "cursor/invalid-format; string={s}"),
+            Self::CursorSortMismatch(cursor, request) => write!(
+                f,
+                "cursor/sort-mismatch; cursor={cursor}; request={request}"
+            ),
+            Self::InvalidWebhookUrl(s) => write!(f, "webhook/invalid-url; string={s}"),
+            Self::InvalidWebhookEventType(s) => {
+                write!(f, "webhook/invalid-event-type; string={s}")
+            }
+            Self::InvalidChangeEventId(s) => write!(f, "change/invalid-event-id; string={s}"),
+            Self::InvalidWebSocketMessage(s) => {
+                write!(f, "websocket/invalid-message; error={s}")
+            }
+            Self::OccurrenceRangeInvalid(from, to) => write!(
+                f,
+                "occurrence/range-invalid; from={}; to={}",
+                from.clone().value().to_rfc3339(),
+                to.clone().value().to_rfc3339()
+            ),
         }
     }
 }
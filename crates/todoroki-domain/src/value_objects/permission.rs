@@ -3,7 +3,9 @@ use std::fmt::Display;
 use crate::{
     entities::{
         client::{Client, ContextedClient},
+        comment::Comment,
         doit::Doit,
+        todo::{Todo, TodoPublishment},
         user::{User, UserRole},
     },
     value_objects::error::ErrorCode,
@@ -13,22 +15,33 @@ use crate::{
 pub enum Permission {
     CreateUser(User),
     ReadUser,
-    // UpdateUser(User),
+    UpdateUser(User), // 自分自身である場合は Contributor も更新できる
     // DeleteUser(User),
     CreateTodo,
     ReadTodo,
     ReadPrivateTodo, // name や description に private ガードがかけられているものを読めるか
     UpdateTodo,
     DeleteTodo,
+    RevertTodo, // 変更履歴から以前の内容に戻す
     CreateDoit,
     ReadDoit,
     ReadPrivateDoit(Doit), // name や description に private ガードがかけられているものを読めるか。 Doit の作成者自身である場合はContributorも読める
     UpdateDoit(Doit),      //  Doit の作成者自身である場合はContributorも更新できる
     DeleteDoit,
+    VoteDoit,
+    AcceptDoit,              // Doit を Todo として受け入れる
+    ReviewDoit,              // Doit を保留・却下・重複とする
+    CreateTodoComment(Todo), // 公開されている Todo には Contributor もコメントできる
+    CreateDoitComment(Doit), // Doit の作成者自身である場合は Contributor もコメントできる
+    ReadComment,
+    UpdateComment(Comment), // コメントの作成者自身である場合は Contributor も編集できる
+    ModerateComment(Comment), // コメントの削除。作成者自身である場合は Contributor も削除できる
     CreateLabel,
     ReadLabel,
     UpdateLabel,
     DeleteLabel,
+    ReadAudit,     // 監査ログを読めるか
+    ManageWebhook, // webhook の登録・削除と配送状況の閲覧
 }
 
 impl<'a> ContextedClient<'a> {
@@ -36,18 +49,33 @@ impl<'a> ContextedClient<'a> {
         let has = match self.client() {
             Client::User(u) => match u.role() {
                 UserRole::Owner => true,
-                UserRole::Contributor => matches!(
-                    permission,
-                    Permission::ReadTodo
-                        | Permission::CreateDoit
-                        | Permission::ReadDoit
-                        | Permission::ReadLabel
-                ),
+                UserRole::Contributor => match &permission {
+                    Permission::CreateDoitComment(doit) => doit.created_by() == u.id(),
+                    Permission::CreateTodoComment(todo) => {
+                        matches!(todo.is_public(), TodoPublishment::Public)
+                    }
+                    Permission::UpdateComment(comment) | Permission::ModerateComment(comment) => {
+                        comment.created_by() == u.id()
+                    }
+                    Permission::UpdateUser(user) => user.id() == u.id(),
+                    _ => matches!(
+                        permission,
+                        Permission::ReadTodo
+                            | Permission::CreateDoit
+                            | Permission::ReadDoit
+                            | Permission::VoteDoit
+                            | Permission::ReadComment
+                            | Permission::ReadLabel
+                    ),
+                },
             },
             Client::Unregistered(email) => {
                 matches!(
                     permission,
-                    Permission::ReadTodo | Permission::ReadDoit | Permission::ReadLabel
+                    Permission::ReadTodo
+                        | Permission::ReadDoit
+                        | Permission::ReadComment
+                        | Permission::ReadLabel
                 ) || if let Permission::CreateUser(u) = permission.clone() {
                     (u.role() == &UserRole::Contributor
                         || (u.email().clone().value()
@@ -60,7 +88,10 @@ impl<'a> ContextedClient<'a> {
             }
             Client::Unverified => matches!(
                 permission,
-                Permission::ReadTodo | Permission::ReadDoit | Permission::ReadLabel
+                Permission::ReadTodo
+                    | Permission::ReadDoit
+                    | Permission::ReadComment
+                    | Permission::ReadLabel
             ),
         };
 
@@ -80,17 +111,29 @@ impl Display for Permission {
             Self::ReadPrivateTodo => write!(f, "read-private-todo"),
             Self::UpdateTodo => write!(f, "update-todo"),
             Self::DeleteTodo => write!(f, "delete-todo"),
+            Self::RevertTodo => write!(f, "revert-todo"),
             Self::CreateDoit => write!(f, "create-doit"),
             Self::ReadDoit => write!(f, "read-doit"),
             Self::ReadPrivateDoit(_) => write!(f, "read-private-doit"),
             Self::UpdateDoit(_) => write!(f, "update-doit"),
             Self::DeleteDoit => write!(f, "delete-doit"),
+            Self::VoteDoit => write!(f, "vote-doit"),
+            Self::AcceptDoit => write!(f, "accept-doit"),
+            Self::ReviewDoit => write!(f, "review-doit"),
+            Self::CreateTodoComment(_) => write!(f, "create-todo-comment"),
+            Self::CreateDoitComment(_) => write!(f, "create-doit-comment"),
+            Self::ReadComment => write!(f, "read-comment"),
+            Self::UpdateComment(_) => write!(f, "update-comment"),
+            Self::ModerateComment(_) => write!(f, "moderate-comment"),
             Self::CreateUser(_) => write!(f, "create-user"),
             Self::ReadUser => write!(f, "read-user"),
+            Self::UpdateUser(_) => write!(f, "update-user"),
             Self::CreateLabel => write!(f, "create-label"),
             Self::ReadLabel => write!(f, "read-label"),
             Self::UpdateLabel => write!(f, "update-label"),
             Self::DeleteLabel => write!(f, "delete-label"),
+            Self::ReadAudit => write!(f, "read-audit"),
+            Self::ManageWebhook => write!(f, "manage-webhook"),
         }
     }
 }
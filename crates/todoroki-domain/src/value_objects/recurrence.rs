@@ -0,0 +1,426 @@
+use getset::Getters;
+
+use crate::value_objects::{datetime::DateTime, error::ErrorCode};
+
+// RFC 5545 の RRULE のうち、FREQ が DAILY 以上の粒度のもの
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct RecurrenceRule {
+    #[getset(get = "pub")]
+    frequency: RecurrenceFrequency,
+    #[getset(get = "pub")]
+    interval: u32,
+    #[getset(get = "pub")]
+    by_day: Vec<RecurrenceWeekday>,
+    #[getset(get = "pub")]
+    by_month_day: Vec<i8>,
+    #[getset(get = "pub")]
+    by_month: Vec<u32>,
+    #[getset(get = "pub")]
+    by_set_pos: Vec<i16>,
+    #[getset(get = "pub")]
+    count: Option<u32>,
+    #[getset(get = "pub")]
+    until: Option<DateTime>,
+    #[getset(get = "pub")]
+    week_start: chrono::Weekday,
+    // RRULE 自体には含まれないが、除外する回の開始日時 (EXDATE) もここで持つ
+    #[getset(get = "pub")]
+    exdates: Vec<DateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+// BYDAY の1要素。ordinal は 1MO や -1FR の 1 や -1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Getters)]
+pub struct RecurrenceWeekday {
+    #[getset(get = "pub")]
+    ordinal: Option<i8>,
+    #[getset(get = "pub")]
+    weekday: chrono::Weekday,
+}
+
+pub enum RecurrenceRuleError {
+    MissingFrequency,
+    UnsupportedPart(String),
+    InvalidPart(String),
+    DuplicatedPart(String),
+    CountWithUntil,
+}
+
+impl From<RecurrenceRuleError> for ErrorCode {
+    fn from(value: RecurrenceRuleError) -> Self {
+        match value {
+            RecurrenceRuleError::MissingFrequency => {
+                Self::InvalidRecurrenceRule("missing-freq".to_string())
+            }
+            RecurrenceRuleError::UnsupportedPart(part) => {
+                Self::InvalidRecurrenceRule(format!("unsupported-part; part={part}"))
+            }
+            RecurrenceRuleError::InvalidPart(part) => {
+                Self::InvalidRecurrenceRule(format!("invalid-part; part={part}"))
+            }
+            RecurrenceRuleError::DuplicatedPart(name) => {
+                Self::InvalidRecurrenceRule(format!("duplicated-part; name={name}"))
+            }
+            RecurrenceRuleError::CountWithUntil => {
+                Self::InvalidRecurrenceRule("count-with-until".to_string())
+            }
+        }
+    }
+}
+
+impl RecurrenceRule {
+    // 例: FREQ=MONTHLY;BYDAY=-1FR, RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=TU
+    pub fn try_new(rule: &str, exdates: Vec<DateTime>) -> Result<Self, RecurrenceRuleError> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut seen: Vec<String> = Vec::new();
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut by_month = Vec::new();
+        let mut by_set_pos = Vec::new();
+        let mut count = None;
+        let mut until = None;
+        let mut week_start = chrono::Weekday::Mon;
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let invalid = || RecurrenceRuleError::InvalidPart(part.to_string());
+
+            let (name, value) = part.split_once('=').ok_or_else(invalid)?;
+            let name = name.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+
+            if seen.contains(&name) {
+                return Err(RecurrenceRuleError::DuplicatedPart(name));
+            }
+
+            match name.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => RecurrenceFrequency::Daily,
+                        "WEEKLY" => RecurrenceFrequency::Weekly,
+                        "MONTHLY" => RecurrenceFrequency::Monthly,
+                        "YEARLY" => RecurrenceFrequency::Yearly,
+                        // NOTE: 時間単位以下の繰り返しには対応しない
+                        _ => return Err(RecurrenceRuleError::UnsupportedPart(part.to_string())),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| *n >= 1)
+                        .ok_or_else(invalid)?
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|n| *n >= 1)
+                            .ok_or_else(invalid)?,
+                    )
+                }
+                "UNTIL" => until = Some(parse_until(&value).ok_or_else(invalid)?),
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(|day| parse_recurrence_weekday(day).ok_or_else(invalid))
+                        .collect::<Result<_, _>>()?
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = parse_list(&value, |n: &i8| (1..=31).contains(&n.abs()))
+                        .ok_or_else(invalid)?
+                }
+                "BYMONTH" => {
+                    by_month =
+                        parse_list(&value, |n: &u32| (1..=12).contains(n)).ok_or_else(invalid)?
+                }
+                "BYSETPOS" => {
+                    by_set_pos = parse_list(&value, |n: &i16| (1..=366).contains(&n.abs()))
+                        .ok_or_else(invalid)?
+                }
+                "WKST" => week_start = parse_weekday(&value).ok_or_else(invalid)?,
+                _ => return Err(RecurrenceRuleError::UnsupportedPart(part.to_string())),
+            }
+
+            seen.push(name);
+        }
+
+        let frequency = frequency.ok_or(RecurrenceRuleError::MissingFrequency)?;
+
+        if count.is_some() && until.is_some() {
+            return Err(RecurrenceRuleError::CountWithUntil);
+        }
+
+        // NOTE: 序数付きの BYDAY は月や年の中での位置なので MONTHLY と YEARLY のみ
+        let has_ordinal = by_day
+            .iter()
+            .any(|d: &RecurrenceWeekday| d.ordinal.is_some());
+        if has_ordinal
+            && !matches!(
+                frequency,
+                RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly
+            )
+        {
+            return Err(RecurrenceRuleError::InvalidPart(format!(
+                "BYDAY={}",
+                join(&by_day)
+            )));
+        }
+
+        if frequency == RecurrenceFrequency::Weekly && !by_month_day.is_empty() {
+            return Err(RecurrenceRuleError::InvalidPart(format!(
+                "BYMONTHDAY={}",
+                join(&by_month_day)
+            )));
+        }
+
+        if !by_set_pos.is_empty()
+            && by_day.is_empty()
+            && by_month_day.is_empty()
+            && by_month.is_empty()
+        {
+            return Err(RecurrenceRuleError::InvalidPart(format!(
+                "BYSETPOS={}",
+                join(&by_set_pos)
+            )));
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            by_month,
+            by_set_pos,
+            count,
+            until,
+            week_start,
+            exdates,
+        })
+    }
+}
+
+// EXDATE を含まない RRULE の値
+impl std::fmt::Display for RecurrenceRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FREQ={}", self.frequency)?;
+
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = &self.until {
+            write!(
+                f,
+                ";UNTIL={}",
+                until.clone().value().format("%Y%m%dT%H%M%SZ")
+            )?;
+        }
+        if !self.by_month.is_empty() {
+            write!(f, ";BYMONTH={}", join(&self.by_month))?;
+        }
+        if !self.by_month_day.is_empty() {
+            write!(f, ";BYMONTHDAY={}", join(&self.by_month_day))?;
+        }
+        if !self.by_day.is_empty() {
+            write!(f, ";BYDAY={}", join(&self.by_day))?;
+        }
+        if !self.by_set_pos.is_empty() {
+            write!(f, ";BYSETPOS={}", join(&self.by_set_pos))?;
+        }
+        if self.week_start != chrono::Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for RecurrenceFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Daily => write!(f, "DAILY"),
+            Self::Weekly => write!(f, "WEEKLY"),
+            Self::Monthly => write!(f, "MONTHLY"),
+            Self::Yearly => write!(f, "YEARLY"),
+        }
+    }
+}
+
+impl std::fmt::Display for RecurrenceWeekday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ordinal {
+            Some(ordinal) => write!(f, "{ordinal}{}", weekday_code(self.weekday)),
+            None => write!(f, "{}", weekday_code(self.weekday)),
+        }
+    }
+}
+
+fn parse_until(value: &str) -> Option<DateTime> {
+    // NOTE: 日付のみの UNTIL はその日の終わりまでを含める
+    if value.len() == 8 {
+        return chrono::NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(23, 59, 59))
+            .map(|until| DateTime::new(until.and_utc()));
+    }
+
+    chrono::NaiveDateTime::parse_from_str(value.strip_suffix('Z')?, "%Y%m%dT%H%M%S")
+        .ok()
+        .map(|until| DateTime::new(until.and_utc()))
+}
+
+fn parse_list<T: std::str::FromStr>(value: &str, is_valid: impl Fn(&T) -> bool) -> Option<Vec<T>> {
+    value
+        .split(',')
+        .map(|n| {
+            n.trim()
+                .trim_start_matches('+')
+                .parse::<T>()
+                .ok()
+                .filter(&is_valid)
+        })
+        .collect()
+}
+
+fn parse_recurrence_weekday(value: &str) -> Option<RecurrenceWeekday> {
+    let value = value.trim();
+    let (ordinal, weekday) = value.split_at(value.len().checked_sub(2)?);
+
+    let ordinal = match ordinal {
+        "" => None,
+        ordinal => Some(
+            ordinal
+                .trim_start_matches('+')
+                .parse::<i8>()
+                .ok()
+                .filter(|n| (1..=53).contains(&n.abs()))?,
+        ),
+    };
+
+    Some(RecurrenceWeekday {
+        ordinal,
+        weekday: parse_weekday(weekday)?,
+    })
+}
+
+fn parse_weekday(value: &str) -> Option<chrono::Weekday> {
+    match value {
+        "MO" => Some(chrono::Weekday::Mon),
+        "TU" => Some(chrono::Weekday::Tue),
+        "WE" => Some(chrono::Weekday::Wed),
+        "TH" => Some(chrono::Weekday::Thu),
+        "FR" => Some(chrono::Weekday::Fri),
+        "SA" => Some(chrono::Weekday::Sat),
+        "SU" => Some(chrono::Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn weekday_code(weekday: chrono::Weekday) -> &'static str {
+    match weekday {
+        chrono::Weekday::Mon => "MO",
+        chrono::Weekday::Tue => "TU",
+        chrono::Weekday::Wed => "WE",
+        chrono::Weekday::Thu => "TH",
+        chrono::Weekday::Fri => "FR",
+        chrono::Weekday::Sat => "SA",
+        chrono::Weekday::Sun => "SU",
+    }
+}
+
+fn join<T: std::fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rule: &str) -> Result<RecurrenceRule, RecurrenceRuleError> {
+        RecurrenceRule::try_new(rule, Vec::new())
+    }
+
+    #[test]
+    fn parses_last_weekday_of_month() {
+        let rule = parse("RRULE:FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1")
+            .unwrap_or_else(|_| panic!("should be valid"));
+
+        assert_eq!(*rule.frequency(), RecurrenceFrequency::Monthly);
+        assert_eq!(rule.by_day().len(), 5);
+        assert!(rule.by_day().iter().all(|d| d.ordinal().is_none()));
+        assert_eq!(*rule.by_set_pos(), vec![-1]);
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1"
+        );
+    }
+
+    #[test]
+    fn parses_ordinal_weekday_and_until() {
+        let rule = parse("freq=monthly;byday=-1fr;until=20251231")
+            .unwrap_or_else(|_| panic!("should be valid"));
+
+        assert_eq!(
+            *rule.by_day(),
+            vec![RecurrenceWeekday {
+                ordinal: Some(-1),
+                weekday: chrono::Weekday::Fri,
+            }]
+        );
+        // 日付のみの UNTIL はその日の終わりまで
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;UNTIL=20251231T235959Z;BYDAY=-1FR"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!(matches!(
+            parse("BYDAY=MO"),
+            Err(RecurrenceRuleError::MissingFrequency)
+        ));
+        assert!(matches!(
+            parse("FREQ=HOURLY"),
+            Err(RecurrenceRuleError::UnsupportedPart(_))
+        ));
+        assert!(matches!(
+            parse("FREQ=DAILY;FREQ=WEEKLY"),
+            Err(RecurrenceRuleError::DuplicatedPart(name)) if name == "FREQ"
+        ));
+        assert!(matches!(
+            parse("FREQ=DAILY;COUNT=3;UNTIL=20251231T000000Z"),
+            Err(RecurrenceRuleError::CountWithUntil)
+        ));
+        assert!(matches!(
+            parse("FREQ=WEEKLY;BYDAY=1MO"),
+            Err(RecurrenceRuleError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            parse("FREQ=MONTHLY;BYMONTHDAY=32"),
+            Err(RecurrenceRuleError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            parse("FREQ=MONTHLY;BYSETPOS=-1"),
+            Err(RecurrenceRuleError::InvalidPart(_))
+        ));
+    }
+}
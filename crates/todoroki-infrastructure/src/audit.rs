@@ -0,0 +1,221 @@
+use crate::shared::{
+    pagination::{datetime_sort_key, into_page, page_request_into_params},
+    postgresql::Postgresql,
+};
+
+use futures_util::{StreamExt, TryStreamExt};
+use sqlx::{postgres::PgExecutor, prelude::FromRow, types::chrono};
+use todoroki_domain::{
+    entities::{
+        audit::{AuditAction, AuditActor, AuditEntity, AuditEvent, AuditEventId, Auditable},
+        user::UserId,
+    },
+    repositories::{
+        audit::{AuditFilter, AuditRepository, AuditRepositoryError, AuditSortKey},
+        pagination::{Page, PageRequest},
+    },
+    value_objects::datetime::DateTime,
+};
+use uuid::Uuid;
+
+#[derive(FromRow)]
+struct AuditEventRow {
+    id: Uuid,
+    entity: AuditEntityColumn,
+    entity_id: Uuid,
+    action: AuditActionColumn,
+    actor_id: Option<Uuid>,
+    permission: String,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "audit_entity", rename_all = "snake_case")]
+pub enum AuditEntityColumn {
+    Todo,
+    Doit,
+    Label,
+    User,
+}
+
+impl From<AuditEntity> for AuditEntityColumn {
+    fn from(value: AuditEntity) -> Self {
+        match value {
+            AuditEntity::Todo => Self::Todo,
+            AuditEntity::Doit => Self::Doit,
+            AuditEntity::Label => Self::Label,
+            AuditEntity::User => Self::User,
+        }
+    }
+}
+
+impl From<AuditEntityColumn> for AuditEntity {
+    fn from(value: AuditEntityColumn) -> Self {
+        match value {
+            AuditEntityColumn::Todo => Self::Todo,
+            AuditEntityColumn::Doit => Self::Doit,
+            AuditEntityColumn::Label => Self::Label,
+            AuditEntityColumn::User => Self::User,
+        }
+    }
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "audit_action", rename_all = "snake_case")]
+pub enum AuditActionColumn {
+    Create,
+    Update,
+    Delete,
+    StatusChange,
+}
+
+impl From<AuditAction> for AuditActionColumn {
+    fn from(value: AuditAction) -> Self {
+        match value {
+            AuditAction::Create => Self::Create,
+            AuditAction::Update => Self::Update,
+            AuditAction::Delete => Self::Delete,
+            AuditAction::StatusChange => Self::StatusChange,
+        }
+    }
+}
+
+impl From<AuditActionColumn> for AuditAction {
+    fn from(value: AuditActionColumn) -> Self {
+        match value {
+            AuditActionColumn::Create => Self::Create,
+            AuditActionColumn::Update => Self::Update,
+            AuditActionColumn::Delete => Self::Delete,
+            AuditActionColumn::StatusChange => Self::StatusChange,
+        }
+    }
+}
+
+impl From<AuditEventRow> for AuditEvent {
+    fn from(value: AuditEventRow) -> Self {
+        Self::new(
+            AuditEventId::new(value.id),
+            value.entity.into(),
+            value.entity_id,
+            value.action.into(),
+            value.actor_id.map(UserId::new),
+            value.permission,
+            value.before,
+            value.after,
+            DateTime::new(value.occurred_at),
+        )
+    }
+}
+
+// NOTE: list の sort_key と同じ値を返すこと
+fn audit_sort_key(event: &AuditEvent, page: &PageRequest<AuditSortKey>) -> String {
+    match page.sort() {
+        AuditSortKey::OccurredAt => datetime_sort_key(event.occurred_at()),
+    }
+}
+
+// 変更と同じトランザクションで実行すれば、コミットされたときにだけ記録される。変更がなかった場合は何もしない
+pub(crate) async fn record_audit_event<'e, E: PgExecutor<'e>, T: Auditable>(
+    executor: E,
+    action: AuditAction,
+    actor: &AuditActor,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<(), sqlx::Error> {
+    let Some(event) = AuditEvent::generate(action, actor, before, after) else {
+        return Ok(());
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO audit_events (id, entity, entity_id, action, actor_id, permission, before, after, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+        event.id().clone().value(),
+        AuditEntityColumn::from(*event.entity()) as AuditEntityColumn,
+        event.entity_id(),
+        AuditActionColumn::from(*event.action()) as AuditActionColumn,
+        event.actor_id().clone().map(|id| id.value()),
+        event.permission(),
+        event.before().clone(),
+        event.after().clone(),
+        event.occurred_at().clone().value(),
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PgAuditRepository {
+    db: Postgresql,
+}
+
+impl PgAuditRepository {
+    pub fn new(db: Postgresql) -> Self {
+        Self { db }
+    }
+}
+
+impl AuditRepository for PgAuditRepository {
+    async fn list(
+        &self,
+        filter: AuditFilter,
+        page: PageRequest<AuditSortKey>,
+    ) -> Result<Page<AuditEvent>, AuditRepositoryError> {
+        let (cursor_key, cursor_id, limit) = page_request_into_params(&page);
+
+        let res: Vec<AuditEvent> = sqlx::query_as!(
+            AuditEventRow,
+            r#"SELECT
+            audit_events.id AS "id",
+            audit_events.entity AS "entity: AuditEntityColumn",
+            audit_events.entity_id AS "entity_id",
+            audit_events.action AS "action: AuditActionColumn",
+            audit_events.actor_id AS "actor_id?",
+            audit_events.permission AS "permission",
+            audit_events.before AS "before?",
+            audit_events.after AS "after?",
+            audit_events.occurred_at AS "occurred_at"
+            FROM audit_events
+            CROSS JOIN LATERAL (
+                SELECT to_char(audit_events.occurred_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS') COLLATE "C" AS sort_key
+            ) k
+            WHERE ($1::audit_entity IS NULL OR audit_events.entity = $1)
+            AND ($2::uuid IS NULL OR audit_events.actor_id = $2)
+            AND ($3::timestamptz IS NULL OR audit_events.occurred_at >= $3)
+            AND ($4::timestamptz IS NULL OR audit_events.occurred_at < $4)
+            AND ($6::text IS NULL OR CASE
+                WHEN $5::bool THEN (k.sort_key, audit_events.id) < ($6 COLLATE "C", $7::uuid)
+                ELSE (k.sort_key, audit_events.id) > ($6 COLLATE "C", $7::uuid)
+            END)
+            ORDER BY
+                CASE WHEN $5 THEN k.sort_key END DESC,
+                CASE WHEN $5 THEN audit_events.id END DESC,
+                CASE WHEN NOT $5 THEN k.sort_key END ASC,
+                CASE WHEN NOT $5 THEN audit_events.id END ASC
+            LIMIT $8"#,
+            filter
+                .entity()
+                .map(AuditEntityColumn::from) as Option<AuditEntityColumn>,
+            filter.actor_id().clone().map(|id| id.value()),
+            filter.occurred_from().clone().map(|t| t.value()),
+            filter.occurred_to().clone().map(|t| t.value()),
+            page.is_desc(),
+            cursor_key,
+            cursor_id,
+            limit,
+        )
+        .fetch(&*self.db)
+        .map(|row| Ok(AuditEvent::from(row?)))
+        .try_collect()
+        .await
+        .map_err(|e: sqlx::Error| AuditRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(into_page(res, &page, |event| {
+            page.cursor_for(audit_sort_key(event, &page), event.id().clone().value())
+        }))
+    }
+}
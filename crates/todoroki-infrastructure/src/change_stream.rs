@@ -0,0 +1,312 @@
+use std::{collections::VecDeque, sync::Arc, sync::Mutex, time::Duration};
+
+use serde::Deserialize;
+use sqlx::postgres::{PgExecutor, PgListener};
+use todoroki_domain::{
+    entities::{
+        change::{ChangeAction, ChangeEvent, ChangeEventId, ChangeTarget},
+        doit::DoitId,
+        label::LabelId,
+        todo::TodoId,
+    },
+    repositories::{
+        change_stream::{ChangeStreamRepository, ChangeStreamRepositoryError, ChangeSubscription},
+        doit::DoitRepository,
+        label::LabelRepository,
+        todo::TodoRepository,
+    },
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::{
+    doit::PgDoitRepository,
+    label::PgLabelRepository,
+    shared::postgresql::{Postgresql, PostgresqlError},
+    todo::PgTodoRepository,
+};
+
+// NOTIFY / LISTEN に使うチャンネル
+const CHANGE_CHANNEL: &str = "todoroki_changes";
+// 再開のために保持しておく変更の件数
+const CHANGE_STREAM_BACKLOG_SIZE: usize = 1024;
+// 受け取りが遅れている購読者のために溜めておける件数
+const CHANGE_STREAM_CHANNEL_CAPACITY: usize = 256;
+// LISTEN の接続に失敗したときに再接続を待つ時間
+const CHANGE_LISTEN_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ChangeTargetKind {
+    Todo,
+    Doit,
+    Label,
+}
+
+impl ChangeTargetKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Todo => "todo",
+            Self::Doit => "doit",
+            Self::Label => "label",
+        }
+    }
+}
+
+// 書き込みと同じトランザクションで実行すれば、コミットされたときにだけ通知される
+pub(crate) async fn notify_change<'e, E: PgExecutor<'e>>(
+    executor: E,
+    target: ChangeTargetKind,
+    action: ChangeAction,
+    id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"
+        SELECT pg_notify(
+            $1,
+            json_build_object(
+                'id', nextval('change_event_id_seq'),
+                'target', $2::TEXT,
+                'action', $3::TEXT,
+                'entity_id', $4::UUID,
+                'occurred_at', CURRENT_TIMESTAMP
+            )::TEXT
+        )
+        "#,
+        CHANGE_CHANNEL,
+        target.as_str(),
+        action.to_string(),
+        id,
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangeNotification {
+    id: u64,
+    target: String,
+    action: String,
+    entity_id: Uuid,
+    occurred_at: String,
+}
+
+// どのプロセスで書き込まれた変更も LISTEN で受け取り、このプロセスの購読者に配信する
+pub struct PgChangeStreamRepository {
+    hub: Arc<ChangeHub>,
+}
+
+impl PgChangeStreamRepository {
+    // NOTE: 受け取った変更の内容を読むためにリポジトリを使う
+    pub async fn new(db: Postgresql) -> Result<Self, PostgresqlError> {
+        let hub = Arc::new(ChangeHub::new());
+
+        // NOTE: 起動した時点から取りこぼさないよう、LISTEN してから返す
+        let mut listener = PgListener::connect_with(&db).await?;
+        listener.listen(CHANGE_CHANNEL).await?;
+
+        tokio::spawn(listen(
+            listener,
+            Arc::clone(&hub),
+            ChangeLoader {
+                todo_repository: PgTodoRepository::new(db.clone()),
+                doit_repository: PgDoitRepository::new(db.clone()),
+                label_repository: PgLabelRepository::new(db),
+            },
+        ));
+
+        Ok(Self { hub })
+    }
+}
+
+impl ChangeStreamRepository for PgChangeStreamRepository {
+    type Subscription = PgChangeSubscription;
+
+    async fn subscribe(
+        &self,
+        after: Option<ChangeEventId>,
+    ) -> Result<Self::Subscription, ChangeStreamRepositoryError> {
+        self.hub.subscribe(after)
+    }
+}
+
+pub struct PgChangeSubscription {
+    backlog: VecDeque<ChangeEvent>,
+    receiver: broadcast::Receiver<ChangeEvent>,
+}
+
+impl ChangeSubscription for PgChangeSubscription {
+    async fn next(&mut self) -> Option<ChangeEvent> {
+        if let Some(event) = self.backlog.pop_front() {
+            return Some(event);
+        }
+
+        match self.receiver.recv().await {
+            Ok(event) => Some(event),
+            // NOTE: 取りこぼした場合は購読を終わらせ、最後に受け取った ID から再開してもらう
+            Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => None,
+        }
+    }
+}
+
+struct ChangeHub {
+    backlog: Mutex<VecDeque<ChangeEvent>>,
+    sender: broadcast::Sender<ChangeEvent>,
+}
+
+impl ChangeHub {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANGE_STREAM_CHANNEL_CAPACITY);
+
+        Self {
+            backlog: Mutex::new(VecDeque::with_capacity(CHANGE_STREAM_BACKLOG_SIZE)),
+            sender,
+        }
+    }
+
+    fn push(&self, event: ChangeEvent) -> Result<(), ChangeStreamRepositoryError> {
+        let mut backlog = self
+            .backlog
+            .lock()
+            .map_err(|e| ChangeStreamRepositoryError::InternalError(e.to_string()))?;
+
+        if backlog.len() == CHANGE_STREAM_BACKLOG_SIZE {
+            backlog.pop_front();
+        }
+        backlog.push_back(event.clone());
+
+        // NOTE: 購読者がいない場合は Err になるが、配信する相手がいないだけなので無視する
+        let _ = self.sender.send(event);
+
+        Ok(())
+    }
+
+    fn subscribe(
+        &self,
+        after: Option<ChangeEventId>,
+    ) -> Result<PgChangeSubscription, ChangeStreamRepositoryError> {
+        let backlog = self
+            .backlog
+            .lock()
+            .map_err(|e| ChangeStreamRepositoryError::InternalError(e.to_string()))?;
+
+        // NOTE: push と同じロックの中で購読を始めることで、保持している変更と受け取る変更が重複も欠落もしない
+        let receiver = self.sender.subscribe();
+
+        // NOTE: ID はコミットの順と前後することがあるため、受け取った順で after より後のものを返す
+        let resumed = match after {
+            Some(after) => match backlog.iter().position(|e| *e.id() == after) {
+                Some(i) => backlog.iter().skip(i + 1).cloned().collect(),
+                None => {
+                    let after = after.value();
+                    backlog
+                        .iter()
+                        .filter(|e| e.id().clone().value() > after)
+                        .cloned()
+                        .collect()
+                }
+            },
+            None => VecDeque::new(),
+        };
+
+        Ok(PgChangeSubscription {
+            backlog: resumed,
+            receiver,
+        })
+    }
+}
+
+struct ChangeLoader {
+    todo_repository: PgTodoRepository,
+    doit_repository: PgDoitRepository,
+    label_repository: PgLabelRepository,
+}
+
+impl ChangeLoader {
+    // 通知された変更の対象を読む。読んだ時点の状態になるので、通知より新しいことがある
+    async fn load(
+        &self,
+        notification: ChangeNotification,
+    ) -> Result<Option<ChangeEvent>, ChangeStreamRepositoryError> {
+        let action = match notification.action.as_str() {
+            "created" => ChangeAction::Created,
+            "updated" => ChangeAction::Updated,
+            "deleted" => ChangeAction::Deleted,
+            a => {
+                return Err(ChangeStreamRepositoryError::InternalError(format!(
+                    "unknown action; action={a}"
+                )))
+            }
+        };
+
+        let target = match notification.target.as_str() {
+            "todo" => self
+                .todo_repository
+                .get_by_id(TodoId::new(notification.entity_id))
+                .await
+                .map_err(|e| ChangeStreamRepositoryError::InternalError(e.to_string()))?
+                .map(ChangeTarget::Todo),
+            "doit" => self
+                .doit_repository
+                .get_by_id(DoitId::new(notification.entity_id))
+                .await
+                .map_err(|e| ChangeStreamRepositoryError::InternalError(e.to_string()))?
+                .map(ChangeTarget::Doit),
+            "label" => self
+                .label_repository
+                .get_by_id(LabelId::new(notification.entity_id))
+                .await
+                .map_err(|e| ChangeStreamRepositoryError::InternalError(e.to_string()))?
+                .map(ChangeTarget::Label),
+            t => {
+                return Err(ChangeStreamRepositoryError::InternalError(format!(
+                    "unknown target; target={t}"
+                )))
+            }
+        };
+
+        let occurred_at = DateTime::try_from(notification.occurred_at).map_err(|e| {
+            ChangeStreamRepositoryError::InternalError(ErrorCode::from(e).to_string())
+        })?;
+
+        Ok(target.map(|target| {
+            ChangeEvent::new(
+                ChangeEventId::new(notification.id),
+                action,
+                target,
+                occurred_at,
+            )
+        }))
+    }
+}
+
+// プロセスごとに1つ動かす
+async fn listen(mut listener: PgListener, hub: Arc<ChangeHub>, loader: ChangeLoader) {
+    loop {
+        let notification = match listener.try_recv().await {
+            Ok(Some(notification)) => notification,
+            // NOTE: 接続が切れた場合は次の try_recv で再接続される。その間の変更は受け取れない
+            Ok(None) => {
+                tracing::warn!("change listener disconnected; reconnecting");
+                continue;
+            }
+            Err(e) => {
+                tracing::error!("failed to receive change notification: {}", e);
+                tokio::time::sleep(CHANGE_LISTEN_RETRY_INTERVAL).await;
+                continue;
+            }
+        };
+
+        let res = match serde_json::from_str::<ChangeNotification>(notification.payload()) {
+            Ok(notification) => loader.load(notification).await,
+            Err(e) => Err(ChangeStreamRepositoryError::InternalError(e.to_string())),
+        };
+
+        match res.and_then(|event| event.map(|e| hub.push(e)).transpose()) {
+            Ok(_) => {}
+            Err(e) => tracing::error!("failed to handle change notification: {}", e),
+        }
+    }
+}
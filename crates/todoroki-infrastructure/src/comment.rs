@@ -0,0 +1,188 @@
+use crate::shared::postgresql::Postgresql;
+
+use sqlx::{prelude::FromRow, types::chrono};
+use todoroki_domain::{
+    entities::{
+        comment::{Comment, CommentBody, CommentId, CommentTarget},
+        doit::DoitId,
+        todo::TodoId,
+        user::UserId,
+    },
+    repositories::comment::{CommentRepository, CommentRepositoryError},
+    value_objects::datetime::DateTime,
+};
+use uuid::Uuid;
+
+#[derive(FromRow)]
+struct CommentRow {
+    id: Uuid,
+    todo_id: Option<Uuid>,
+    doit_id: Option<Uuid>,
+    parent_id: Option<Uuid>,
+    body: String,
+    created_by: Uuid,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+struct CommentIdColumn {
+    id: Uuid,
+}
+
+impl TryFrom<CommentRow> for Comment {
+    type Error = CommentRepositoryError;
+
+    fn try_from(value: CommentRow) -> Result<Self, Self::Error> {
+        let target = match (value.todo_id, value.doit_id) {
+            (Some(todo_id), None) => CommentTarget::Todo(TodoId::new(todo_id)),
+            (None, Some(doit_id)) => CommentTarget::Doit(DoitId::new(doit_id)),
+            _ => {
+                return Err(CommentRepositoryError::InternalError(format!(
+                    "comment {} must belong to either a todo or a doit",
+                    value.id
+                )))
+            }
+        };
+
+        Ok(Self::new(
+            CommentId::new(value.id),
+            target,
+            value.parent_id.map(CommentId::new),
+            CommentBody::new(value.body),
+            UserId::new(value.created_by),
+            DateTime::new(value.created_at),
+            DateTime::new(value.updated_at),
+            value.deleted_at.map(DateTime::new),
+        ))
+    }
+}
+
+// (todo_id, doit_id)
+fn target_into_columns(target: &CommentTarget) -> (Option<Uuid>, Option<Uuid>) {
+    match target {
+        CommentTarget::Todo(id) => (Some(id.clone().value()), None),
+        CommentTarget::Doit(id) => (None, Some(id.clone().value())),
+    }
+}
+
+pub struct PgCommentRepository {
+    db: Postgresql,
+}
+
+impl PgCommentRepository {
+    pub fn new(db: Postgresql) -> Self {
+        Self { db }
+    }
+}
+
+impl CommentRepository for PgCommentRepository {
+    async fn create(&self, comment: Comment) -> Result<CommentId, CommentRepositoryError> {
+        let (todo_id, doit_id) = target_into_columns(comment.target());
+
+        let res = sqlx::query_as!(
+            CommentIdColumn,
+            r#"
+            INSERT INTO comments (id, todo_id, doit_id, parent_id, body, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id
+            "#,
+            comment.id().clone().value(),
+            todo_id,
+            doit_id,
+            comment.parent_id().clone().map(|id| id.value()),
+            comment.body().clone().value(),
+            comment.created_by().clone().value(),
+        )
+        .fetch_one(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| CommentRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(CommentId::new(res.id))
+    }
+
+    async fn update(&self, id: CommentId, body: CommentBody) -> Result<(), CommentRepositoryError> {
+        sqlx::query!(
+            r#"UPDATE comments SET body = $2 WHERE id = $1 AND deleted_at IS NULL"#,
+            id.value(),
+            body.value(),
+        )
+        .execute(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| CommentRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: CommentId) -> Result<Option<Comment>, CommentRepositoryError> {
+        let res = sqlx::query_as!(
+            CommentRow,
+            r#"SELECT
+            id,
+            todo_id AS "todo_id?",
+            doit_id AS "doit_id?",
+            parent_id AS "parent_id?",
+            body,
+            created_by,
+            created_at,
+            updated_at,
+            deleted_at AS "deleted_at?"
+            FROM comments
+            WHERE id = $1"#,
+            id.value()
+        )
+        .fetch_optional(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| CommentRepositoryError::InternalError(e.to_string()))?;
+
+        res.map(Comment::try_from).transpose()
+    }
+
+    async fn list_by_target(
+        &self,
+        target: CommentTarget,
+    ) -> Result<Vec<Comment>, CommentRepositoryError> {
+        let (todo_id, doit_id) = target_into_columns(&target);
+
+        let res = sqlx::query_as!(
+            CommentRow,
+            r#"SELECT
+            id,
+            todo_id AS "todo_id?",
+            doit_id AS "doit_id?",
+            parent_id AS "parent_id?",
+            body,
+            created_by,
+            created_at,
+            updated_at,
+            deleted_at AS "deleted_at?"
+            FROM comments
+            WHERE ($1::uuid IS NOT NULL AND todo_id = $1)
+            OR ($2::uuid IS NOT NULL AND doit_id = $2)
+            ORDER BY created_at ASC, id ASC"#,
+            todo_id,
+            doit_id,
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| CommentRepositoryError::InternalError(e.to_string()))?;
+
+        res.into_iter().map(Comment::try_from).collect()
+    }
+
+    async fn delete_by_id(&self, id: CommentId) -> Result<(), CommentRepositoryError> {
+        sqlx::query!(
+            r#"
+            UPDATE comments
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            id.value()
+        )
+        .execute(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| CommentRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+}
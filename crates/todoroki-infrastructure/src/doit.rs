@@ -1,15 +1,35 @@
-use crate::{label::LabelRow, shared::postgresql::Postgresql};
+use crate::{
+    audit::record_audit_event,
+    change_stream::{notify_change, ChangeTargetKind},
+    label::{label_condition_into_arrays, LabelRow},
+    shared::{
+        pagination::{datetime_sort_key, into_page, missing_sort_key, page_request_into_params},
+        postgresql::Postgresql,
+    },
+    todo::insert_todo,
+    webhook::enqueue_webhook_events,
+};
 
 use futures_util::TryStreamExt;
-use sqlx::{prelude::FromRow, types::chrono};
+use sqlx::{postgres::PgExecutor, prelude::FromRow, types::chrono};
 use todoroki_domain::{
     entities::{
-        doit::{Doit, DoitDescription, DoitId, DoitName, DoitPublishment, DoitUpdateCommand},
+        audit::{AuditAction, AuditActor},
+        change::ChangeAction,
+        doit::{
+            Doit, DoitAcceptance, DoitDescription, DoitId, DoitName, DoitPublishment,
+            DoitReviewCommand, DoitStatus, DoitUpdateCommand,
+        },
         label::Label,
         todo::TodoId,
         user::UserId,
+        webhook::{WebhookEvent, WebhookEventsFn},
+    },
+    repositories::{
+        doit::{DoitAffectsToFilter, DoitFilter, DoitRepository, DoitRepositoryError, DoitSortKey},
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
     },
-    repositories::doit::{DoitRepository, DoitRepositoryError},
     value_objects::datetime::DateTime,
 };
 use uuid::Uuid;
@@ -22,11 +42,15 @@ struct DoitRow {
     is_public: bool,
     alternative_name: Option<String>,
     affects_to: Option<Uuid>,
+    status: DoitStatusColumn,
+    status_reason: Option<String>,
+    duplicate_of: Option<Uuid>,
+    reviewed_at: Option<chrono::DateTime<chrono::Utc>>,
     deadlined_at: Option<chrono::DateTime<chrono::Utc>>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
-    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
     labels: serde_json::Value,
+    voters: Vec<Uuid>,
     created_by: Uuid,
 }
 
@@ -34,6 +58,40 @@ struct DoitIdColumn {
     id: Uuid,
 }
 
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "doit_status", rename_all = "snake_case")]
+pub enum DoitStatusColumn {
+    Pending,
+    OnHold,
+    Accepted,
+    Rejected,
+    Duplicate,
+}
+
+impl From<DoitStatus> for DoitStatusColumn {
+    fn from(value: DoitStatus) -> Self {
+        match value {
+            DoitStatus::Pending => Self::Pending,
+            DoitStatus::OnHold => Self::OnHold,
+            DoitStatus::Accepted => Self::Accepted,
+            DoitStatus::Rejected => Self::Rejected,
+            DoitStatus::Duplicate => Self::Duplicate,
+        }
+    }
+}
+
+impl From<DoitStatusColumn> for DoitStatus {
+    fn from(value: DoitStatusColumn) -> Self {
+        match value {
+            DoitStatusColumn::Pending => Self::Pending,
+            DoitStatusColumn::OnHold => Self::OnHold,
+            DoitStatusColumn::Accepted => Self::Accepted,
+            DoitStatusColumn::Rejected => Self::Rejected,
+            DoitStatusColumn::Duplicate => Self::Duplicate,
+        }
+    }
+}
+
 impl TryFrom<DoitRow> for Doit {
     type Error = DoitRepositoryError;
 
@@ -54,7 +112,12 @@ impl TryFrom<DoitRow> for Doit {
                 DoitPublishment::Private(value.alternative_name)
             },
             labels,
+            value.voters.into_iter().map(UserId::new).collect(),
             value.affects_to.map(TodoId::new),
+            DoitStatus::from(value.status),
+            value.status_reason,
+            value.duplicate_of.map(DoitId::new),
+            value.reviewed_at.map(DateTime::new),
             value.deadlined_at.map(DateTime::new),
             DateTime::new(value.created_at),
             DateTime::new(value.updated_at),
@@ -63,6 +126,34 @@ impl TryFrom<DoitRow> for Doit {
     }
 }
 
+fn doit_sort_key_column(sort: &DoitSortKey) -> &'static str {
+    match sort {
+        DoitSortKey::CreatedAt => "created_at",
+        DoitSortKey::UpdatedAt => "updated_at",
+        DoitSortKey::Deadline => "deadline",
+        DoitSortKey::Name => "name",
+        DoitSortKey::Votes => "votes",
+    }
+}
+
+// NOTE: list の sort_key と同じ値を返すこと
+fn doit_sort_key(doit: &Doit, page: &PageRequest<DoitSortKey>) -> String {
+    match page.sort() {
+        DoitSortKey::CreatedAt => datetime_sort_key(doit.created_at()),
+        DoitSortKey::UpdatedAt => datetime_sort_key(doit.updated_at()),
+        DoitSortKey::Deadline => doit
+            .deadlined_at()
+            .as_ref()
+            .map(datetime_sort_key)
+            .unwrap_or_else(|| missing_sort_key(page.is_desc())),
+        DoitSortKey::Name => match doit.is_public() {
+            DoitPublishment::Public => doit.name().clone().value(),
+            DoitPublishment::Private(alt) => alt.clone().unwrap_or_default(),
+        },
+        DoitSortKey::Votes => format!("{:010}", doit.voters().len()),
+    }
+}
+
 pub struct PgDoitRepository {
     db: Postgresql,
 }
@@ -74,7 +165,12 @@ impl PgDoitRepository {
 }
 
 impl DoitRepository for PgDoitRepository {
-    async fn create(&self, doit: Doit) -> Result<DoitId, DoitRepositoryError> {
+    async fn create(
+        &self,
+        doit: Doit,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<DoitId, DoitRepositoryError> {
         let mut tx = self
             .db
             .begin()
@@ -116,6 +212,23 @@ impl DoitRepository for PgDoitRepository {
             .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
         }
 
+        record_audit_event(&mut *tx, AuditAction::Create, &actor, None, Some(&doit))
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        enqueue_webhook_events(&mut tx, &events)
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Doit,
+            ChangeAction::Created,
+            res.id,
+        )
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
         tx.commit()
             .await
             .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
@@ -123,11 +236,24 @@ impl DoitRepository for PgDoitRepository {
         Ok(DoitId::new(res.id))
     }
 
-    async fn update(&self, cmd: DoitUpdateCommand) -> Result<(), DoitRepositoryError> {
+    async fn update(
+        &self,
+        cmd: DoitUpdateCommand,
+        actor: AuditActor,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<(), DoitRepositoryError> {
         if cmd.is_nothing_todo() {
             return Ok(());
         }
 
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_doit(&mut tx, cmd.id().clone()).await?;
+
         sqlx::query!(
             r#"
             UPDATE doits
@@ -159,15 +285,288 @@ impl DoitRepository for PgDoitRepository {
                 .map(|opt_t| opt_t.map(|t| t.value()))
                 .flatten(),
         )
-        .execute(&*self.db)
+        .execute(&mut *tx)
         .await
         .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
 
+        let after = fetch_doit(&mut *tx, cmd.id().clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Update,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Doit,
+            ChangeAction::Updated,
+            cmd.id().clone().value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
         Ok(())
     }
 
     async fn get_by_id(&self, id: DoitId) -> Result<Option<Doit>, DoitRepositoryError> {
-        let res: Result<Option<DoitRow>, sqlx::Error> = sqlx::query_as!(
+        fetch_doit(&*self.db, id).await
+    }
+
+    async fn review(
+        &self,
+        cmd: DoitReviewCommand,
+        actor: AuditActor,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<(), DoitRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_doit(&mut tx, cmd.id().clone()).await?;
+
+        // NOTE: 審査をやり直す場合は審査日時を消す
+        sqlx::query!(
+            r#"
+            UPDATE doits
+            SET
+                status = $2::doit_status,
+                status_reason = $3,
+                duplicate_of = $4,
+                reviewed_at = CASE WHEN $2::doit_status = 'pending' THEN NULL ELSE CURRENT_TIMESTAMP END
+            WHERE id = $1
+            "#,
+            cmd.id().clone().value(),
+            DoitStatusColumn::from(*cmd.status()) as DoitStatusColumn,
+            cmd.reason().clone(),
+            cmd.duplicate_of().clone().map(|id| id.value()),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_doit(&mut *tx, cmd.id().clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::StatusChange,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Doit,
+            ChangeAction::Updated,
+            cmd.id().clone().value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn vote(&self, id: DoitId, user_id: UserId) -> Result<(), DoitRepositoryError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO doit_votes (doit_id, user_id) VALUES ($1, $2)
+            ON CONFLICT (doit_id, user_id) DO NOTHING
+            "#,
+            id.clone().value(),
+            user_id.value(),
+        )
+        .execute(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        notify_change(
+            &*self.db,
+            ChangeTargetKind::Doit,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn unvote(&self, id: DoitId, user_id: UserId) -> Result<(), DoitRepositoryError> {
+        sqlx::query!(
+            r#"DELETE FROM doit_votes WHERE doit_id = $1 AND user_id = $2"#,
+            id.clone().value(),
+            user_id.value(),
+        )
+        .execute(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        notify_change(
+            &*self.db,
+            ChangeTargetKind::Doit,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn accept(
+        &self,
+        id: DoitId,
+        acceptance: DoitAcceptance,
+        actor: AuditActor,
+        todo_events: Vec<WebhookEvent>,
+        events: WebhookEventsFn<Doit>,
+    ) -> Result<Option<TodoId>, DoitRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_doit(&mut tx, id.clone()).await?;
+
+        let todo_id = match acceptance {
+            DoitAcceptance::NewTodo(todo) => {
+                let todo_id = insert_todo(&mut tx, &todo)
+                    .await
+                    .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+                record_audit_event(&mut *tx, AuditAction::Create, &actor, None, Some(&*todo))
+                    .await
+                    .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+                enqueue_webhook_events(&mut tx, &todo_events)
+                    .await
+                    .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+                notify_change(
+                    &mut *tx,
+                    ChangeTargetKind::Todo,
+                    ChangeAction::Created,
+                    todo_id.clone().value(),
+                )
+                .await
+                .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+                todo_id
+            }
+            DoitAcceptance::ExistingTodo(todo_id) => todo_id,
+        };
+
+        // NOTE: 同時に受け入れられたときに片方だけが成功するよう、審査中のものだけを更新する
+        let res = sqlx::query!(
+            r#"
+            UPDATE doits
+            SET
+                affects_to = $2,
+                status = 'accepted',
+                status_reason = NULL,
+                duplicate_of = NULL,
+                reviewed_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            AND affects_to IS NULL
+            AND status IN ('pending', 'on_hold')
+            AND deleted_at IS NULL
+            "#,
+            id.clone().value(),
+            todo_id.clone().value(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        if res.rows_affected() == 0 {
+            tx.rollback()
+                .await
+                .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+            return Ok(None);
+        }
+
+        let after = fetch_doit(&mut *tx, id.clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::StatusChange,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Doit,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DoitRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(Some(todo_id))
+    }
+
+    async fn list(
+        &self,
+        filter: DoitFilter,
+        page: PageRequest<DoitSortKey>,
+    ) -> Result<Page<Doit>, DoitRepositoryError> {
+        let (any_labels, all_labels) = label_condition_into_arrays(filter.labels().clone());
+        let (cursor_key, cursor_id, limit) = page_request_into_params(&page);
+
+        // (affects_to IS NOT NULL, affects_to)
+        let (is_linked, affects_to) = match filter.affects_to() {
+            Some(DoitAffectsToFilter::Unlinked) => (Some(false), None),
+            Some(DoitAffectsToFilter::Linked) => (Some(true), None),
+            Some(DoitAffectsToFilter::Todo(id)) => (None, Some(id.clone().value())),
+            None => (None, None),
+        };
+
+        let res = sqlx::query_as!(
             DoitRow,
             r#"SELECT
             doits.id AS "id",
@@ -176,11 +575,19 @@ impl DoitRepository for PgDoitRepository {
             doits.is_public AS "is_public",
             doits.alternative_name AS "alternative_name",
             doits.affects_to AS "affects_to?",
+            doits.status AS "status: DoitStatusColumn",
+            doits.status_reason AS "status_reason?",
+            doits.duplicate_of AS "duplicate_of?",
+            doits.reviewed_at AS "reviewed_at?",
             doits.deadlined_at AS "deadlined_at?",
             doits.created_at AS "created_at",
             doits.updated_at AS "updated_at",
-            doits.deleted_at AS "deleted_at?",
             doits.created_by AS "created_by",
+            ARRAY(
+                SELECT dv.user_id FROM doit_votes dv
+                WHERE dv.doit_id = doits.id
+                ORDER BY dv.created_at
+            ) AS "voters!",
             COALESCE(
                 json_agg(
                     json_build_object(
@@ -189,28 +596,87 @@ impl DoitRepository for PgDoitRepository {
                         'description', l.description,
                         'color', l.color,
                         'created_at', l.created_at,
-                        'updated_at', l.updated_at,
-                        'deleted_at', l.deleted_at
+                        'updated_at', l.updated_at
                     )
                 ) FILTER (WHERE l.id IS NOT NULL),
                 '[]'
             ) AS "labels"
             FROM doits
+            CROSS JOIN LATERAL (
+                SELECT (CASE $9::text
+                    WHEN 'created_at' THEN to_char(doits.created_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'updated_at' THEN to_char(doits.updated_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'deadline' THEN COALESCE(
+                        to_char(doits.deadlined_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS'),
+                        CASE WHEN $10::bool THEN '' ELSE '~' END
+                    )
+                    WHEN 'name' THEN CASE WHEN doits.is_public THEN doits.name ELSE COALESCE(doits.alternative_name, '') END
+                    WHEN 'votes' THEN lpad((SELECT count(*) FROM doit_votes dv WHERE dv.doit_id = doits.id)::text, 10, '0')
+                END) COLLATE "C" AS sort_key
+            ) k
             LEFT JOIN doit_labels tl ON doits.id = tl.doit_id
             LEFT JOIN labels l ON tl.label_id = l.id
-            WHERE doits.id = $1
-            GROUP BY doits.id"#,
-            id.value()
+            WHERE ($1::uuid[] IS NULL OR EXISTS (
+                SELECT 1 FROM doit_labels fdl
+                WHERE fdl.doit_id = doits.id AND fdl.label_id = ANY($1)
+            ))
+            AND ($2::uuid[] IS NULL OR NOT EXISTS (
+                SELECT 1 FROM unnest($2::uuid[]) AS required(label_id)
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM doit_labels fdl
+                    WHERE fdl.doit_id = doits.id AND fdl.label_id = required.label_id
+                )
+            ))
+            AND ($3::bool IS NULL OR doits.is_public = $3)
+            AND ($4::timestamptz IS NULL OR doits.deadlined_at >= $4)
+            AND ($5::timestamptz IS NULL OR doits.deadlined_at < $5)
+            AND ($6::uuid IS NULL OR doits.created_by = $6)
+            AND ($7::bool IS NULL OR (doits.affects_to IS NOT NULL) = $7)
+            AND ($8::uuid IS NULL OR doits.affects_to = $8)
+            AND ($14::doit_status IS NULL OR doits.status = $14)
+            AND ($11::text IS NULL OR CASE
+                WHEN $10 THEN (k.sort_key, doits.id) < ($11 COLLATE "C", $12::uuid)
+                ELSE (k.sort_key, doits.id) > ($11 COLLATE "C", $12::uuid)
+            END)
+            GROUP BY doits.id, k.sort_key
+            ORDER BY
+                CASE WHEN $10 THEN k.sort_key END DESC,
+                CASE WHEN $10 THEN doits.id END DESC,
+                CASE WHEN NOT $10 THEN k.sort_key END ASC,
+                CASE WHEN NOT $10 THEN doits.id END ASC
+            LIMIT $13"#,
+            any_labels.as_deref(),
+            all_labels.as_deref(),
+            *filter.is_public(),
+            filter.deadlined_from().clone().map(|t| t.value()),
+            filter.deadlined_to().clone().map(|t| t.value()),
+            filter.created_by().clone().map(|id| id.value()),
+            is_linked,
+            affects_to,
+            doit_sort_key_column(page.sort()),
+            page.is_desc(),
+            cursor_key,
+            cursor_id,
+            limit,
+            filter.status().map(DoitStatusColumn::from) as Option<DoitStatusColumn>,
         )
-        .fetch_optional(&*self.db)
-        .await;
+        .fetch(&*self.db)
+        .and_then(|row| async move {
+            Doit::try_from(row).map_err(|e| sqlx::Error::ColumnDecode {
+                index: "labels".into(),
+                source: Box::new(e),
+            })
+        })
+        .try_collect::<Vec<Doit>>()
+        .await
+        .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
 
-        res.map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?
-            .map(Doit::try_from)
-            .transpose()
+        Ok(into_page(res, &page, |doit| {
+            page.cursor_for(doit_sort_key(doit, &page), doit.id().clone().value())
+        }))
     }
 
-    async fn list(&self) -> Result<Vec<Doit>, DoitRepositoryError> {
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Doit>, DoitRepositoryError> {
         let res = sqlx::query_as!(
             DoitRow,
             r#"SELECT
@@ -220,11 +686,19 @@ impl DoitRepository for PgDoitRepository {
             doits.is_public AS "is_public",
             doits.alternative_name AS "alternative_name",
             doits.affects_to AS "affects_to?",
+            doits.status AS "status: DoitStatusColumn",
+            doits.status_reason AS "status_reason?",
+            doits.duplicate_of AS "duplicate_of?",
+            doits.reviewed_at AS "reviewed_at?",
             doits.deadlined_at AS "deadlined_at?",
             doits.created_at AS "created_at",
             doits.updated_at AS "updated_at",
-            doits.deleted_at AS "deleted_at?",
             doits.created_by AS "created_by",
+            ARRAY(
+                SELECT dv.user_id FROM doit_votes dv
+                WHERE dv.doit_id = doits.id
+                ORDER BY dv.created_at
+            ) AS "voters!",
             COALESCE(
                 json_agg(
                     json_build_object(
@@ -233,17 +707,30 @@ impl DoitRepository for PgDoitRepository {
                         'description', l.description,
                         'color', l.color,
                         'created_at', l.created_at,
-                        'updated_at', l.updated_at,
-                        'deleted_at', l.deleted_at
+                        'updated_at', l.updated_at
                     )
                 ) FILTER (WHERE l.id IS NOT NULL),
                 '[]'
             ) AS "labels"
             FROM doits
+            CROSS JOIN websearch_to_tsquery('simple', $1) q
             LEFT JOIN doit_labels tl ON doits.id = tl.doit_id
             LEFT JOIN labels l ON tl.label_id = l.id
-            GROUP BY doits.id
-            ORDER BY doits.updated_at DESC"#
+            WHERE ($2 AND doits.search_vector @@ q)
+            OR (NOT $2 AND doits.public_search_vector @@ q)
+            OR doits.id IN (
+                SELECT fdl.doit_id FROM doit_labels fdl
+                JOIN labels fl ON fdl.label_id = fl.id
+                WHERE to_tsvector('simple', fl.name) @@ q
+            )
+            GROUP BY doits.id, q
+            ORDER BY
+                ts_rank(CASE WHEN $2 THEN doits.search_vector ELSE doits.public_search_vector END, q) DESC,
+                doits.updated_at DESC
+            LIMIT $3"#,
+            query.text(),
+            *query.include_private(),
+            i64::from(*query.limit()),
         )
         .fetch(&*self.db)
         .and_then(|row| async move {
@@ -263,3 +750,73 @@ impl DoitRepository for PgDoitRepository {
         todo!()
     }
 }
+
+async fn fetch_doit<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: DoitId,
+) -> Result<Option<Doit>, DoitRepositoryError> {
+    let res: Result<Option<DoitRow>, sqlx::Error> = sqlx::query_as!(
+        DoitRow,
+        r#"SELECT
+        doits.id AS "id",
+        doits.name AS "name",
+        doits.description AS "description",
+        doits.is_public AS "is_public",
+        doits.alternative_name AS "alternative_name",
+        doits.affects_to AS "affects_to?",
+        doits.status AS "status: DoitStatusColumn",
+        doits.status_reason AS "status_reason?",
+        doits.duplicate_of AS "duplicate_of?",
+        doits.reviewed_at AS "reviewed_at?",
+        doits.deadlined_at AS "deadlined_at?",
+        doits.created_at AS "created_at",
+        doits.updated_at AS "updated_at",
+        doits.created_by AS "created_by",
+        ARRAY(
+            SELECT dv.user_id FROM doit_votes dv
+            WHERE dv.doit_id = doits.id
+            ORDER BY dv.created_at
+        ) AS "voters!",
+        COALESCE(
+            json_agg(
+                json_build_object(
+                    'id', l.id,
+                    'name', l.name,
+                    'description', l.description,
+                    'color', l.color,
+                    'created_at', l.created_at,
+                    'updated_at', l.updated_at
+                )
+            ) FILTER (WHERE l.id IS NOT NULL),
+            '[]'
+        ) AS "labels"
+        FROM doits
+        LEFT JOIN doit_labels tl ON doits.id = tl.doit_id
+        LEFT JOIN labels l ON tl.label_id = l.id
+        WHERE doits.id = $1
+        GROUP BY doits.id"#,
+        id.value()
+    )
+    .fetch_optional(executor)
+    .await;
+
+    res.map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?
+        .map(Doit::try_from)
+        .transpose()
+}
+
+// 変更前の内容を取得し、コミットするまで他から変更されないようにロックしておく
+async fn lock_doit(
+    conn: &mut sqlx::PgConnection,
+    id: DoitId,
+) -> Result<Option<Doit>, DoitRepositoryError> {
+    sqlx::query!(
+        r#"SELECT id FROM doits WHERE id = $1 FOR UPDATE"#,
+        id.clone().value(),
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e: sqlx::Error| DoitRepositoryError::InternalError(e.to_string()))?;
+
+    fetch_doit(&mut *conn, id).await
+}
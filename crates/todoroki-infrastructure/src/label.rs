@@ -1,10 +1,27 @@
-use crate::shared::postgresql::Postgresql;
+use crate::{
+    audit::record_audit_event,
+    change_stream::{notify_change, ChangeTargetKind},
+    shared::{
+        pagination::{datetime_sort_key, into_page, page_request_into_params},
+        postgresql::Postgresql,
+    },
+    webhook::enqueue_webhook_events,
+};
 
 use futures_util::{StreamExt, TryStreamExt};
 use sqlx::{prelude::FromRow, types::chrono};
 use todoroki_domain::{
-    entities::label::{Label, LabelColor, LabelDescription, LabelId, LabelName},
-    repositories::label::{LabelRepository, LabelRepositoryError},
+    entities::{
+        audit::{AuditAction, AuditActor},
+        change::ChangeAction,
+        label::{Label, LabelColor, LabelDescription, LabelId, LabelName},
+        webhook::WebhookEvent,
+    },
+    repositories::{
+        label::{LabelCondition, LabelRepository, LabelRepositoryError, LabelSortKey},
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
+    },
     value_objects::datetime::DateTime,
 };
 use uuid::Uuid;
@@ -17,7 +34,6 @@ pub(super) struct LabelRow {
     color: Option<i32>,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
-    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 struct LabelIdColumn {
@@ -30,7 +46,7 @@ impl From<LabelRow> for Label {
             LabelId::new(value.id),
             LabelName::new(value.name),
             LabelDescription::new(value.description),
-            value.color.map(|i| color_from_i32(i)),
+            value.color.map(color_from_i32),
             DateTime::new(value.created_at),
             DateTime::new(value.updated_at),
         )
@@ -49,6 +65,38 @@ fn color_from_i32(value: i32) -> LabelColor {
     )
 }
 
+// (any, all) の形にして SQL に渡す
+pub(super) fn label_condition_into_arrays(
+    value: Option<LabelCondition>,
+) -> (Option<Vec<Uuid>>, Option<Vec<Uuid>>) {
+    match value {
+        Some(LabelCondition::Any(ids)) => {
+            (Some(ids.into_iter().map(|id| id.value()).collect()), None)
+        }
+        Some(LabelCondition::All(ids)) => {
+            (None, Some(ids.into_iter().map(|id| id.value()).collect()))
+        }
+        None => (None, None),
+    }
+}
+
+fn label_sort_key_column(sort: &LabelSortKey) -> &'static str {
+    match sort {
+        LabelSortKey::CreatedAt => "created_at",
+        LabelSortKey::UpdatedAt => "updated_at",
+        LabelSortKey::Name => "name",
+    }
+}
+
+// NOTE: list の sort_key と同じ値を返すこと
+fn label_sort_key(label: &Label, page: &PageRequest<LabelSortKey>) -> String {
+    match page.sort() {
+        LabelSortKey::CreatedAt => datetime_sort_key(label.created_at()),
+        LabelSortKey::UpdatedAt => datetime_sort_key(label.updated_at()),
+        LabelSortKey::Name => label.name().clone().value(),
+    }
+}
+
 pub struct PgLabelRepository {
     db: Postgresql,
 }
@@ -60,7 +108,18 @@ impl PgLabelRepository {
 }
 
 impl LabelRepository for PgLabelRepository {
-    async fn create(&self, label: Label) -> Result<LabelId, LabelRepositoryError> {
+    async fn create(
+        &self,
+        label: Label,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<LabelId, LabelRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| LabelRepositoryError::InternalError(e.to_string()))?;
+
         let res = sqlx::query_as!(
             LabelIdColumn,
             r#"
@@ -73,20 +132,62 @@ impl LabelRepository for PgLabelRepository {
             label.description().clone().value(),
             label.color().clone().map(|c: LabelColor| color_into_i32(c))
         )
-        .fetch_one(&*self.db)
+        .fetch_one(&mut *tx)
         .await;
 
-        match res {
-            Ok(id_column) => Ok(LabelId::new(id_column.id)),
-            Err(e) => match e.as_database_error() {
-                Some(e) => Err(LabelRepositoryError::InternalError(e.message().to_string())),
-                _ => Err(LabelRepositoryError::InternalError(e.to_string())),
-            },
-        }
+        let id = res
+            .map_err(|e| match e.as_database_error() {
+                Some(e) => LabelRepositoryError::InternalError(e.message().to_string()),
+                _ => LabelRepositoryError::InternalError(e.to_string()),
+            })?
+            .id;
+
+        record_audit_event(&mut *tx, AuditAction::Create, &actor, None, Some(&label))
+            .await
+            .map_err(|e| LabelRepositoryError::InternalError(e.to_string()))?;
+
+        enqueue_webhook_events(&mut tx, &events)
+            .await
+            .map_err(|e| LabelRepositoryError::InternalError(e.to_string()))?;
+
+        notify_change(&mut *tx, ChangeTargetKind::Label, ChangeAction::Created, id)
+            .await
+            .map_err(|e: sqlx::Error| LabelRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| LabelRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(LabelId::new(id))
     }
 
-    async fn list(&self) -> Result<Vec<Label>, LabelRepositoryError> {
-        let res = sqlx::query_as!(
+    async fn get_by_id(&self, id: LabelId) -> Result<Option<Label>, LabelRepositoryError> {
+        let res: Result<Option<LabelRow>, sqlx::Error> = sqlx::query_as!(
+            LabelRow,
+            r#"SELECT
+            labels.id AS "id",
+            labels.name AS "name",
+            labels.description AS "description",
+            labels.color AS "color",
+            labels.created_at AS "created_at",
+            labels.updated_at AS "updated_at"
+            FROM labels WHERE id = $1"#,
+            id.value()
+        )
+        .fetch_optional(&*self.db)
+        .await;
+
+        res.map(|opt_l| opt_l.map(Label::from))
+            .map_err(|e: sqlx::Error| LabelRepositoryError::InternalError(e.to_string()))
+    }
+
+    async fn list(
+        &self,
+        page: PageRequest<LabelSortKey>,
+    ) -> Result<Page<Label>, LabelRepositoryError> {
+        let (cursor_key, cursor_id, limit) = page_request_into_params(&page);
+
+        let res: Vec<Label> = sqlx::query_as!(
             LabelRow,
             r#"SELECT
             labels.id AS "id",
@@ -94,10 +195,58 @@ impl LabelRepository for PgLabelRepository {
             labels.description AS "description",
             labels.color AS "color",
             labels.created_at AS "created_at",
-            labels.updated_at AS "updated_at",
-            labels.deleted_at AS "deleted_at?"
+            labels.updated_at AS "updated_at"
             FROM labels
-            ORDER BY labels.updated_at DESC"#
+            CROSS JOIN LATERAL (
+                SELECT (CASE $1::text
+                    WHEN 'created_at' THEN to_char(labels.created_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'updated_at' THEN to_char(labels.updated_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'name' THEN labels.name
+                END) COLLATE "C" AS sort_key
+            ) k
+            WHERE ($3::text IS NULL OR CASE
+                WHEN $2::bool THEN (k.sort_key, labels.id) < ($3 COLLATE "C", $4::uuid)
+                ELSE (k.sort_key, labels.id) > ($3 COLLATE "C", $4::uuid)
+            END)
+            ORDER BY
+                CASE WHEN $2 THEN k.sort_key END DESC,
+                CASE WHEN $2 THEN labels.id END DESC,
+                CASE WHEN NOT $2 THEN k.sort_key END ASC,
+                CASE WHEN NOT $2 THEN labels.id END ASC
+            LIMIT $5"#,
+            label_sort_key_column(page.sort()),
+            page.is_desc(),
+            cursor_key,
+            cursor_id,
+            limit,
+        )
+        .fetch(&*self.db)
+        .map(|row| Ok(Label::from(row?)))
+        .try_collect()
+        .await
+        .map_err(|e: sqlx::Error| LabelRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(into_page(res, &page, |label| {
+            page.cursor_for(label_sort_key(label, &page), label.id().clone().value())
+        }))
+    }
+
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Label>, LabelRepositoryError> {
+        let res = sqlx::query_as!(
+            LabelRow,
+            r#"SELECT
+            labels.id AS "id",
+            labels.name AS "name",
+            labels.description AS "description",
+            labels.color AS "color",
+            labels.created_at AS "created_at",
+            labels.updated_at AS "updated_at"
+            FROM labels, websearch_to_tsquery('simple', $1) q
+            WHERE labels.search_vector @@ q
+            ORDER BY ts_rank(labels.search_vector, q) DESC, labels.updated_at DESC
+            LIMIT $2"#,
+            query.text(),
+            i64::from(*query.limit()),
         )
         .fetch(&*self.db)
         .map(|row| Ok(Label::from(row?)))
@@ -1,9 +1,14 @@
+pub mod audit;
+pub mod change_stream;
+pub mod comment;
 pub mod doit;
 pub mod label;
 pub mod shared;
 pub mod todo;
 pub mod user;
 pub mod user_auth;
+pub mod webhook;
+pub mod webhook_sender;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
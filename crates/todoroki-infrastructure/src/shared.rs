@@ -1,8 +1,12 @@
+pub mod pagination;
 pub mod postgresql;
 
 use crate::{
-    doit::PgDoitRepository, label::PgLabelRepository, shared::postgresql::Postgresql,
-    todo::PgTodoRepository, user::PgUserRepository, user_auth::FirebaseUserAuthRepository,
+    audit::PgAuditRepository, change_stream::PgChangeStreamRepository,
+    comment::PgCommentRepository, doit::PgDoitRepository, label::PgLabelRepository,
+    shared::postgresql::Postgresql, todo::PgTodoRepository, user::PgUserRepository,
+    user_auth::FirebaseUserAuthRepository, webhook::PgWebhookRepository,
+    webhook_sender::HttpWebhookSenderRepository,
 };
 use postgresql::PostgresqlError;
 use todoroki_domain::repositories::Repositories;
@@ -21,6 +25,11 @@ pub struct DefaultRepositories {
     label_repository: PgLabelRepository,
     user_repository: PgUserRepository,
     user_auth_repository: FirebaseUserAuthRepository,
+    comment_repository: PgCommentRepository,
+    audit_repository: PgAuditRepository,
+    webhook_repository: PgWebhookRepository,
+    webhook_sender_repository: HttpWebhookSenderRepository,
+    change_stream_repository: PgChangeStreamRepository,
 }
 
 impl DefaultRepositories {
@@ -31,8 +40,13 @@ impl DefaultRepositories {
             todo_repository: PgTodoRepository::new(postgresql.clone()),
             doit_repository: PgDoitRepository::new(postgresql.clone()),
             label_repository: PgLabelRepository::new(postgresql.clone()),
-            user_repository: PgUserRepository::new(postgresql),
+            user_repository: PgUserRepository::new(postgresql.clone()),
             user_auth_repository: FirebaseUserAuthRepository::new(jwk_url.to_string()),
+            comment_repository: PgCommentRepository::new(postgresql.clone()),
+            audit_repository: PgAuditRepository::new(postgresql.clone()),
+            change_stream_repository: PgChangeStreamRepository::new(postgresql.clone()).await?,
+            webhook_repository: PgWebhookRepository::new(postgresql),
+            webhook_sender_repository: HttpWebhookSenderRepository::new(),
         })
     }
 }
@@ -43,6 +57,11 @@ impl Repositories for DefaultRepositories {
     type LabelRepositoryImpl = PgLabelRepository;
     type UserRepositoryImpl = PgUserRepository;
     type UserAuthRepositoryImpl = FirebaseUserAuthRepository;
+    type CommentRepositoryImpl = PgCommentRepository;
+    type AuditRepositoryImpl = PgAuditRepository;
+    type WebhookRepositoryImpl = PgWebhookRepository;
+    type WebhookSenderRepositoryImpl = HttpWebhookSenderRepository;
+    type ChangeStreamRepositoryImpl = PgChangeStreamRepository;
 
     fn todo_repository(&self) -> &Self::TodoRepositoryImpl {
         &self.todo_repository
@@ -63,4 +82,24 @@ impl Repositories for DefaultRepositories {
     fn label_repository(&self) -> &Self::LabelRepositoryImpl {
         &self.label_repository
     }
+
+    fn comment_repository(&self) -> &Self::CommentRepositoryImpl {
+        &self.comment_repository
+    }
+
+    fn audit_repository(&self) -> &Self::AuditRepositoryImpl {
+        &self.audit_repository
+    }
+
+    fn webhook_repository(&self) -> &Self::WebhookRepositoryImpl {
+        &self.webhook_repository
+    }
+
+    fn webhook_sender_repository(&self) -> &Self::WebhookSenderRepositoryImpl {
+        &self.webhook_sender_repository
+    }
+
+    fn change_stream_repository(&self) -> &Self::ChangeStreamRepositoryImpl {
+        &self.change_stream_repository
+    }
 }
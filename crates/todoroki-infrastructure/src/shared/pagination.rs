@@ -0,0 +1,44 @@
+use todoroki_domain::{
+    repositories::pagination::{Page, PageCursor, PageRequest},
+    value_objects::datetime::DateTime,
+};
+use uuid::Uuid;
+
+// NOTE: 各一覧クエリの sort_key と同じ表現にすること
+// 日時: to_char(... AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+pub(crate) fn datetime_sort_key(value: &DateTime) -> String {
+    value.clone().value().format("%Y%m%d%H%M%S%6f").to_string()
+}
+
+// 値がないものは並び順に関わらず末尾にする
+pub(crate) fn missing_sort_key(is_desc: bool) -> String {
+    if is_desc { "" } else { "~" }.to_string()
+}
+
+// (cursor_key, cursor_id, limit) の形にして SQL に渡す
+// 次のページの有無を知るために1件多く取得する
+pub(crate) fn page_request_into_params<K>(
+    page: &PageRequest<K>,
+) -> (Option<String>, Option<Uuid>, Option<i64>) {
+    (
+        page.cursor().as_ref().map(|c| c.key().clone()),
+        page.cursor().as_ref().map(|c| *c.id()),
+        page.limit().map(|limit| i64::from(limit) + 1),
+    )
+}
+
+pub(crate) fn into_page<T, K>(
+    mut items: Vec<T>,
+    page: &PageRequest<K>,
+    cursor_of: impl Fn(&T) -> PageCursor,
+) -> Page<T> {
+    match page.limit() {
+        Some(limit) if items.len() > *limit as usize => {
+            items.truncate(*limit as usize);
+            let next_cursor = items.last().map(cursor_of);
+
+            Page::new(items, next_cursor)
+        }
+        _ => Page::new(items, None),
+    }
+}
@@ -1,17 +1,36 @@
-use crate::{label::LabelRow, shared::postgresql::Postgresql};
+use crate::{
+    audit::record_audit_event,
+    change_stream::{notify_change, ChangeTargetKind},
+    label::{label_condition_into_arrays, LabelRow},
+    shared::{
+        pagination::{datetime_sort_key, into_page, missing_sort_key, page_request_into_params},
+        postgresql::Postgresql,
+    },
+    webhook::enqueue_webhook_events,
+};
 
 use futures_util::TryStreamExt;
-use sqlx::{prelude::FromRow, types::chrono};
+use sqlx::{postgres::PgExecutor, prelude::FromRow, types::chrono};
 use todoroki_domain::{
     entities::{
+        audit::{AuditAction, AuditActor},
+        change::ChangeAction,
         label::Label,
         todo::{
-            Todo, TodoDescription, TodoId, TodoName, TodoPublishment, TodoSchedule,
-            TodoUpdateCommand, TodoUpdateProgressStatus,
+            Todo, TodoBlocker, TodoDescription, TodoId, TodoName, TodoOccurrence,
+            TodoOccurrenceCompletion, TodoProgress, TodoPublishment, TodoSchedule, TodoStatus,
+            TodoStatusTransition, TodoUpdateCommand, TodoVersion,
         },
+        user::UserId,
+        webhook::{WebhookEvent, WebhookEventsFn},
     },
-    repositories::todo::{TodoRepository, TodoRepositoryError},
-    value_objects::{self, datetime::DateTime},
+    repositories::{
+        pagination::{Page, PageRequest},
+        search::SearchQuery,
+        todo::{TodoFilter, TodoRepository, TodoRepositoryError, TodoSortKey},
+    },
+    services::{dependency, hierarchy},
+    value_objects::{self, datetime::DateTime, error::ErrorCode},
 };
 use uuid::Uuid;
 
@@ -22,6 +41,10 @@ struct TodoRow {
     description: String,
     is_public: bool,
     alternative_name: Option<String>,
+    parent_id: Option<Uuid>,
+    status: TodoStatusColumn,
+    children_total: i64,
+    children_completed: i64,
     started_at: Option<chrono::DateTime<chrono::Utc>>,
     scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
     ended_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -29,22 +52,41 @@ struct TodoRow {
     updated_at: chrono::DateTime<chrono::Utc>,
     deleted_at: Option<chrono::DateTime<chrono::Utc>>,
     labels: serde_json::Value,
+    blockers: serde_json::Value,
     schedules: serde_json::Value,
+    occurrence_completions: serde_json::Value,
 }
 
 struct TodoIdColumn {
     id: Uuid,
 }
 
-#[derive(FromRow, serde::Deserialize)]
-struct TodoScheduleRow {
+#[derive(FromRow)]
+struct TodoVersionRow {
     todo_id: Uuid,
+    version: i32,
+    name: String,
+    description: String,
+    is_public: bool,
+    alternative_name: Option<String>,
+    deadlined_at: Option<chrono::DateTime<chrono::Utc>>,
+    labels: serde_json::Value,
+    schedules: serde_json::Value,
+    created_by: Option<Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(FromRow, serde::Deserialize, serde::Serialize)]
+struct TodoScheduleRow {
     interval: TodoScheduleInterval,
     starts_at: chrono::DateTime<chrono::Utc>,
     ends_at: chrono::DateTime<chrono::Utc>,
+    rrule: Option<String>,
+    exdates: Vec<chrono::DateTime<chrono::Utc>>,
+    timezone: String,
 }
 
-#[derive(sqlx::Type, serde::Deserialize)]
+#[derive(sqlx::Type, serde::Deserialize, serde::Serialize)]
 #[sqlx(type_name = "todo_schedule_interval", rename_all = "snake_case")]
 pub enum TodoScheduleInterval {
     #[serde(rename = "once")]
@@ -55,12 +97,96 @@ pub enum TodoScheduleInterval {
     Weekly,
     #[serde(rename = "monthly")]
     Monthly,
+    #[serde(rename = "recurrence")]
+    Recurrence,
+}
+
+#[derive(sqlx::Type, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "todo_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatusColumn {
+    NotStarted,
+    OnProgress,
+    Paused,
+    Completed,
+    Cancelled,
+}
+
+impl From<TodoStatus> for TodoStatusColumn {
+    fn from(value: TodoStatus) -> Self {
+        match value {
+            TodoStatus::NotStarted => Self::NotStarted,
+            TodoStatus::OnProgress => Self::OnProgress,
+            TodoStatus::Paused => Self::Paused,
+            TodoStatus::Completed => Self::Completed,
+            TodoStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<TodoStatusColumn> for TodoStatus {
+    fn from(value: TodoStatusColumn) -> Self {
+        match value {
+            TodoStatusColumn::NotStarted => Self::NotStarted,
+            TodoStatusColumn::OnProgress => Self::OnProgress,
+            TodoStatusColumn::Paused => Self::Paused,
+            TodoStatusColumn::Completed => Self::Completed,
+            TodoStatusColumn::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TodoBlockerRow {
+    id: Uuid,
+    status: TodoStatusColumn,
+}
+
+impl From<TodoBlockerRow> for TodoBlocker {
+    fn from(value: TodoBlockerRow) -> Self {
+        Self::new(TodoId::new(value.id), TodoStatus::from(value.status))
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TodoOccurrenceCompletionRow {
+    occurrence_start: chrono::DateTime<chrono::Utc>,
+    occurrence_end: chrono::DateTime<chrono::Utc>,
+    completed_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<TodoOccurrenceCompletionRow> for TodoOccurrenceCompletion {
+    fn from(value: TodoOccurrenceCompletionRow) -> Self {
+        Self::new(
+            DateTime::new(value.occurrence_start),
+            DateTime::new(value.occurrence_end),
+            DateTime::new(value.completed_at),
+        )
+    }
+}
+
+struct TodoStatusTransitionRow {
+    from_status: TodoStatusColumn,
+    to_status: TodoStatusColumn,
+    transitioned_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<TodoStatusTransitionRow> for TodoStatusTransition {
+    fn from(value: TodoStatusTransitionRow) -> Self {
+        Self::new(
+            TodoStatus::from(value.from_status),
+            TodoStatus::from(value.to_status),
+            DateTime::new(value.transitioned_at),
+        )
+    }
 }
 
 impl TryFrom<TodoScheduleRow> for TodoSchedule {
     type Error = value_objects::error::ErrorCode;
 
     fn try_from(value: TodoScheduleRow) -> Result<Self, Self::Error> {
+        let timezone = value_objects::datetime::Timezone::try_from(value.timezone)?;
+
         match value.interval {
             TodoScheduleInterval::Once => Ok(Self::Once(
                 DateTime::new(value.starts_at),
@@ -69,14 +195,26 @@ impl TryFrom<TodoScheduleRow> for TodoSchedule {
             TodoScheduleInterval::Daily => Ok(Self::Daily(
                 value_objects::datetime::Time::try_from(DateTime::new(value.starts_at))?,
                 value_objects::datetime::Time::try_from(DateTime::new(value.ends_at))?,
+                timezone,
             )),
             TodoScheduleInterval::Weekly => Ok(Self::Weekly(
                 value_objects::datetime::WeeklyTime::try_from(DateTime::new(value.starts_at))?,
                 value_objects::datetime::WeeklyTime::try_from(DateTime::new(value.ends_at))?,
+                timezone,
             )),
             TodoScheduleInterval::Monthly => Ok(Self::Monthly(
                 value_objects::datetime::MonthlyTime::try_from(DateTime::new(value.starts_at))?,
                 value_objects::datetime::MonthlyTime::try_from(DateTime::new(value.ends_at))?,
+                timezone,
+            )),
+            TodoScheduleInterval::Recurrence => Ok(Self::Recurrence(
+                DateTime::new(value.starts_at),
+                DateTime::new(value.ends_at),
+                value_objects::recurrence::RecurrenceRule::try_new(
+                    &value.rrule.unwrap_or_default(),
+                    value.exdates.into_iter().map(DateTime::new).collect(),
+                )?,
+                timezone,
             )),
         }
     }
@@ -91,21 +229,37 @@ fn interval_and_timestamps_from(
 ) {
     match value {
         TodoSchedule::Once(s, e) => (TodoScheduleInterval::Once, s.value(), e.value()),
-        TodoSchedule::Daily(s, e) => (
+        TodoSchedule::Daily(s, e, _) => (
             TodoScheduleInterval::Daily,
             DateTime::from(s).value(),
             DateTime::from(e).value(),
         ),
-        TodoSchedule::Weekly(s, e) => (
+        TodoSchedule::Weekly(s, e, _) => (
             TodoScheduleInterval::Weekly,
             DateTime::from(s).value(),
             DateTime::from(e).value(),
         ),
-        TodoSchedule::Monthly(s, e) => (
+        TodoSchedule::Monthly(s, e, _) => (
             TodoScheduleInterval::Monthly,
             DateTime::from(s).value(),
             DateTime::from(e).value(),
         ),
+        TodoSchedule::Recurrence(s, e, _, _) => {
+            (TodoScheduleInterval::Recurrence, s.value(), e.value())
+        }
+    }
+}
+
+// (rrule, exdates)
+fn recurrence_columns_from(
+    value: &TodoSchedule,
+) -> (Option<String>, Vec<chrono::DateTime<chrono::Utc>>) {
+    match value {
+        TodoSchedule::Recurrence(_, _, rule, _) => (
+            Some(rule.to_string()),
+            rule.exdates().iter().map(|t| t.clone().value()).collect(),
+        ),
+        _ => (None, Vec::new()),
     }
 }
 
@@ -119,6 +273,13 @@ impl TryFrom<TodoRow> for Todo {
             .map(Label::from)
             .collect();
 
+        let blockers: Vec<TodoBlocker> =
+            serde_json::from_value::<Vec<TodoBlockerRow>>(value.blockers)
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?
+                .into_iter()
+                .map(TodoBlocker::from)
+                .collect();
+
         let schedules: Vec<TodoSchedule> =
             serde_json::from_value::<Vec<TodoScheduleRow>>(value.schedules)
                 .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?
@@ -127,6 +288,15 @@ impl TryFrom<TodoRow> for Todo {
                 .collect::<Result<_, _>>()
                 .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
 
+        let occurrence_completions: Vec<TodoOccurrenceCompletion> =
+            serde_json::from_value::<Vec<TodoOccurrenceCompletionRow>>(
+                value.occurrence_completions,
+            )
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?
+            .into_iter()
+            .map(TodoOccurrenceCompletion::from)
+            .collect();
+
         Ok(Self::new(
             TodoId::new(value.id),
             TodoName::new(value.name),
@@ -136,8 +306,13 @@ impl TryFrom<TodoRow> for Todo {
             } else {
                 TodoPublishment::Private(value.alternative_name)
             },
+            value.parent_id.map(TodoId::new),
             labels,
+            blockers,
             schedules,
+            TodoStatus::from(value.status),
+            TodoProgress::new(value.children_completed as u32, value.children_total as u32),
+            occurrence_completions,
             value.started_at.map(DateTime::new),
             value.scheduled_at.map(DateTime::new),
             value.ended_at.map(DateTime::new),
@@ -148,6 +323,32 @@ impl TryFrom<TodoRow> for Todo {
     }
 }
 
+fn todo_sort_key_column(sort: &TodoSortKey) -> &'static str {
+    match sort {
+        TodoSortKey::CreatedAt => "created_at",
+        TodoSortKey::UpdatedAt => "updated_at",
+        TodoSortKey::Deadline => "deadline",
+        TodoSortKey::Name => "name",
+    }
+}
+
+// NOTE: list の sort_key と同じ値を返すこと
+fn todo_sort_key(todo: &Todo, page: &PageRequest<TodoSortKey>) -> String {
+    match page.sort() {
+        TodoSortKey::CreatedAt => datetime_sort_key(todo.created_at()),
+        TodoSortKey::UpdatedAt => datetime_sort_key(todo.updated_at()),
+        TodoSortKey::Deadline => todo
+            .deadlined_at()
+            .as_ref()
+            .map(datetime_sort_key)
+            .unwrap_or_else(|| missing_sort_key(page.is_desc())),
+        TodoSortKey::Name => match todo.is_public() {
+            TodoPublishment::Public => todo.name().clone().value(),
+            TodoPublishment::Private(alt) => alt.clone().unwrap_or_default(),
+        },
+    }
+}
+
 pub struct PgTodoRepository {
     db: Postgresql,
 }
@@ -159,124 +360,256 @@ impl PgTodoRepository {
 }
 
 impl TodoRepository for PgTodoRepository {
-    async fn create(&self, todo: Todo) -> Result<TodoId, TodoRepositoryError> {
+    async fn create(
+        &self,
+        todo: Todo,
+        actor: AuditActor,
+        events: Vec<WebhookEvent>,
+    ) -> Result<TodoId, TodoRepositoryError> {
         let mut tx = self
             .db
             .begin()
             .await
             .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
 
-        let res = sqlx::query_as!(
-            TodoIdColumn,
-            r#"
-           INSERT INTO todos (id, name, description, is_public, alternative_name, started_at, scheduled_at, ended_at)
-           VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-           RETURNING id
-            "#,
-            todo.id().clone().value(),
-            todo.name().clone().value(),
-            todo.description().clone().value(),
-            matches!(todo.is_public(), TodoPublishment::Public),
-            match todo.is_public() {
-                TodoPublishment::Public => None,
-                TodoPublishment::Private(alt) => alt.clone()
-            },
-            todo.started_at().clone().map(|t| t.value()),
-            todo.deadlined_at().clone().map(|t| t.value()),
-            todo.ended_at().clone().map(|t| t.value()),
-        )
-        .fetch_one(&mut *tx)
-        .await.map_err(|e| {
-            TodoRepositoryError::InternalError(e.to_string())
-        })?;
-
-        for label in todo.labels() {
-            sqlx::query!(
-                r#"INSERT INTO todo_labels (todo_id, label_id) VALUES ($1, $2)"#,
-                res.id,
-                label.id().clone().value(),
-            )
-            .execute(&mut *tx)
+        let id = insert_todo(&mut tx, &todo)
             .await
             .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
-        }
 
-        for schedule in todo.schedules() {
-            let (interval, starts_at, ends_at) = interval_and_timestamps_from(schedule.clone());
+        record_audit_event(&mut *tx, AuditAction::Create, &actor, None, Some(&todo))
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
 
-            sqlx::query!(
-                r#"INSERT INTO todo_schedules (todo_id, interval, starts_at, ends_at) VALUES ($1, $2, $3, $4)"#,
-                res.id,
-                interval as TodoScheduleInterval,
-                starts_at,
-                ends_at
-            )
-            .execute(&mut *tx)
+        enqueue_webhook_events(&mut tx, &events)
             .await
             .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
-        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Todo,
+            ChangeAction::Created,
+            id.clone().value(),
+        )
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
 
         tx.commit()
             .await
             .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
 
-        Ok(TodoId::new(res.id))
+        Ok(id)
     }
 
-    async fn update(&self, cmd: TodoUpdateCommand) -> Result<(), TodoRepositoryError> {
+    async fn update(
+        &self,
+        cmd: TodoUpdateCommand,
+        completes_descendants: bool,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError> {
         if cmd.is_nothing_todo() {
             return Ok(());
         }
 
-        sqlx::query!(
-            r#"
-            UPDATE todos
-            SET
-                name = COALESCE($2, name),
-                description = COALESCE($3, description),
-                is_public = COALESCE($4, is_public),
-                alternative_name = COALESCE($5, alternative_name),
-                started_at = COALESCE(started_at, $6),
-                ended_at = COALESCE(ended_at, $7),
-                scheduled_at = COALESCE($8, scheduled_at)
-            WHERE id = $1
-            "#,
-            cmd.id().clone().value(),
-            cmd.name().clone().map(|n| n.value()),
-            cmd.description().clone().map(|d| d.value()),
-            cmd.is_public()
-                .clone()
-                .map(|is_public| matches!(is_public, TodoPublishment::Public)),
-            cmd.is_public()
-                .clone()
-                .map(|is_public| match is_public {
-                    TodoPublishment::Public => None,
-                    TodoPublishment::Private(alt) => alt.clone(),
-                })
-                .flatten(),
-            if matches!(cmd.status(), Some(TodoUpdateProgressStatus::OnProgress)) {
-                Some(chrono::Utc::now())
-            } else {
-                None
-            },
-            if matches!(cmd.status(), Some(TodoUpdateProgressStatus::Completed)) {
-                Some(chrono::Utc::now())
-            } else {
-                None
-            },
-            cmd.deadlined_at()
-                .clone()
-                .map(|opt_t| opt_t.map(|t| t.value()))
-                .flatten(),
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        update_todo(&mut tx, &cmd, &actor, events).await?;
+
+        // NOTE: 中止したものなど、完了にできない子孫はそのままにする
+        if completes_descendants {
+            for id in list_descendant_ids(&mut tx, cmd.id().clone()).await? {
+                let descendant = lock_todo(&mut tx, id.clone()).await?;
+
+                if descendant.is_some_and(|t| t.status().can_transition_to(&TodoStatus::Completed))
+                {
+                    let cmd = TodoUpdateCommand::new(
+                        id,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        Some(TodoStatus::Completed),
+                        Vec::new(),
+                        Vec::new(),
+                        Vec::new(),
+                        Vec::new(),
+                        None,
+                    );
+
+                    update_todo(&mut tx, &cmd, &actor, events).await?;
+                }
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_by_id(&self, id: TodoId) -> Result<Option<Todo>, TodoRepositoryError> {
+        fetch_todo(&*self.db, id).await
+    }
+
+    async fn list(
+        &self,
+        filter: TodoFilter,
+        page: PageRequest<TodoSortKey>,
+    ) -> Result<Page<Todo>, TodoRepositoryError> {
+        let (any_labels, all_labels) = label_condition_into_arrays(filter.labels().clone());
+        let (cursor_key, cursor_id, limit) = page_request_into_params(&page);
+
+        let res = sqlx::query_as!(
+            TodoRow,
+            r#"SELECT
+            todos.id AS "id",
+            todos.name AS "name",
+            todos.description AS "description",
+            todos.is_public AS "is_public",
+            todos.alternative_name AS "alternative_name",
+            todos.parent_id AS "parent_id?",
+            todos.status AS "status: TodoStatusColumn",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status <> 'cancelled'
+            ) AS "children_total!",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status = 'completed'
+            ) AS "children_completed!",
+            todos.started_at AS "started_at?",
+            todos.scheduled_at AS "scheduled_at?",
+            todos.ended_at AS "ended_at?",
+            todos.created_at AS "created_at",
+            todos.updated_at AS "updated_at",
+            todos.deleted_at AS "deleted_at?",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', l.id,
+                        'name', l.name,
+                        'description', l.description,
+                        'color', l.color,
+                        'created_at', l.created_at,
+                        'updated_at', l.updated_at
+                    )
+                )
+                FROM todo_labels tl
+                JOIN labels l ON tl.label_id = l.id
+                WHERE tl.todo_id = todos.id
+            ), '[]') AS "labels!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', b.id,
+                        'status', b.status
+                    )
+                )
+                FROM todo_dependencies td
+                JOIN todos b ON td.blocker_id = b.id
+                WHERE td.todo_id = todos.id AND b.deleted_at IS NULL
+            ), '[]') AS "blockers!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'interval', ts.interval,
+                        'starts_at', ts.starts_at,
+                        'ends_at', ts.ends_at,
+                        'rrule', ts.rrule,
+                        'exdates', ts.exdates,
+                        'timezone', ts.timezone
+                    )
+                )
+                FROM todo_schedules ts
+                WHERE ts.todo_id = todos.id
+            ), '[]') AS "schedules!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'occurrence_start', toc.occurrence_start,
+                        'occurrence_end', toc.occurrence_end,
+                        'completed_at', toc.completed_at
+                    )
+                )
+                FROM todo_occurrence_completions toc
+                WHERE toc.todo_id = todos.id AND toc.occurrence_end >= $12
+            ), '[]') AS "occurrence_completions!"
+            FROM todos
+            CROSS JOIN LATERAL (
+                SELECT (CASE $7::text
+                    WHEN 'created_at' THEN to_char(todos.created_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'updated_at' THEN to_char(todos.updated_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS')
+                    WHEN 'deadline' THEN COALESCE(
+                        to_char(todos.scheduled_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS'),
+                        CASE WHEN $8::bool THEN '' ELSE '~' END
+                    )
+                    WHEN 'name' THEN CASE WHEN todos.is_public THEN todos.name ELSE COALESCE(todos.alternative_name, '') END
+                END) COLLATE "C" AS sort_key
+            ) k
+            WHERE todos.deleted_at IS NULL
+            AND ($1::uuid[] IS NULL OR EXISTS (
+                SELECT 1 FROM todo_labels ftl
+                WHERE ftl.todo_id = todos.id AND ftl.label_id = ANY($1)
+            ))
+            AND ($2::uuid[] IS NULL OR NOT EXISTS (
+                SELECT 1 FROM unnest($2::uuid[]) AS required(label_id)
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM todo_labels ftl
+                    WHERE ftl.todo_id = todos.id AND ftl.label_id = required.label_id
+                )
+            ))
+            AND ($3::todo_status IS NULL OR todos.status = $3)
+            AND ($4::bool IS NULL OR todos.is_public = $4)
+            AND ($5::timestamptz IS NULL OR todos.scheduled_at >= $5)
+            AND ($6::timestamptz IS NULL OR todos.scheduled_at < $6)
+            AND ($13::uuid IS NULL OR todos.parent_id = $13)
+            AND ($9::text IS NULL OR CASE
+                WHEN $8 THEN (k.sort_key, todos.id) < ($9 COLLATE "C", $10::uuid)
+                ELSE (k.sort_key, todos.id) > ($9 COLLATE "C", $10::uuid)
+            END)
+            ORDER BY
+                CASE WHEN $8 THEN k.sort_key END DESC,
+                CASE WHEN $8 THEN todos.id END DESC,
+                CASE WHEN NOT $8 THEN k.sort_key END ASC,
+                CASE WHEN NOT $8 THEN todos.id END ASC
+            LIMIT $11"#,
+            any_labels.as_deref(),
+            all_labels.as_deref(),
+            filter.status().map(TodoStatusColumn::from) as Option<TodoStatusColumn>,
+            *filter.is_public(),
+            filter.deadlined_from().clone().map(|t| t.value()),
+            filter.deadlined_to().clone().map(|t| t.value()),
+            todo_sort_key_column(page.sort()),
+            page.is_desc(),
+            cursor_key,
+            cursor_id,
+            limit,
+            Todo::recent_occurrences_since(&DateTime::now()).value(),
+            filter.parent_id().clone().map(|id| id.value()),
         )
-        .execute(&*self.db)
+        .fetch(&*self.db)
+        .and_then(|row| async move {
+            Todo::try_from(row).map_err(|e| sqlx::Error::ColumnDecode {
+                index: "labels".into(),
+                source: Box::new(e),
+            })
+        })
+        .try_collect::<Vec<Todo>>()
         .await
         .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
 
-        Ok(())
+        Ok(into_page(res, &page, |todo| {
+            page.cursor_for(todo_sort_key(todo, &page), todo.id().clone().value())
+        }))
     }
 
-    async fn list(&self) -> Result<Vec<Todo>, TodoRepositoryError> {
+    async fn list_deleted(&self) -> Result<Vec<Todo>, TodoRepositoryError> {
         let res = sqlx::query_as!(
             TodoRow,
             r#"SELECT
@@ -285,43 +618,198 @@ impl TodoRepository for PgTodoRepository {
             todos.description AS "description",
             todos.is_public AS "is_public",
             todos.alternative_name AS "alternative_name",
+            todos.parent_id AS "parent_id?",
+            todos.status AS "status: TodoStatusColumn",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status <> 'cancelled'
+            ) AS "children_total!",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status = 'completed'
+            ) AS "children_completed!",
             todos.started_at AS "started_at?",
             todos.scheduled_at AS "scheduled_at?",
             todos.ended_at AS "ended_at?",
             todos.created_at AS "created_at",
             todos.updated_at AS "updated_at",
             todos.deleted_at AS "deleted_at?",
-            COALESCE(
-                json_agg(
+            COALESCE((
+                SELECT json_agg(
                     json_build_object(
                         'id', l.id,
                         'name', l.name,
                         'description', l.description,
                         'color', l.color,
                         'created_at', l.created_at,
-                        'updated_at', l.updated_at,
-                        'deleted_at', l.deleted_at
+                        'updated_at', l.updated_at
                     )
-                ) FILTER (WHERE l.id IS NOT NULL),
-                '[]'
-            ) AS "labels",
-            COALESCE(
-                json_agg(
+                )
+                FROM todo_labels tl
+                JOIN labels l ON tl.label_id = l.id
+                WHERE tl.todo_id = todos.id
+            ), '[]') AS "labels!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', b.id,
+                        'status', b.status
+                    )
+                )
+                FROM todo_dependencies td
+                JOIN todos b ON td.blocker_id = b.id
+                WHERE td.todo_id = todos.id AND b.deleted_at IS NULL
+            ), '[]') AS "blockers!",
+            COALESCE((
+                SELECT json_agg(
                     json_build_object(
-                        'todo_id', ts.todo_id,
                         'interval', ts.interval,
                         'starts_at', ts.starts_at,
-                        'ends_at', ts.ends_at
+                        'ends_at', ts.ends_at,
+                        'rrule', ts.rrule,
+                        'exdates', ts.exdates,
+                        'timezone', ts.timezone
+                    )
+                )
+                FROM todo_schedules ts
+                WHERE ts.todo_id = todos.id
+            ), '[]') AS "schedules!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'occurrence_start', toc.occurrence_start,
+                        'occurrence_end', toc.occurrence_end,
+                        'completed_at', toc.completed_at
                     )
-                ) FILTER (WHERE ts.todo_id IS NOT NULL),
-                '[]'
-            ) AS "schedules"
+                )
+                FROM todo_occurrence_completions toc
+                WHERE toc.todo_id = todos.id AND toc.occurrence_end >= $1
+            ), '[]') AS "occurrence_completions!"
             FROM todos
-            LEFT JOIN todo_labels tl ON todos.id = tl.todo_id
-            LEFT JOIN labels l ON tl.label_id = l.id
-            LEFT JOIN todo_schedules ts ON todos.id = ts.todo_id
-            GROUP BY todos.id
-            ORDER BY todos.updated_at DESC"#
+            WHERE todos.deleted_at IS NOT NULL
+            ORDER BY todos.deleted_at DESC"#,
+            Todo::recent_occurrences_since(&DateTime::now()).value(),
+        )
+        .fetch(&*self.db)
+        .and_then(|row| async move {
+            Todo::try_from(row).map_err(|e| sqlx::Error::ColumnDecode {
+                index: "labels".into(),
+                source: Box::new(e),
+            })
+        })
+        .try_collect::<Vec<Todo>>()
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(res)
+    }
+
+    async fn list_ancestor_ids(&self, id: TodoId) -> Result<Vec<TodoId>, TodoRepositoryError> {
+        fetch_ancestor_ids(&*self.db, id).await
+    }
+
+    async fn list_transitive_blocker_ids(
+        &self,
+        id: TodoId,
+    ) -> Result<Vec<TodoId>, TodoRepositoryError> {
+        fetch_transitive_blocker_ids(&*self.db, id).await
+    }
+
+    async fn search(&self, query: SearchQuery) -> Result<Vec<Todo>, TodoRepositoryError> {
+        let res = sqlx::query_as!(
+            TodoRow,
+            r#"SELECT
+            todos.id AS "id",
+            todos.name AS "name",
+            todos.description AS "description",
+            todos.is_public AS "is_public",
+            todos.alternative_name AS "alternative_name",
+            todos.parent_id AS "parent_id?",
+            todos.status AS "status: TodoStatusColumn",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status <> 'cancelled'
+            ) AS "children_total!",
+            (
+                SELECT COUNT(*) FROM todos c
+                WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status = 'completed'
+            ) AS "children_completed!",
+            todos.started_at AS "started_at?",
+            todos.scheduled_at AS "scheduled_at?",
+            todos.ended_at AS "ended_at?",
+            todos.created_at AS "created_at",
+            todos.updated_at AS "updated_at",
+            todos.deleted_at AS "deleted_at?",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', l.id,
+                        'name', l.name,
+                        'description', l.description,
+                        'color', l.color,
+                        'created_at', l.created_at,
+                        'updated_at', l.updated_at
+                    )
+                )
+                FROM todo_labels tl
+                JOIN labels l ON tl.label_id = l.id
+                WHERE tl.todo_id = todos.id
+            ), '[]') AS "labels!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', b.id,
+                        'status', b.status
+                    )
+                )
+                FROM todo_dependencies td
+                JOIN todos b ON td.blocker_id = b.id
+                WHERE td.todo_id = todos.id AND b.deleted_at IS NULL
+            ), '[]') AS "blockers!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'interval', ts.interval,
+                        'starts_at', ts.starts_at,
+                        'ends_at', ts.ends_at,
+                        'rrule', ts.rrule,
+                        'exdates', ts.exdates,
+                        'timezone', ts.timezone
+                    )
+                )
+                FROM todo_schedules ts
+                WHERE ts.todo_id = todos.id
+            ), '[]') AS "schedules!",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'occurrence_start', toc.occurrence_start,
+                        'occurrence_end', toc.occurrence_end,
+                        'completed_at', toc.completed_at
+                    )
+                )
+                FROM todo_occurrence_completions toc
+                WHERE toc.todo_id = todos.id AND toc.occurrence_end >= $4
+            ), '[]') AS "occurrence_completions!"
+            FROM todos, websearch_to_tsquery('simple', $1) q
+            WHERE todos.deleted_at IS NULL
+            AND (
+                ($2 AND todos.search_vector @@ q)
+                OR (NOT $2 AND todos.public_search_vector @@ q)
+                OR todos.id IN (
+                    SELECT tl.todo_id FROM todo_labels tl
+                    JOIN labels l ON tl.label_id = l.id
+                    WHERE to_tsvector('simple', l.name) @@ q
+                )
+            )
+            ORDER BY
+                ts_rank(CASE WHEN $2 THEN todos.search_vector ELSE todos.public_search_vector END, q) DESC,
+                todos.updated_at DESC
+            LIMIT $3"#,
+            query.text(),
+            *query.include_private(),
+            i64::from(*query.limit()),
+            Todo::recent_occurrences_since(&DateTime::now()).value(),
         )
         .fetch(&*self.db)
         .and_then(|row| async move {
@@ -337,7 +825,1058 @@ impl TodoRepository for PgTodoRepository {
         Ok(res)
     }
 
-    async fn delete_by_id(&self, _id: TodoId) -> Result<(), TodoRepositoryError> {
-        todo!()
+    async fn delete_by_id(
+        &self,
+        id: TodoId,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_todo(&mut tx, id.clone()).await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE todos
+            SET deleted_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            id.clone().value()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_todo(&mut *tx, id.clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Delete,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Todo,
+            ChangeAction::Deleted,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_status_transitions(
+        &self,
+        id: TodoId,
+    ) -> Result<Vec<TodoStatusTransition>, TodoRepositoryError> {
+        let res = sqlx::query_as!(
+            TodoStatusTransitionRow,
+            r#"SELECT
+            from_status AS "from_status: TodoStatusColumn",
+            to_status AS "to_status: TodoStatusColumn",
+            transitioned_at
+            FROM todo_status_transitions
+            WHERE todo_id = $1
+            ORDER BY transitioned_at ASC, id ASC"#,
+            id.value()
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(res.into_iter().map(TodoStatusTransition::from).collect())
+    }
+
+    async fn restore_by_id(
+        &self,
+        id: TodoId,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_todo(&mut tx, id.clone()).await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE todos
+            SET deleted_at = NULL
+            WHERE id = $1
+            "#,
+            id.clone().value()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_todo(&mut *tx, id.clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Update,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Todo,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn complete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence: TodoOccurrence,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_todo(&mut tx, id.clone()).await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO todo_occurrence_completions (todo_id, occurrence_start, occurrence_end)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            id.clone().value(),
+            occurrence.starts_at().clone().value(),
+            occurrence.ends_at().clone().value(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_todo(&mut *tx, id.clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Update,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Todo,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn uncomplete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence_start: DateTime,
+        actor: AuditActor,
+        events: WebhookEventsFn<Todo>,
+    ) -> Result<(), TodoRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let before = lock_todo(&mut tx, id.clone()).await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM todo_occurrence_completions
+            WHERE todo_id = $1 AND occurrence_start = $2
+            "#,
+            id.clone().value(),
+            occurrence_start.value(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_todo(&mut *tx, id.clone()).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Update,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        if let (Some(before), Some(after)) = (&before, &after) {
+            enqueue_webhook_events(&mut tx, &events(before, after))
+                .await
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+
+        notify_change(
+            &mut *tx,
+            ChangeTargetKind::Todo,
+            ChangeAction::Updated,
+            id.value(),
+        )
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_versions(&self, id: TodoId) -> Result<Vec<TodoVersion>, TodoRepositoryError> {
+        let res = sqlx::query_as!(
+            TodoVersionRow,
+            r#"SELECT
+            v.todo_id AS "todo_id",
+            v.version AS "version",
+            v.name AS "name",
+            v.description AS "description",
+            v.is_public AS "is_public",
+            v.alternative_name AS "alternative_name?",
+            v.deadlined_at AS "deadlined_at?",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', l.id,
+                        'name', l.name,
+                        'description', l.description,
+                        'color', l.color,
+                        'created_at', l.created_at,
+                        'updated_at', l.updated_at
+                    )
+                )
+                FROM labels l
+                WHERE l.id = ANY(v.label_ids)
+            ), '[]') AS "labels!",
+            v.schedules AS "schedules",
+            v.created_by AS "created_by?",
+            v.created_at AS "created_at"
+            FROM todo_versions v
+            WHERE v.todo_id = $1
+            ORDER BY v.version ASC"#,
+            id.value(),
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        res.into_iter().map(TodoVersion::try_from).collect()
+    }
+
+    async fn get_version(
+        &self,
+        id: TodoId,
+        version: u32,
+    ) -> Result<Option<TodoVersion>, TodoRepositoryError> {
+        let res = sqlx::query_as!(
+            TodoVersionRow,
+            r#"SELECT
+            v.todo_id AS "todo_id",
+            v.version AS "version",
+            v.name AS "name",
+            v.description AS "description",
+            v.is_public AS "is_public",
+            v.alternative_name AS "alternative_name?",
+            v.deadlined_at AS "deadlined_at?",
+            COALESCE((
+                SELECT json_agg(
+                    json_build_object(
+                        'id', l.id,
+                        'name', l.name,
+                        'description', l.description,
+                        'color', l.color,
+                        'created_at', l.created_at,
+                        'updated_at', l.updated_at
+                    )
+                )
+                FROM labels l
+                WHERE l.id = ANY(v.label_ids)
+            ), '[]') AS "labels!",
+            v.schedules AS "schedules",
+            v.created_by AS "created_by?",
+            v.created_at AS "created_at"
+            FROM todo_versions v
+            WHERE v.todo_id = $1 AND v.version = $2"#,
+            id.value(),
+            version as i32,
+        )
+        .fetch_optional(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        res.map(TodoVersion::try_from).transpose()
+    }
+}
+
+impl TryFrom<TodoVersionRow> for TodoVersion {
+    type Error = TodoRepositoryError;
+
+    fn try_from(value: TodoVersionRow) -> Result<Self, Self::Error> {
+        let labels: Vec<Label> = serde_json::from_value::<Vec<LabelRow>>(value.labels)
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?
+            .into_iter()
+            .map(Label::from)
+            .collect();
+
+        let schedules: Vec<TodoSchedule> =
+            serde_json::from_value::<Vec<TodoScheduleRow>>(value.schedules)
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?
+                .into_iter()
+                .map(TodoSchedule::try_from)
+                .collect::<Result<_, _>>()
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(Self::new(
+            TodoId::new(value.todo_id),
+            value.version as u32,
+            TodoName::new(value.name),
+            TodoDescription::new(value.description),
+            if value.is_public {
+                TodoPublishment::Public
+            } else {
+                TodoPublishment::Private(value.alternative_name)
+            },
+            value.deadlined_at.map(DateTime::new),
+            labels,
+            schedules,
+            value.created_by.map(UserId::new),
+            DateTime::new(value.created_at),
+        ))
+    }
+}
+
+// todo_schedules の行と同じ形の JSON にする
+fn schedules_into_json(schedules: &[TodoSchedule]) -> Result<serde_json::Value, serde_json::Error> {
+    let rows: Vec<TodoScheduleRow> = schedules
+        .iter()
+        .map(|schedule| {
+            let (interval, starts_at, ends_at) = interval_and_timestamps_from(schedule.clone());
+            let (rrule, exdates) = recurrence_columns_from(schedule);
+
+            TodoScheduleRow {
+                interval,
+                starts_at,
+                ends_at,
+                rrule,
+                exdates,
+                timezone: schedule.timezone().name().to_string(),
+            }
+        })
+        .collect();
+
+    serde_json::to_value(rows)
+}
+
+// todo とそのラベル・スケジュールを挿入する。トランザクションは呼び出し側で管理する
+pub(crate) async fn insert_todo(
+    conn: &mut sqlx::PgConnection,
+    todo: &Todo,
+) -> Result<TodoId, sqlx::Error> {
+    let res = sqlx::query_as!(
+        TodoIdColumn,
+        r#"
+       INSERT INTO todos (id, name, description, is_public, alternative_name, status, started_at, scheduled_at, ended_at, parent_id)
+       VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+       RETURNING id
+        "#,
+        todo.id().clone().value(),
+        todo.name().clone().value(),
+        todo.description().clone().value(),
+        matches!(todo.is_public(), TodoPublishment::Public),
+        match todo.is_public() {
+            TodoPublishment::Public => None,
+            TodoPublishment::Private(alt) => alt.clone()
+        },
+        TodoStatusColumn::from(*todo.status()) as TodoStatusColumn,
+        todo.started_at().clone().map(|t| t.value()),
+        todo.deadlined_at().clone().map(|t| t.value()),
+        todo.ended_at().clone().map(|t| t.value()),
+        todo.parent_id().clone().map(|id| id.value()),
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    for label in todo.labels() {
+        sqlx::query!(
+            r#"INSERT INTO todo_labels (todo_id, label_id) VALUES ($1, $2)"#,
+            res.id,
+            label.id().clone().value(),
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    for schedule in todo.schedules() {
+        let (interval, starts_at, ends_at) = interval_and_timestamps_from(schedule.clone());
+        let (rrule, exdates) = recurrence_columns_from(schedule);
+
+        sqlx::query!(
+            r#"INSERT INTO todo_schedules (todo_id, interval, starts_at, ends_at, rrule, exdates, timezone) VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            res.id,
+            interval as TodoScheduleInterval,
+            starts_at,
+            ends_at,
+            rrule,
+            &exdates,
+            schedule.timezone().name()
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(TodoId::new(res.id))
+}
+
+// 論理削除されたものも含めて取得する
+async fn fetch_todo<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: TodoId,
+) -> Result<Option<Todo>, TodoRepositoryError> {
+    let res: Result<Option<TodoRow>, sqlx::Error> = sqlx::query_as!(
+        TodoRow,
+        r#"SELECT
+        todos.id AS "id",
+        todos.name AS "name",
+        todos.description AS "description",
+        todos.is_public AS "is_public",
+        todos.alternative_name AS "alternative_name",
+        todos.parent_id AS "parent_id?",
+        todos.status AS "status: TodoStatusColumn",
+        (
+            SELECT COUNT(*) FROM todos c
+            WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status <> 'cancelled'
+        ) AS "children_total!",
+        (
+            SELECT COUNT(*) FROM todos c
+            WHERE c.parent_id = todos.id AND c.deleted_at IS NULL AND c.status = 'completed'
+        ) AS "children_completed!",
+        todos.started_at AS "started_at?",
+        todos.scheduled_at AS "scheduled_at?",
+        todos.ended_at AS "ended_at?",
+        todos.created_at AS "created_at",
+        todos.updated_at AS "updated_at",
+        todos.deleted_at AS "deleted_at?",
+        COALESCE((
+            SELECT json_agg(
+                json_build_object(
+                    'id', l.id,
+                    'name', l.name,
+                    'description', l.description,
+                    'color', l.color,
+                    'created_at', l.created_at,
+                    'updated_at', l.updated_at
+                )
+            )
+            FROM todo_labels tl
+            JOIN labels l ON tl.label_id = l.id
+            WHERE tl.todo_id = todos.id
+        ), '[]') AS "labels!",
+        COALESCE((
+            SELECT json_agg(
+                json_build_object(
+                    'id', b.id,
+                    'status', b.status
+                )
+            )
+            FROM todo_dependencies td
+            JOIN todos b ON td.blocker_id = b.id
+            WHERE td.todo_id = todos.id AND b.deleted_at IS NULL
+        ), '[]') AS "blockers!",
+        COALESCE((
+            SELECT json_agg(
+                json_build_object(
+                    'interval', ts.interval,
+                    'starts_at', ts.starts_at,
+                    'ends_at', ts.ends_at,
+                    'rrule', ts.rrule,
+                    'exdates', ts.exdates,
+                    'timezone', ts.timezone
+                )
+            )
+            FROM todo_schedules ts
+            WHERE ts.todo_id = todos.id
+        ), '[]') AS "schedules!",
+        COALESCE((
+            SELECT json_agg(
+                json_build_object(
+                    'occurrence_start', toc.occurrence_start,
+                    'occurrence_end', toc.occurrence_end,
+                    'completed_at', toc.completed_at
+                )
+            )
+            FROM todo_occurrence_completions toc
+            WHERE toc.todo_id = todos.id AND toc.occurrence_end >= $2
+        ), '[]') AS "occurrence_completions!"
+        FROM todos
+        WHERE todos.id = $1"#,
+        id.value(),
+        Todo::recent_occurrences_since(&DateTime::now()).value(),
+    )
+    .fetch_optional(executor)
+    .await;
+
+    res.map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?
+        .map(Todo::try_from)
+        .transpose()
+}
+
+// 1つの todo を更新し、遷移や版、監査ログ、webhook の配送、変更の通知を記録する
+async fn update_todo(
+    conn: &mut sqlx::PgConnection,
+    cmd: &TodoUpdateCommand,
+    actor: &AuditActor,
+    events: WebhookEventsFn<Todo>,
+) -> Result<(), TodoRepositoryError> {
+    let status = cmd.status().map(TodoStatusColumn::from);
+
+    // NOTE: 同時に親や blocker を変えて循環しないよう、それぞれの変更は1つずつ行う
+    if let Some(Some(_)) = cmd.parent_id() {
+        lock_structure(&mut *conn, TODO_HIERARCHY_LOCK_KEY).await?;
+    }
+    if !cmd.add_blockers().is_empty() {
+        lock_structure(&mut *conn, TODO_DEPENDENCY_LOCK_KEY).await?;
+    }
+
+    // 遷移の検証と記録や監査ログのために、変更前の内容を取得して更新が終わるまでロックしておく
+    let before = lock_todo(&mut *conn, cmd.id().clone()).await?;
+    let prev_status = before.as_ref().map(|t| *t.status());
+
+    // NOTE: use-case で検証した後に他のリクエストで status が変えられていることがある
+    if let (Some(from), Some(to)) = (prev_status, cmd.status()) {
+        if !from.can_transition_to(to) {
+            return Err(TodoRepositoryError::StatusTransitionInvalid(from, *to));
+        }
+    }
+
+    if let Some(Some(parent_id)) = cmd.parent_id() {
+        let parent = fetch_todo(&mut *conn, parent_id.clone())
+            .await?
+            .ok_or(TodoRepositoryError::TodoNotFound(parent_id.clone()))?;
+        let ancestors = fetch_ancestor_ids(&mut *conn, parent_id.clone()).await?;
+
+        hierarchy::validate_parent(cmd.id(), &parent, &ancestors).map_err(validation_error)?;
+    }
+
+    for blocker_id in cmd.add_blockers() {
+        let blocker = fetch_todo(&mut *conn, blocker_id.clone())
+            .await?
+            .ok_or(TodoRepositoryError::TodoNotFound(blocker_id.clone()))?;
+        let blocker_blockers = fetch_transitive_blocker_ids(&mut *conn, blocker_id.clone()).await?;
+
+        dependency::validate_blocker(cmd.id(), &blocker, &blocker_blockers)
+            .map_err(validation_error)?;
+    }
+
+    // NOTE: 履歴を残し始める前に作られた todo では、更新前の内容を最初の版として残す
+    if let Some(before) = &before {
+        let has_version = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM todo_versions WHERE todo_id = $1) AS "exists!""#,
+            before.id().clone().value(),
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        if !has_version {
+            insert_version(&mut *conn, before, None)
+                .await
+                .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+    }
+
+    // NOTE: ラベルやスケジュールのみの変更でも updated_at を更新するため、常に UPDATE する
+    sqlx::query!(
+        r#"
+        UPDATE todos
+        SET
+            name = COALESCE($2, name),
+            description = COALESCE($3, description),
+            is_public = COALESCE($4, is_public),
+            alternative_name = COALESCE($5, alternative_name),
+            status = COALESCE($6, status),
+            started_at = CASE
+                WHEN $6 = 'on_progress' THEN COALESCE(started_at, CURRENT_TIMESTAMP)
+                ELSE started_at
+            END,
+            ended_at = CASE
+                WHEN $6 IS NULL THEN ended_at
+                WHEN $6 IN ('completed', 'cancelled') THEN CURRENT_TIMESTAMP
+                ELSE NULL
+            END,
+            scheduled_at = CASE WHEN $7 THEN $8 ELSE scheduled_at END,
+            parent_id = CASE WHEN $9 THEN $10 ELSE parent_id END
+        WHERE id = $1
+        "#,
+        cmd.id().clone().value(),
+        cmd.name().clone().map(|n| n.value()),
+        cmd.description().clone().map(|d| d.value()),
+        cmd.is_public()
+            .clone()
+            .map(|is_public| matches!(is_public, TodoPublishment::Public)),
+        cmd.is_public()
+            .clone()
+            .map(|is_public| match is_public {
+                TodoPublishment::Public => None,
+                TodoPublishment::Private(alt) => alt.clone(),
+            })
+            .flatten(),
+        status as Option<TodoStatusColumn>,
+        cmd.deadlined_at().is_some(),
+        cmd.deadlined_at().clone().flatten().map(|t| t.value())
+            as Option<chrono::DateTime<chrono::Utc>>,
+        cmd.parent_id().is_some(),
+        cmd.parent_id().clone().flatten().map(|id| id.value()) as Option<Uuid>,
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+    if let (Some(from), Some(to)) = (prev_status.map(TodoStatusColumn::from), status) {
+        if from != to {
+            sqlx::query!(
+                r#"INSERT INTO todo_status_transitions (todo_id, from_status, to_status) VALUES ($1, $2, $3)"#,
+                cmd.id().clone().value(),
+                from as TodoStatusColumn,
+                to as TodoStatusColumn,
+            )
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+    }
+
+    if !cmd.remove_labels().is_empty() {
+        sqlx::query!(
+            r#"DELETE FROM todo_labels WHERE todo_id = $1 AND label_id = ANY($2)"#,
+            cmd.id().clone().value(),
+            &cmd.remove_labels()
+                .iter()
+                .map(|id| id.clone().value())
+                .collect::<Vec<Uuid>>(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    for label_id in cmd.add_labels() {
+        sqlx::query!(
+            r#"INSERT INTO todo_labels (todo_id, label_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"#,
+            cmd.id().clone().value(),
+            label_id.clone().value(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    if !cmd.remove_blockers().is_empty() {
+        sqlx::query!(
+            r#"DELETE FROM todo_dependencies WHERE todo_id = $1 AND blocker_id = ANY($2)"#,
+            cmd.id().clone().value(),
+            &cmd.remove_blockers()
+                .iter()
+                .map(|id| id.clone().value())
+                .collect::<Vec<Uuid>>(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    for blocker_id in cmd.add_blockers() {
+        sqlx::query!(
+            r#"INSERT INTO todo_dependencies (todo_id, blocker_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"#,
+            cmd.id().clone().value(),
+            blocker_id.clone().value(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    if let Some(schedules) = cmd.schedules() {
+        sqlx::query!(
+            r#"DELETE FROM todo_schedules WHERE todo_id = $1"#,
+            cmd.id().clone().value(),
+        )
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+        for schedule in schedules {
+            let (interval, starts_at, ends_at) = interval_and_timestamps_from(schedule.clone());
+            let (rrule, exdates) = recurrence_columns_from(schedule);
+
+            sqlx::query!(
+                r#"INSERT INTO todo_schedules (todo_id, interval, starts_at, ends_at, rrule, exdates, timezone) VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+                cmd.id().clone().value(),
+                interval as TodoScheduleInterval,
+                starts_at,
+                ends_at,
+                rrule,
+                &exdates,
+                schedule.timezone().name()
+            )
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+        }
+    }
+
+    let after = fetch_todo(&mut *conn, cmd.id().clone()).await?;
+    let action = if cmd.status().is_some() {
+        AuditAction::StatusChange
+    } else {
+        AuditAction::Update
+    };
+
+    record_audit_event(&mut *conn, action, actor, before.as_ref(), after.as_ref())
+        .await
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+    if let Some(after) = &after {
+        insert_version(&mut *conn, after, actor.id().clone())
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    if let (Some(before), Some(after)) = (&before, &after) {
+        enqueue_webhook_events(&mut *conn, &events(before, after))
+            .await
+            .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+    }
+
+    notify_change(
+        &mut *conn,
+        ChangeTargetKind::Todo,
+        ChangeAction::Updated,
+        cmd.id().clone().value(),
+    )
+    .await
+    .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))?;
+
+    Ok(())
+}
+
+// 論理削除されていない子孫の id を、親に近い順に取得する
+// NOTE: 循環していても止まるよう UNION で重複を除く
+async fn list_descendant_ids(
+    conn: &mut sqlx::PgConnection,
+    id: TodoId,
+) -> Result<Vec<TodoId>, TodoRepositoryError> {
+    let res = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE descendants(id, depth) AS (
+            SELECT id, 1 FROM todos WHERE parent_id = $1 AND deleted_at IS NULL
+            UNION
+            SELECT todos.id, descendants.depth + 1
+            FROM todos JOIN descendants ON todos.parent_id = descendants.id
+            WHERE todos.deleted_at IS NULL
+        )
+        SELECT id AS "id!" FROM descendants GROUP BY id ORDER BY MIN(depth), id
+        "#,
+        id.value(),
+    )
+    .fetch_all(&mut *conn)
+    .await;
+
+    res.map(|ids| ids.into_iter().map(TodoId::new).collect())
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))
+}
+
+// 親から根までたどった祖先の ID。論理削除されたものも含む
+// NOTE: 循環していても止まるよう UNION で重複を除く
+async fn fetch_ancestor_ids<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: TodoId,
+) -> Result<Vec<TodoId>, TodoRepositoryError> {
+    let res = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE ancestors(id) AS (
+            SELECT parent_id FROM todos WHERE id = $1
+            UNION
+            SELECT todos.parent_id FROM todos JOIN ancestors ON todos.id = ancestors.id
+        )
+        SELECT id AS "id!" FROM ancestors WHERE id IS NOT NULL
+        "#,
+        id.value(),
+    )
+    .fetch_all(executor)
+    .await;
+
+    res.map(|ids| ids.into_iter().map(TodoId::new).collect())
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))
+}
+
+// blocker の blocker… と間接的なものまでたどった ID。論理削除されたものも含む
+async fn fetch_transitive_blocker_ids<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: TodoId,
+) -> Result<Vec<TodoId>, TodoRepositoryError> {
+    let res = sqlx::query_scalar!(
+        r#"
+        WITH RECURSIVE blockers(id) AS (
+            SELECT blocker_id FROM todo_dependencies WHERE todo_id = $1
+            UNION
+            SELECT td.blocker_id FROM todo_dependencies td JOIN blockers ON td.todo_id = blockers.id
+        )
+        SELECT id AS "id!" FROM blockers
+        "#,
+        id.value(),
+    )
+    .fetch_all(executor)
+    .await;
+
+    res.map(|ids| ids.into_iter().map(TodoId::new).collect())
+        .map_err(|e| TodoRepositoryError::InternalError(e.to_string()))
+}
+
+// 親の付け替えや blocker の追加を、トランザクションをまたいで直列にするための advisory lock のキー
+const TODO_HIERARCHY_LOCK_KEY: i64 = 0x746f_646f_0001;
+const TODO_DEPENDENCY_LOCK_KEY: i64 = 0x746f_646f_0002;
+
+// コミットするまで、同じキーでロックする他のトランザクションを待たせる
+// NOTE: 行のロックより先に取り、ロックの順序を揃える
+async fn lock_structure(
+    conn: &mut sqlx::PgConnection,
+    key: i64,
+) -> Result<(), TodoRepositoryError> {
+    sqlx::query_scalar!(
+        r#"SELECT 1 AS "locked!" FROM pg_advisory_xact_lock($1)"#,
+        key
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+    Ok(())
+}
+
+// domain での検証の失敗をリポジトリのエラーにする
+fn validation_error(e: ErrorCode) -> TodoRepositoryError {
+    match e {
+        ErrorCode::TodoHierarchyCycle(id, parent_id) => {
+            TodoRepositoryError::HierarchyCycle(id, parent_id)
+        }
+        ErrorCode::TodoDependencyCycle(id, blocker_id) => {
+            TodoRepositoryError::DependencyCycle(id, blocker_id)
+        }
+        ErrorCode::TodoNotFound(id) => TodoRepositoryError::TodoNotFound(id),
+        e => TodoRepositoryError::InternalError(e.to_string()),
+    }
+}
+
+// 変更前の内容を取得し、コミットするまで他から変更されないようにロックしておく
+async fn lock_todo(
+    conn: &mut sqlx::PgConnection,
+    id: TodoId,
+) -> Result<Option<Todo>, TodoRepositoryError> {
+    sqlx::query!(
+        r#"SELECT id FROM todos WHERE id = $1 FOR UPDATE"#,
+        id.clone().value(),
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e: sqlx::Error| TodoRepositoryError::InternalError(e.to_string()))?;
+
+    fetch_todo(&mut *conn, id).await
+}
+
+// 現在の内容を次の版として記録する
+// NOTE: 同時に記録された場合に版が重複しないよう、todo の行をロックしたトランザクションで実行する
+async fn insert_version<'e, E: PgExecutor<'e>>(
+    executor: E,
+    todo: &Todo,
+    created_by: Option<UserId>,
+) -> Result<(), sqlx::Error> {
+    let schedules =
+        schedules_into_json(todo.schedules()).map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO todo_versions (todo_id, version, name, description, is_public, alternative_name, deadlined_at, label_ids, schedules, created_by)
+        SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, $4, $5, $6, $7, $8, $9
+        FROM todo_versions
+        WHERE todo_id = $1
+        "#,
+        todo.id().clone().value(),
+        todo.name().clone().value(),
+        todo.description().clone().value(),
+        matches!(todo.is_public(), TodoPublishment::Public),
+        match todo.is_public() {
+            TodoPublishment::Public => None,
+            TodoPublishment::Private(alt) => alt.clone(),
+        },
+        todo.deadlined_at().clone().map(|t| t.value()),
+        &todo
+            .labels()
+            .iter()
+            .map(|l| l.id().clone().value())
+            .collect::<Vec<_>>(),
+        schedules,
+        created_by.map(|id| id.value()),
+    )
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todoroki_domain::{
+        entities::todo::{TodoDescription, TodoName},
+        value_objects::permission::Permission,
+    };
+
+    fn actor() -> AuditActor {
+        AuditActor::new(None, Permission::UpdateTodo)
+    }
+
+    fn no_events(_: &Todo, _: &Todo) -> Vec<WebhookEvent> {
+        vec![]
+    }
+
+    // NOTE: PostgreSQL が必要なため、DATABASE_URL を指定して --ignored で実行する
+    #[ignore]
+    #[sqlx::test(migrations = "../../migrations")]
+    async fn completing_occurrence_leaves_history_unchanged(pool: sqlx::PgPool) {
+        let repository = PgTodoRepository::new(Postgresql(pool));
+
+        let todo = Todo::generate(
+            TodoName::new("todo".to_string()),
+            TodoDescription::new(String::new()),
+            TodoPublishment::Public,
+            None,
+            vec![],
+            vec![],
+            None,
+        );
+        let id = repository.create(todo, actor(), vec![]).await.unwrap();
+
+        let cmd = TodoUpdateCommand::new(
+            id.clone(),
+            Some(TodoName::new("renamed".to_string())),
+            None,
+            None,
+            None,
+            None,
+            None,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            None,
+        );
+        repository
+            .update(cmd, false, actor(), no_events)
+            .await
+            .unwrap();
+
+        let versions = repository.list_versions(id.clone()).await.unwrap();
+        assert_eq!(versions.len(), 2);
+
+        let now = DateTime::now();
+        let occurrence = TodoOccurrence::new(now.clone(), now.clone());
+        repository
+            .complete_occurrence(id.clone(), occurrence, actor(), no_events)
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.list_versions(id.clone()).await.unwrap().len(),
+            versions.len()
+        );
+
+        repository
+            .uncomplete_occurrence(id.clone(), now, actor(), no_events)
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.list_versions(id.clone()).await.unwrap().len(),
+            versions.len()
+        );
+
+        repository
+            .delete_by_id(id.clone(), actor(), no_events)
+            .await
+            .unwrap();
+        repository
+            .restore_by_id(id.clone(), actor(), no_events)
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.list_versions(id).await.unwrap().len(),
+            versions.len()
+        );
     }
 }
@@ -1,13 +1,20 @@
-use crate::shared::postgresql::Postgresql;
+use crate::{audit::record_audit_event, shared::postgresql::Postgresql};
 
 use sqlx::{
+    postgres::PgExecutor,
     prelude::{FromRow, Type},
     types::chrono,
 };
 use todoroki_domain::{
-    entities::user::{User, UserEmail, UserId, UserName, UserRole},
+    entities::{
+        audit::{AuditAction, AuditActor},
+        user::{User, UserCalendarToken, UserEmail, UserId, UserName, UserRole},
+    },
     repositories::user::{UserRepository, UserRepositoryError},
-    value_objects::datetime::DateTime,
+    value_objects::{
+        datetime::{DateTime, Timezone},
+        error::ErrorCode,
+    },
 };
 use uuid::Uuid;
 
@@ -17,9 +24,9 @@ struct UserRow {
     role: UserRoleColumn,
     name: String,
     email: String,
+    timezone: String,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: chrono::DateTime<chrono::Utc>,
-    deleted_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Type)]
@@ -51,16 +58,20 @@ struct UserIdColumn {
     id: Uuid,
 }
 
-impl From<UserRow> for User {
-    fn from(value: UserRow) -> Self {
-        Self::new(
+impl TryFrom<UserRow> for User {
+    type Error = UserRepositoryError;
+
+    fn try_from(value: UserRow) -> Result<Self, Self::Error> {
+        Ok(Self::new(
             UserId::new(value.id),
             UserRole::from(value.role),
             UserName::new(value.name),
             UserEmail::new(value.email),
+            Timezone::try_from(value.timezone)
+                .map_err(|e| UserRepositoryError::InternalError(ErrorCode::from(e).to_string()))?,
             DateTime::new(value.created_at),
             DateTime::new(value.updated_at),
-        )
+        ))
     }
 }
 
@@ -75,32 +86,52 @@ impl PgUserRepository {
 }
 
 impl UserRepository for PgUserRepository {
-    async fn create(&self, user: User) -> Result<UserId, UserRepositoryError> {
+    async fn create(&self, user: User, actor: AuditActor) -> Result<UserId, UserRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
         let res = sqlx::query_as!(
             UserIdColumn,
             r#"
-           INSERT INTO users (id, role, name, email)
-           VALUES ($1, $2, $3, $4)
+           INSERT INTO users (id, role, name, email, timezone)
+           VALUES ($1, $2, $3, $4, $5)
            RETURNING id
             "#,
             user.id().clone().value(),
             UserRoleColumn::from(user.role().clone()) as UserRoleColumn,
             user.name().clone().value(),
             user.email().clone().value(),
+            user.timezone().name(),
         )
-        .fetch_one(&*self.db)
+        .fetch_one(&mut *tx)
         .await;
 
-        match res {
-            Ok(id_column) => Ok(UserId::new(id_column.id)),
-            Err(e) => match e.as_database_error() {
-                Some(e) => Err(UserRepositoryError::InternalError(e.message().to_string())),
-                _ => Err(UserRepositoryError::InternalError(e.to_string())),
-            },
-        }
+        let id = res
+            .map_err(|e| match e.as_database_error() {
+                Some(e) => UserRepositoryError::InternalError(e.message().to_string()),
+                _ => UserRepositoryError::InternalError(e.to_string()),
+            })?
+            .id;
+
+        record_audit_event(&mut *tx, AuditAction::Create, &actor, None, Some(&user))
+            .await
+            .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(UserId::new(id))
     }
 
     async fn get_by_id(&self, id: UserId) -> Result<Option<User>, UserRepositoryError> {
+        fetch_user(&*self.db, id).await
+    }
+
+    async fn get_by_email(&self, email: UserEmail) -> Result<Option<User>, UserRepositoryError> {
         let res: Result<Option<UserRow>, sqlx::Error> = sqlx::query_as!(
             UserRow,
             r#"SELECT
@@ -108,20 +139,24 @@ impl UserRepository for PgUserRepository {
             users.role AS "role: UserRoleColumn",
             users.name AS "name",
             users.email AS "email",
+            users.timezone AS "timezone",
             users.created_at AS "created_at",
-            users.updated_at AS "updated_at",
-            users.deleted_at AS "deleted_at?"
-            FROM users WHERE id = $1"#,
-            id.value()
+            users.updated_at AS "updated_at"
+            FROM users WHERE email = $1"#,
+            email.clone().value()
         )
         .fetch_optional(&*self.db)
         .await;
 
-        res.map(|opt_u| opt_u.map(User::from))
-            .map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))
+        res.map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?
+            .map(User::try_from)
+            .transpose()
     }
 
-    async fn get_by_email(&self, email: UserEmail) -> Result<Option<User>, UserRepositoryError> {
+    async fn get_by_calendar_token(
+        &self,
+        token: UserCalendarToken,
+    ) -> Result<Option<User>, UserRepositoryError> {
         let res: Result<Option<UserRow>, sqlx::Error> = sqlx::query_as!(
             UserRow,
             r#"SELECT
@@ -129,16 +164,118 @@ impl UserRepository for PgUserRepository {
             users.role AS "role: UserRoleColumn",
             users.name AS "name",
             users.email AS "email",
+            users.timezone AS "timezone",
             users.created_at AS "created_at",
-            users.updated_at AS "updated_at",
-            users.deleted_at AS "deleted_at?"
-            FROM users WHERE email = $1"#,
-            email.clone().value()
+            users.updated_at AS "updated_at"
+            FROM users
+            WHERE calendar_token_hash = sha256(convert_to($1, 'UTF8'))
+            AND deleted_at IS NULL"#,
+            token.value()
         )
         .fetch_optional(&*self.db)
         .await;
 
-        res.map(|opt_u| opt_u.map(User::from))
-            .map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))
+        res.map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?
+            .map(User::try_from)
+            .transpose()
+    }
+
+    async fn update_calendar_token(
+        &self,
+        id: UserId,
+        token: Option<UserCalendarToken>,
+    ) -> Result<(), UserRepositoryError> {
+        // NOTE: トークンの発行はプロフィールの変更ではないので updated_at は変えない
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET
+                calendar_token_hash = sha256(convert_to($2, 'UTF8')),
+                updated_at = updated_at
+            WHERE id = $1
+            "#,
+            id.value(),
+            token.map(|t| t.value()),
+        )
+        .execute(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn update_timezone(
+        &self,
+        id: UserId,
+        timezone: Timezone,
+        actor: AuditActor,
+    ) -> Result<(), UserRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
+        sqlx::query!(
+            r#"SELECT id FROM users WHERE id = $1 FOR UPDATE"#,
+            id.clone().value(),
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?;
+
+        let before = fetch_user(&mut *tx, id.clone()).await?;
+
+        sqlx::query!(
+            r#"UPDATE users SET timezone = $2 WHERE id = $1"#,
+            id.clone().value(),
+            timezone.name(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?;
+
+        let after = fetch_user(&mut *tx, id).await?;
+
+        record_audit_event(
+            &mut *tx,
+            AuditAction::Update,
+            &actor,
+            before.as_ref(),
+            after.as_ref(),
+        )
+        .await
+        .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| UserRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
     }
 }
+
+async fn fetch_user<'e, E: PgExecutor<'e>>(
+    executor: E,
+    id: UserId,
+) -> Result<Option<User>, UserRepositoryError> {
+    let res: Result<Option<UserRow>, sqlx::Error> = sqlx::query_as!(
+        UserRow,
+        r#"SELECT
+        users.id AS "id",
+        users.role AS "role: UserRoleColumn",
+        users.name AS "name",
+        users.email AS "email",
+        users.timezone AS "timezone",
+        users.created_at AS "created_at",
+        users.updated_at AS "updated_at"
+        FROM users WHERE id = $1"#,
+        id.value()
+    )
+    .fetch_optional(executor)
+    .await;
+
+    res.map_err(|e: sqlx::Error| UserRepositoryError::InternalError(e.to_string()))?
+        .map(User::try_from)
+        .transpose()
+}
@@ -33,17 +33,14 @@ impl UserAuthRepository for FirebaseUserAuthRepository {
             .send()
             .await
             .map_err(|e| {
-                UserAuthRepositoryError::InternalError(format!(
-                    "Failed to get JWKS; error={}",
-                    e.to_string()
-                ))
+                UserAuthRepositoryError::InternalError(format!("Failed to get JWKS; error={}", e))
             })?
             .json()
             .await
             .map_err(|e| {
                 UserAuthRepositoryError::InternalError(format!(
                     "Failed to deserialize JWKS; error={}",
-                    e.to_string()
+                    e
                 ))
             })?;
 
@@ -0,0 +1,539 @@
+use crate::shared::{
+    pagination::{datetime_sort_key, into_page, page_request_into_params},
+    postgresql::Postgresql,
+};
+
+use sqlx::{prelude::FromRow, types::chrono};
+use todoroki_domain::{
+    entities::{
+        user::UserId,
+        webhook::{
+            Webhook, WebhookDelivery, WebhookDeliveryAttempt, WebhookDeliveryId,
+            WebhookDeliveryStatus, WebhookEvent, WebhookEventId, WebhookEventType, WebhookId,
+            WebhookSecret, WebhookUrl,
+        },
+    },
+    repositories::{
+        pagination::{Page, PageRequest},
+        webhook::{WebhookDeliverySortKey, WebhookRepository, WebhookRepositoryError},
+    },
+    value_objects::datetime::DateTime,
+};
+use uuid::Uuid;
+
+#[derive(FromRow)]
+struct WebhookRow {
+    id: Uuid,
+    url: String,
+    secret: String,
+    event_types: Vec<String>,
+    created_by: Uuid,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+struct WebhookIdColumn {
+    id: Uuid,
+}
+
+#[derive(FromRow)]
+struct WebhookDeliveryRow {
+    id: Uuid,
+    webhook_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    data: serde_json::Value,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+    status: WebhookDeliveryStatusColumn,
+    attempts: i32,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+// 取り出した配送と、その送信先
+#[derive(FromRow)]
+struct ClaimedWebhookDeliveryRow {
+    id: Uuid,
+    webhook_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    data: serde_json::Value,
+    occurred_at: chrono::DateTime<chrono::Utc>,
+    status: WebhookDeliveryStatusColumn,
+    attempts: i32,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    webhook_url: String,
+    webhook_secret: String,
+    webhook_event_types: Vec<String>,
+    webhook_created_by: Uuid,
+    webhook_created_at: chrono::DateTime<chrono::Utc>,
+    webhook_updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+struct WebhookDeliveryAttemptRow {
+    delivery_id: Uuid,
+    attempt: i32,
+    status_code: Option<i32>,
+    error: Option<String>,
+    attempted_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "snake_case")]
+pub enum WebhookDeliveryStatusColumn {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+impl From<WebhookDeliveryStatus> for WebhookDeliveryStatusColumn {
+    fn from(value: WebhookDeliveryStatus) -> Self {
+        match value {
+            WebhookDeliveryStatus::Pending => Self::Pending,
+            WebhookDeliveryStatus::Succeeded => Self::Succeeded,
+            WebhookDeliveryStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<WebhookDeliveryStatusColumn> for WebhookDeliveryStatus {
+    fn from(value: WebhookDeliveryStatusColumn) -> Self {
+        match value {
+            WebhookDeliveryStatusColumn::Pending => Self::Pending,
+            WebhookDeliveryStatusColumn::Succeeded => Self::Succeeded,
+            WebhookDeliveryStatusColumn::Failed => Self::Failed,
+        }
+    }
+}
+
+fn event_type_from(value: String) -> Result<WebhookEventType, WebhookRepositoryError> {
+    WebhookEventType::try_from(value)
+        .map_err(|e| WebhookRepositoryError::InternalError(e.to_string()))
+}
+
+impl TryFrom<WebhookRow> for Webhook {
+    type Error = WebhookRepositoryError;
+
+    fn try_from(value: WebhookRow) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            WebhookId::new(value.id),
+            WebhookUrl::new(value.url),
+            WebhookSecret::new(value.secret),
+            value
+                .event_types
+                .into_iter()
+                .map(event_type_from)
+                .collect::<Result<_, _>>()?,
+            UserId::new(value.created_by),
+            DateTime::new(value.created_at),
+            DateTime::new(value.updated_at),
+        ))
+    }
+}
+
+impl TryFrom<WebhookDeliveryRow> for WebhookDelivery {
+    type Error = WebhookRepositoryError;
+
+    fn try_from(value: WebhookDeliveryRow) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            WebhookDeliveryId::new(value.id),
+            WebhookId::new(value.webhook_id),
+            WebhookEvent::new(
+                WebhookEventId::new(value.event_id),
+                event_type_from(value.event_type)?,
+                value.data,
+                DateTime::new(value.occurred_at),
+            ),
+            WebhookDeliveryStatus::from(value.status),
+            value.attempts as u32,
+            DateTime::new(value.next_attempt_at),
+            DateTime::new(value.created_at),
+            DateTime::new(value.updated_at),
+        ))
+    }
+}
+
+impl TryFrom<ClaimedWebhookDeliveryRow> for (Webhook, WebhookDelivery) {
+    type Error = WebhookRepositoryError;
+
+    fn try_from(value: ClaimedWebhookDeliveryRow) -> Result<Self, Self::Error> {
+        let webhook = Webhook::try_from(WebhookRow {
+            id: value.webhook_id,
+            url: value.webhook_url,
+            secret: value.webhook_secret,
+            event_types: value.webhook_event_types,
+            created_by: value.webhook_created_by,
+            created_at: value.webhook_created_at,
+            updated_at: value.webhook_updated_at,
+        })?;
+
+        let delivery = WebhookDelivery::try_from(WebhookDeliveryRow {
+            id: value.id,
+            webhook_id: value.webhook_id,
+            event_id: value.event_id,
+            event_type: value.event_type,
+            data: value.data,
+            occurred_at: value.occurred_at,
+            status: value.status,
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        })?;
+
+        Ok((webhook, delivery))
+    }
+}
+
+impl From<WebhookDeliveryAttemptRow> for WebhookDeliveryAttempt {
+    fn from(value: WebhookDeliveryAttemptRow) -> Self {
+        Self::new(
+            WebhookDeliveryId::new(value.delivery_id),
+            value.attempt as u32,
+            value.status_code.map(|code| code as u16),
+            value.error,
+            DateTime::new(value.attempted_at),
+        )
+    }
+}
+
+// NOTE: list_deliveries の sort_key と同じ値を返すこと
+fn delivery_sort_key(
+    delivery: &WebhookDelivery,
+    page: &PageRequest<WebhookDeliverySortKey>,
+) -> String {
+    match page.sort() {
+        WebhookDeliverySortKey::CreatedAt => datetime_sort_key(delivery.created_at()),
+    }
+}
+
+// イベントを購読しているすべての webhook への配送を積む
+// NOTE: 変更と同じトランザクションで実行すれば、コミットされたときにだけ積まれる
+pub(crate) async fn enqueue_webhook_events(
+    conn: &mut sqlx::PgConnection,
+    events: &[WebhookEvent],
+) -> Result<(), sqlx::Error> {
+    for event in events {
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_deliveries (id, webhook_id, event_id, event_type, data, occurred_at)
+            SELECT gen_random_uuid(), webhooks.id, $1, $2, $3, $4
+            FROM webhooks
+            WHERE $2 = ANY(webhooks.event_types)
+            "#,
+            event.id().clone().value(),
+            event.event_type().to_string(),
+            event.data().clone(),
+            event.occurred_at().clone().value(),
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub struct PgWebhookRepository {
+    db: Postgresql,
+}
+
+impl PgWebhookRepository {
+    pub fn new(db: Postgresql) -> Self {
+        Self { db }
+    }
+}
+
+impl WebhookRepository for PgWebhookRepository {
+    async fn create(&self, webhook: Webhook) -> Result<WebhookId, WebhookRepositoryError> {
+        let res = sqlx::query_as!(
+            WebhookIdColumn,
+            r#"
+            INSERT INTO webhooks (id, url, secret, event_types, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            webhook.id().clone().value(),
+            webhook.url().clone().value(),
+            webhook.secret().clone().value(),
+            &webhook
+                .event_types()
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>(),
+            webhook.created_by().clone().value(),
+        )
+        .fetch_one(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(WebhookId::new(res.id))
+    }
+
+    async fn list(&self) -> Result<Vec<Webhook>, WebhookRepositoryError> {
+        let res = sqlx::query_as!(
+            WebhookRow,
+            r#"SELECT
+            id,
+            url,
+            secret,
+            event_types,
+            created_by,
+            created_at,
+            updated_at
+            FROM webhooks
+            ORDER BY created_at ASC, id ASC"#
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        res.into_iter().map(Webhook::try_from).collect()
+    }
+
+    async fn get_by_id(&self, id: WebhookId) -> Result<Option<Webhook>, WebhookRepositoryError> {
+        let res = sqlx::query_as!(
+            WebhookRow,
+            r#"SELECT
+            id,
+            url,
+            secret,
+            event_types,
+            created_by,
+            created_at,
+            updated_at
+            FROM webhooks
+            WHERE id = $1"#,
+            id.value()
+        )
+        .fetch_optional(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        res.map(Webhook::try_from).transpose()
+    }
+
+    async fn delete_by_id(&self, id: WebhookId) -> Result<(), WebhookRepositoryError> {
+        sqlx::query!(r#"DELETE FROM webhooks WHERE id = $1"#, id.value())
+            .execute(&*self.db)
+            .await
+            .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn claim_due_delivery(
+        &self,
+        lease_until: DateTime,
+    ) -> Result<Option<(Webhook, WebhookDelivery)>, WebhookRepositoryError> {
+        // NOTE: 複数のプロセスから同時に取り出しても重複しないよう、ロック済みの行は飛ばす
+        // 取り出せる条件は WebhookDelivery::is_due と同じ
+        let res = sqlx::query_as!(
+            ClaimedWebhookDeliveryRow,
+            r#"
+            WITH due AS (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= CURRENT_TIMESTAMP
+                ORDER BY next_attempt_at ASC
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            ), claimed AS (
+                UPDATE webhook_deliveries
+                SET next_attempt_at = $1
+                FROM due
+                WHERE webhook_deliveries.id = due.id
+                RETURNING webhook_deliveries.*
+            )
+            SELECT
+            claimed.id AS "id!",
+            claimed.webhook_id AS "webhook_id!",
+            claimed.event_id AS "event_id!",
+            claimed.event_type AS "event_type!",
+            claimed.data AS "data!",
+            claimed.occurred_at AS "occurred_at!",
+            claimed.status AS "status!: WebhookDeliveryStatusColumn",
+            claimed.attempts AS "attempts!",
+            claimed.next_attempt_at AS "next_attempt_at!",
+            claimed.created_at AS "created_at!",
+            claimed.updated_at AS "updated_at!",
+            webhooks.url AS "webhook_url",
+            webhooks.secret AS "webhook_secret",
+            webhooks.event_types AS "webhook_event_types",
+            webhooks.created_by AS "webhook_created_by",
+            webhooks.created_at AS "webhook_created_at",
+            webhooks.updated_at AS "webhook_updated_at"
+            FROM claimed
+            JOIN webhooks ON webhooks.id = claimed.webhook_id
+            "#,
+            lease_until.value(),
+        )
+        .fetch_optional(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        res.map(TryFrom::try_from).transpose()
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery: WebhookDelivery,
+        attempt: WebhookDeliveryAttempt,
+    ) -> Result<(), WebhookRepositoryError> {
+        let mut tx = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_delivery_attempts (delivery_id, attempt, status_code, error, attempted_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            attempt.delivery_id().clone().value(),
+            *attempt.attempt() as i32,
+            attempt.status_code().map(i32::from),
+            attempt.error().clone(),
+            attempt.attempted_at().clone().value(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, next_attempt_at = $4
+            WHERE id = $1
+            "#,
+            delivery.id().clone().value(),
+            WebhookDeliveryStatusColumn::from(*delivery.status()) as WebhookDeliveryStatusColumn,
+            *delivery.attempts() as i32,
+            delivery.next_attempt_at().clone().value(),
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        webhook_id: WebhookId,
+        page: PageRequest<WebhookDeliverySortKey>,
+    ) -> Result<Page<WebhookDelivery>, WebhookRepositoryError> {
+        let (cursor_key, cursor_id, limit) = page_request_into_params(&page);
+
+        let res = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"SELECT
+            d.id,
+            d.webhook_id,
+            d.event_id,
+            d.event_type,
+            d.data,
+            d.occurred_at,
+            d.status AS "status: WebhookDeliveryStatusColumn",
+            d.attempts,
+            d.next_attempt_at,
+            d.created_at,
+            d.updated_at
+            FROM webhook_deliveries d
+            CROSS JOIN LATERAL (
+                SELECT to_char(d.created_at AT TIME ZONE 'UTC', 'YYYYMMDDHH24MISSUS') COLLATE "C" AS sort_key
+            ) k
+            WHERE d.webhook_id = $1
+            AND ($3::text IS NULL OR CASE
+                WHEN $2::bool THEN (k.sort_key, d.id) < ($3 COLLATE "C", $4::uuid)
+                ELSE (k.sort_key, d.id) > ($3 COLLATE "C", $4::uuid)
+            END)
+            ORDER BY
+                CASE WHEN $2 THEN k.sort_key END DESC,
+                CASE WHEN $2 THEN d.id END DESC,
+                CASE WHEN NOT $2 THEN k.sort_key END ASC,
+                CASE WHEN NOT $2 THEN d.id END ASC
+            LIMIT $5"#,
+            webhook_id.value(),
+            page.is_desc(),
+            cursor_key,
+            cursor_id,
+            limit,
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        let deliveries = res
+            .into_iter()
+            .map(WebhookDelivery::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(into_page(deliveries, &page, |delivery| {
+            page.cursor_for(
+                delivery_sort_key(delivery, &page),
+                delivery.id().clone().value(),
+            )
+        }))
+    }
+
+    async fn get_delivery_by_id(
+        &self,
+        id: WebhookDeliveryId,
+    ) -> Result<Option<WebhookDelivery>, WebhookRepositoryError> {
+        let res = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"SELECT
+            id,
+            webhook_id,
+            event_id,
+            event_type,
+            data,
+            occurred_at,
+            status AS "status: WebhookDeliveryStatusColumn",
+            attempts,
+            next_attempt_at,
+            created_at,
+            updated_at
+            FROM webhook_deliveries
+            WHERE id = $1"#,
+            id.value()
+        )
+        .fetch_optional(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        res.map(WebhookDelivery::try_from).transpose()
+    }
+
+    async fn list_attempts(
+        &self,
+        delivery_id: WebhookDeliveryId,
+    ) -> Result<Vec<WebhookDeliveryAttempt>, WebhookRepositoryError> {
+        let res = sqlx::query_as!(
+            WebhookDeliveryAttemptRow,
+            r#"SELECT
+            delivery_id,
+            attempt,
+            status_code AS "status_code?",
+            error AS "error?",
+            attempted_at
+            FROM webhook_delivery_attempts
+            WHERE delivery_id = $1
+            ORDER BY attempt ASC"#,
+            delivery_id.value()
+        )
+        .fetch_all(&*self.db)
+        .await
+        .map_err(|e: sqlx::Error| WebhookRepositoryError::InternalError(e.to_string()))?;
+
+        Ok(res.into_iter().map(WebhookDeliveryAttempt::from).collect())
+    }
+}
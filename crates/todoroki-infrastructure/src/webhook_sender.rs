@@ -0,0 +1,270 @@
+use todoroki_domain::{
+    entities::webhook::{
+        Webhook, WebhookDelivery, WebhookDeliveryAttempt, WEBHOOK_DELIVERY_TIMEOUT_SECS,
+    },
+    repositories::webhook_sender::{WebhookSenderRepository, WebhookSenderRepositoryError},
+    value_objects::datetime::DateTime,
+};
+
+use hmac::{Hmac, Mac};
+use reqwest::ClientBuilder;
+use sha2::Sha256;
+use std::time::Duration;
+
+pub struct HttpWebhookSenderRepository {}
+
+impl HttpWebhookSenderRepository {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for HttpWebhookSenderRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// body の HMAC-SHA256 を16進数にしたもの
+fn sign(secret: &str, body: &[u8]) -> Result<String, WebhookSenderRepositoryError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| WebhookSenderRepositoryError::InternalError(e.to_string()))?;
+    mac.update(body);
+
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+impl WebhookSenderRepository for HttpWebhookSenderRepository {
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+    ) -> Result<WebhookDeliveryAttempt, WebhookSenderRepositoryError> {
+        let body = serde_json::to_vec(&delivery.event().payload())
+            .map_err(|e| WebhookSenderRepositoryError::InternalError(e.to_string()))?;
+        let signature = sign(&webhook.secret().clone().value(), &body)?;
+
+        let attempt = delivery.attempts() + 1;
+        let attempted_at = DateTime::now();
+
+        tracing::info!(
+            "sending webhook...; url={}; delivery_id={}; attempt={attempt}",
+            webhook.url().clone().value(),
+            delivery.id().clone().value()
+        );
+
+        let client = ClientBuilder::new()
+            .timeout(Duration::from_secs(WEBHOOK_DELIVERY_TIMEOUT_SECS))
+            .build()
+            .map_err(|_| {
+                WebhookSenderRepositoryError::InternalError(
+                    "Failed to create http client".to_string(),
+                )
+            })?;
+
+        let res = client
+            .post(webhook.url().clone().value())
+            .header("Content-Type", "application/json")
+            .header(
+                "X-Todoroki-Event",
+                delivery.event().event_type().to_string(),
+            )
+            .header(
+                "X-Todoroki-Delivery",
+                delivery.id().clone().value().to_string(),
+            )
+            .header("X-Todoroki-Signature", format!("sha256={signature}"))
+            .body(body)
+            .send()
+            .await;
+
+        let (status_code, error) = match res {
+            Ok(res) if res.status().is_success() => (Some(res.status().as_u16()), None),
+            Ok(res) => (
+                Some(res.status().as_u16()),
+                Some(format!("unexpected status; status={}", res.status())),
+            ),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        Ok(WebhookDeliveryAttempt::new(
+            delivery.id().clone(),
+            attempt,
+            status_code,
+            error,
+            attempted_at,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use todoroki_domain::entities::{
+        user::UserId,
+        webhook::{
+            WebhookDeliveryId, WebhookDeliveryStatus, WebhookEvent, WebhookEventId,
+            WebhookEventType, WebhookId, WebhookSecret, WebhookUrl,
+        },
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+    use uuid::Uuid;
+
+    struct ReceivedRequest {
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    // 1回だけリクエストを受け取り、status を返す
+    async fn serve_once(status: &'static str) -> (String, JoinHandle<ReceivedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut buf = Vec::new();
+            let header_end = loop {
+                let mut chunk = [0u8; 1024];
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+
+                if let Some(i) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break i + 4;
+                }
+            };
+
+            let headers: HashMap<String, String> = String::from_utf8_lossy(&buf[..header_end])
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(": "))
+                .map(|(k, v)| (k.to_lowercase(), v.to_string()))
+                .collect();
+
+            let content_length: usize = headers["content-length"].parse().unwrap();
+            while buf.len() < header_end + content_length {
+                let mut chunk = [0u8; 1024];
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+            }
+
+            let res =
+                format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+            stream.write_all(res.as_bytes()).await.unwrap();
+
+            ReceivedRequest {
+                headers,
+                body: buf[header_end..].to_vec(),
+            }
+        });
+
+        (url, handle)
+    }
+
+    fn webhook(url: String) -> Webhook {
+        Webhook::new(
+            WebhookId::new(Uuid::new_v4()),
+            WebhookUrl::new(url),
+            WebhookSecret::new("secret".to_string()),
+            vec![WebhookEventType::TodoCreated],
+            UserId::new(Uuid::new_v4()),
+            DateTime::now(),
+            DateTime::now(),
+        )
+    }
+
+    fn delivery(webhook: &Webhook) -> WebhookDelivery {
+        let event = WebhookEvent::new(
+            WebhookEventId::new(Uuid::new_v4()),
+            WebhookEventType::TodoCreated,
+            json!({ "id": Uuid::new_v4(), "name": "todo" }),
+            DateTime::now(),
+        );
+
+        WebhookDelivery::new(
+            WebhookDeliveryId::new(Uuid::new_v4()),
+            webhook.id().clone(),
+            event,
+            WebhookDeliveryStatus::Pending,
+            2,
+            DateTime::now(),
+            DateTime::now(),
+            DateTime::now(),
+        )
+    }
+
+    #[tokio::test]
+    async fn send_signs_body_and_sets_headers() {
+        let (url, handle) = serve_once("200 OK").await;
+        let webhook = webhook(url);
+        let delivery = delivery(&webhook);
+
+        let attempt = HttpWebhookSenderRepository::new()
+            .send(&webhook, &delivery)
+            .await
+            .unwrap();
+        let req = handle.await.unwrap();
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(&req.body);
+        let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        assert_eq!(req.headers["x-todoroki-signature"], expected);
+        assert_eq!(req.headers["x-todoroki-event"], "todo.created");
+        assert_eq!(
+            req.headers["x-todoroki-delivery"],
+            delivery.id().clone().value().to_string()
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&req.body).unwrap(),
+            delivery.event().payload()
+        );
+
+        assert_eq!(*attempt.attempt(), 3);
+        assert_eq!(*attempt.status_code(), Some(200));
+        assert_eq!(*attempt.error(), None);
+        assert!(attempt.is_succeeded());
+    }
+
+    #[tokio::test]
+    async fn send_records_non_2xx_as_failure() {
+        let (url, handle) = serve_once("503 Service Unavailable").await;
+        let webhook = webhook(url);
+        let delivery = delivery(&webhook);
+
+        let attempt = HttpWebhookSenderRepository::new()
+            .send(&webhook, &delivery)
+            .await
+            .unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(*attempt.status_code(), Some(503));
+        assert!(attempt.error().as_ref().unwrap().contains("503"));
+        assert!(!attempt.is_succeeded());
+    }
+
+    #[tokio::test]
+    async fn send_records_connection_error_without_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let webhook = webhook(url);
+        let delivery = delivery(&webhook);
+
+        let attempt = HttpWebhookSenderRepository::new()
+            .send(&webhook, &delivery)
+            .await
+            .unwrap();
+
+        assert_eq!(*attempt.status_code(), None);
+        assert!(attempt.error().is_some());
+        assert!(!attempt.is_succeeded());
+    }
+}
@@ -4,3 +4,4 @@ pub mod middlewares;
 pub mod models;
 pub mod modules;
 pub mod routes;
+pub mod workers;
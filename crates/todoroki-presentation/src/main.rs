@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use todoroki_presentation::{config::Config, modules, routes};
+use todoroki_presentation::{config::Config, modules, routes, workers};
 
 #[tokio::main]
 async fn main() {
@@ -11,7 +11,11 @@ async fn main() {
     let config = Config::load().unwrap();
     let modules = modules::default(config).await.unwrap();
 
-    let app = routes::router(Arc::new(modules));
+    let modules = Arc::new(modules);
+
+    tokio::spawn(workers::run_webhook_delivery(Arc::clone(&modules)));
+
+    let app = routes::router(modules);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
 
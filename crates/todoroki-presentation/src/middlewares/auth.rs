@@ -1,30 +1,20 @@
 use std::sync::Arc;
 
 use axum::{
-    extract::{Request, State},
+    extract::{Query, Request, State},
     middleware::Next,
     response::IntoResponse,
 };
 
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use todoroki_domain::{
-    entities::{client::Client, user_auth::UserAuthToken},
+    entities::{client::Client, user::UserCalendarToken, user_auth::UserAuthToken},
     repositories::Repositories,
     value_objects::error::ErrorCode,
 };
 
 use crate::{context::Context, models::responses::error::ErrorResponse, modules::Modules};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct Claims {
-    pub aud: String,
-    pub iat: u64,
-    pub exp: u64,
-    pub iss: String,
-    pub sub: String,
-    pub email_verified: bool,
-}
-
 pub(crate) async fn jwt_auth(
     State(modules): State<Arc<Modules<impl Repositories>>>,
     mut request: Request,
@@ -62,7 +52,7 @@ pub(crate) async fn jwt_auth(
         .await
         .map_err(ErrorResponse::from)?;
 
-    let ctx = Context::new(client, modules.config().clone().into());
+    let ctx = Context::new(client, modules.config().clone());
     request.extensions_mut().insert(ctx);
 
     Ok(next.run(request).await)
@@ -77,7 +67,7 @@ pub(crate) async fn optional_jwt_auth(
         Some(h) => h,
         None => {
             // Authorization ヘッダがない場合認証なしとみなして直ちに処理を次に移す
-            let ctx = Context::new(Client::Unverified, modules.config().clone().into());
+            let ctx = Context::new(Client::Unverified, modules.config().clone());
             request.extensions_mut().insert(ctx);
 
             return Ok(next.run(request).await);
@@ -106,7 +96,40 @@ pub(crate) async fn optional_jwt_auth(
         .await
         .map_err(ErrorResponse::from)?;
 
-    let ctx = Context::new(client, modules.config().clone().into());
+    let ctx = Context::new(client, modules.config().clone());
+    request.extensions_mut().insert(ctx);
+
+    Ok(next.run(request).await)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct CalendarTokenQuery {
+    token: Option<String>,
+}
+
+// カレンダーアプリは Authorization ヘッダを送れないので、クエリのトークンでも認証する
+pub(crate) async fn calendar_token_auth(
+    State(modules): State<Arc<Modules<impl Repositories>>>,
+    mut request: Request,
+    next: Next,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let token = Query::<CalendarTokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.token);
+
+    let Some(token) = token else {
+        return optional_jwt_auth(State(modules), request, next)
+            .await
+            .map(IntoResponse::into_response);
+    };
+
+    let client = modules
+        .user_use_case()
+        .verify_calendar_token(UserCalendarToken::new(token))
+        .await
+        .map_err(ErrorResponse::from)?;
+
+    let ctx = Context::new(client, modules.config().clone());
     request.extensions_mut().insert(ctx);
 
     Ok(next.run(request).await)
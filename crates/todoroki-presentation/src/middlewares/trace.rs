@@ -0,0 +1,71 @@
+use axum::http::{Request, Uri};
+use tracing::Span;
+
+// クエリで受け取るトークンは、ログに残らないよう伏せる
+const REDACTED_QUERY_KEYS: &[&str] = &["token"];
+
+// DefaultMakeSpan と同じ項目を、トークンを伏せた uri で記録する
+// NOTE: WebSocket はヘッダーを付けられないため /ws?token= で認証する
+// NOTE: カレンダーアプリも同じ理由で /calendar.ics?token= で購読する
+pub(crate) fn make_span<B>(request: &Request<B>) -> Span {
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %redact_uri(request.uri()),
+        version = ?request.version(),
+    )
+}
+
+fn redact_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.to_string();
+    };
+
+    let query = query
+        .split('&')
+        .map(|pair| {
+            let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+
+            if REDACTED_QUERY_KEYS.contains(&key) {
+                format!("{key}=REDACTED")
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}?{}", uri.path(), query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redact(uri: &str) -> String {
+        redact_uri(&uri.parse().unwrap())
+    }
+
+    #[test]
+    fn redacts_websocket_token() {
+        assert_eq!(redact("/ws?token=secret"), "/ws?token=REDACTED");
+    }
+
+    #[test]
+    fn redacts_calendar_token() {
+        assert_eq!(
+            redact("/calendar.ics?token=secret"),
+            "/calendar.ics?token=REDACTED"
+        );
+    }
+
+    #[test]
+    fn keeps_other_query_pairs() {
+        assert_eq!(
+            redact("/todos?limit=10&token=secret&order=asc"),
+            "/todos?limit=10&token=REDACTED&order=asc"
+        );
+        assert_eq!(redact("/todos?limit=10"), "/todos?limit=10");
+        assert_eq!(redact("/health"), "/health");
+    }
+}
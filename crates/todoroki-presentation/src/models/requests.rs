@@ -1,4 +1,11 @@
+pub mod audit;
+pub mod calendar;
+pub mod comment;
 pub mod doit;
 pub mod label;
+pub mod page;
+pub mod search;
 pub mod todo;
 pub mod user;
+pub mod webhook;
+pub mod websocket;
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use todoroki_domain::{
+    entities::{audit::AuditEntity, user::UserId},
+    repositories::{
+        audit::{AuditFilter, AuditSortKey},
+        pagination::PageRequest,
+    },
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::page::{page_request_try_from_query, SortOrderQuery};
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditListQuery {
+    #[param(inline)]
+    pub entity: Option<AuditEntityQuery>,
+    /// 操作したユーザーのID
+    pub actor_id: Option<String>,
+    /// この日時以降の操作 (RFC 3339)
+    pub occurred_from: Option<String>,
+    /// この日時より前の操作 (RFC 3339)
+    pub occurred_to: Option<String>,
+    /// 操作日時の順。既定は desc
+    #[param(inline)]
+    pub order: Option<SortOrderQuery>,
+    /// 1ページの件数 (最大200)。既定は50
+    pub limit: Option<u32>,
+    /// 直前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+pub enum AuditEntityQuery {
+    #[serde(rename = "todo")]
+    Todo,
+    #[serde(rename = "doit")]
+    Doit,
+    #[serde(rename = "label")]
+    Label,
+    #[serde(rename = "user")]
+    User,
+}
+
+impl From<AuditEntityQuery> for AuditEntity {
+    fn from(value: AuditEntityQuery) -> Self {
+        match value {
+            AuditEntityQuery::Todo => Self::Todo,
+            AuditEntityQuery::Doit => Self::Doit,
+            AuditEntityQuery::Label => Self::Label,
+            AuditEntityQuery::User => Self::User,
+        }
+    }
+}
+
+impl AuditListQuery {
+    pub fn try_into_page_request(&self) -> Result<PageRequest<AuditSortKey>, ErrorCode> {
+        page_request_try_from_query(
+            AuditSortKey::OccurredAt,
+            self.order.clone(),
+            self.limit,
+            self.cursor.clone(),
+        )
+    }
+}
+
+impl TryFrom<AuditListQuery> for AuditFilter {
+    type Error = ErrorCode;
+
+    fn try_from(value: AuditListQuery) -> Result<Self, Self::Error> {
+        Ok(AuditFilter::new(
+            value.entity.map(AuditEntity::from),
+            value.actor_id.map(UserId::try_from).transpose()?,
+            value.occurred_from.map(DateTime::try_from).transpose()?,
+            value.occurred_to.map(DateTime::try_from).transpose()?,
+        ))
+    }
+}
@@ -0,0 +1,764 @@
+use chrono::{Datelike, Timelike};
+use serde::Deserialize;
+use todoroki_domain::{
+    entities::{
+        label::LabelName,
+        todo::{TodoDescription, TodoName, TodoPublishment, TodoSchedule, TodoStatus},
+    },
+    value_objects::{
+        datetime::{DateTime, MonthlyTime, Time, Timezone, WeeklyTime},
+        error::ErrorCode,
+        recurrence::RecurrenceRule,
+    },
+};
+use todoroki_use_case::todo::dto::{TodoImportDraft, TodoImportEntryDto};
+use utoipa::IntoParams;
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoImportQuery {
+    /// 既存のラベルにない CATEGORIES をラベルとして作成するか。既定は false
+    pub create_missing_labels: Option<bool>,
+}
+
+// RFC 5545 の content line
+#[derive(Debug, Clone)]
+struct ContentLine {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl ContentLine {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Component {
+    kind: String,
+    properties: Vec<ContentLine>,
+}
+
+impl Component {
+    fn property(&self, name: &str) -> Option<&ContentLine> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn properties<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a ContentLine> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+}
+
+// .ics の VEVENT と VTODO を取り込み用の下書きにする
+// NOTE: TZID も Z もない日時は default_timezone での日時とみなす
+pub fn todo_import_entries_from_ics(
+    text: &str,
+    default_timezone: &Timezone,
+) -> Vec<TodoImportEntryDto> {
+    components(text)
+        .into_iter()
+        .map(|component| {
+            let mut warnings = Vec::new();
+            let draft = draft_from_component(&component, default_timezone, &mut warnings);
+
+            TodoImportEntryDto {
+                uid: component.property("UID").map(|p| unescape_text(&p.value)),
+                summary: component
+                    .property("SUMMARY")
+                    .map(|p| unescape_text(&p.value)),
+                draft,
+                warnings,
+            }
+        })
+        .collect()
+}
+
+fn components(text: &str) -> Vec<Component> {
+    let mut components = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for line in unfold_lines(text) {
+        let Some(line) = parse_content_line(&line) else {
+            continue;
+        };
+
+        match line.name.as_str() {
+            "BEGIN" => stack.push(Component {
+                kind: line.value.to_ascii_uppercase(),
+                properties: Vec::new(),
+            }),
+            "END" => {
+                let Some(component) = stack.pop() else {
+                    continue;
+                };
+
+                // NOTE: VALARM など入れ子になったものは取り込まない
+                if matches!(component.kind.as_str(), "VEVENT" | "VTODO")
+                    && stack.iter().all(|c| c.kind == "VCALENDAR")
+                {
+                    components.push(component);
+                }
+            }
+            _ => {
+                if let Some(component) = stack.last_mut() {
+                    component.properties.push(line);
+                }
+            }
+        }
+    }
+
+    components
+}
+
+fn unfold_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in text.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continued), Some(last)) => last.push_str(continued),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn parse_content_line(line: &str) -> Option<ContentLine> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(k, v)| {
+            (
+                k.trim().to_ascii_uppercase(),
+                v.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    Some(ContentLine {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+
+    unescaped
+}
+
+// エスケープされていないカンマで区切る
+fn split_text_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+
+    for c in value.chars() {
+        match c {
+            ',' if !escaped => items.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+
+        escaped = c == '\\' && !escaped;
+    }
+
+    items.push(current);
+
+    items
+        .iter()
+        .map(|item| unescape_text(item).trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn draft_from_component(
+    component: &Component,
+    default_timezone: &Timezone,
+    warnings: &mut Vec<String>,
+) -> Result<TodoImportDraft, String> {
+    let is_todo = component.kind == "VTODO";
+
+    let name = component
+        .property("SUMMARY")
+        .map(|p| unescape_text(&p.value))
+        .filter(|s| !s.is_empty())
+        .ok_or("missing-summary".to_string())?;
+
+    let description = component
+        .property("DESCRIPTION")
+        .map(|p| unescape_text(&p.value))
+        .unwrap_or_default();
+
+    let is_public = match component.property("CLASS").map(|p| p.value.as_str()) {
+        Some("PRIVATE" | "CONFIDENTIAL") => TodoPublishment::Private(None),
+        _ => TodoPublishment::Public,
+    };
+
+    let categories = component
+        .properties("CATEGORIES")
+        .flat_map(|p| split_text_list(&p.value))
+        .map(LabelName::new)
+        .collect();
+
+    for name in ["RDATE", "EXRULE"] {
+        if component.property(name).is_some() {
+            warnings.push(format!("property-ignored; name={name}"));
+        }
+    }
+
+    let starts_at = component
+        .property("DTSTART")
+        .map(|p| parse_date_time(p, default_timezone, warnings))
+        .transpose()?;
+
+    let ends_at = component
+        .property(if is_todo { "DUE" } else { "DTEND" })
+        .map(|p| parse_date_time(p, default_timezone, warnings))
+        .transpose()?;
+
+    let duration = component
+        .property("DURATION")
+        .map(|p| parse_duration(&p.value))
+        .transpose()?;
+
+    let rrules = component.properties("RRULE").collect::<Vec<_>>();
+
+    let (schedules, deadlined_at) = match (is_todo, rrules.as_slice()) {
+        (_, [_, _, ..]) => return Err("multiple-rrules".to_string()),
+        (_, [rrule]) => {
+            let (starts_at, is_date) = starts_at.ok_or("missing-dtstart".to_string())?;
+            let ends_at = span_end(&starts_at, is_date, ends_at, duration);
+            // NOTE: 繰り返しは DTSTART のタイムゾーンで数える
+            let timezone = component
+                .property("DTSTART")
+                .map(|p| line_timezone(p, default_timezone, &mut Vec::new()))
+                .unwrap_or_default();
+            let exdates = exdates(component, &starts_at, &timezone, default_timezone, warnings)?;
+
+            // NOTE: daily/weekly/monthly で表せないものは RRULE のまま持つ
+            let schedules =
+                match schedules_from_rrule(&rrule.value, &starts_at, &ends_at, &timezone) {
+                    Ok(schedules) if exdates.is_empty() => schedules,
+                    _ => vec![RecurrenceRule::try_new(&rrule.value, exdates)
+                        .map_err(ErrorCode::from)
+                        .and_then(|rule| {
+                            TodoSchedule::try_recurrence(starts_at, ends_at, rule, timezone)
+                        })
+                        .map_err(|e| e.to_string())?],
+                };
+
+            (schedules, None)
+        }
+        // NOTE: 繰り返さない VTODO は期限のみを持つ
+        (true, []) => (Vec::new(), ends_at.map(|(due, _)| due)),
+        (false, []) => {
+            let (starts_at, is_date) = starts_at.ok_or("missing-dtstart".to_string())?;
+            let ends_at = span_end(&starts_at, is_date, ends_at, duration);
+
+            (vec![TodoSchedule::Once(starts_at, ends_at)], None)
+        }
+    };
+
+    let changed_at = component
+        .property(if is_todo {
+            "COMPLETED"
+        } else {
+            "LAST-MODIFIED"
+        })
+        .or(component.property("LAST-MODIFIED"))
+        .and_then(|p| parse_date_time(p, default_timezone, &mut Vec::new()).ok())
+        .map(|(t, _)| t)
+        .unwrap_or_else(DateTime::now);
+
+    let status = match component.property("STATUS").map(|p| p.value.as_str()) {
+        Some("COMPLETED") => Some(TodoStatus::Completed),
+        Some("CANCELLED") => Some(TodoStatus::Cancelled),
+        Some("IN-PROCESS") => Some(TodoStatus::OnProgress),
+        _ => None,
+    }
+    .map(|status| (status, changed_at));
+
+    Ok(TodoImportDraft {
+        name: TodoName::new(name),
+        description: TodoDescription::new(description),
+        is_public,
+        categories,
+        schedules,
+        deadlined_at,
+        status,
+    })
+}
+
+// 日時を解釈するタイムゾーン。日付のみと Z 付きは UTC とする
+fn line_timezone(
+    line: &ContentLine,
+    default_timezone: &Timezone,
+    warnings: &mut Vec<String>,
+) -> Timezone {
+    let value = line.value.trim();
+
+    if line.param("VALUE") == Some("DATE") || value.len() == 8 || value.ends_with('Z') {
+        return Timezone::utc();
+    }
+
+    match line.param("TZID") {
+        Some(tzid) => Timezone::try_from(tzid.to_string()).unwrap_or_else(|_| {
+            // NOTE: VTIMEZONE による独自の定義には未対応
+            warnings.push(format!(
+                "unknown-timezone-as-default; name={}; tzid={tzid}",
+                line.name
+            ));
+            default_timezone.clone()
+        }),
+        None => default_timezone.clone(),
+    }
+}
+
+// (日時, 日付のみか)
+fn parse_date_time(
+    line: &ContentLine,
+    default_timezone: &Timezone,
+    warnings: &mut Vec<String>,
+) -> Result<(DateTime, bool), String> {
+    let invalid = || {
+        format!(
+            "invalid-date-time; name={}; value={}",
+            line.name, line.value
+        )
+    };
+    let value = line.value.trim();
+
+    if line.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = chrono::NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| invalid())?;
+
+        return Ok((
+            DateTime::new(date.and_time(chrono::NaiveTime::MIN).and_utc()),
+            true,
+        ));
+    }
+
+    let naive = chrono::NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map_err(|_| invalid())?;
+
+    Ok((
+        line_timezone(line, default_timezone, warnings).to_utc(naive),
+        false,
+    ))
+}
+
+// 例: P1W, P1D, PT1H30M, P1DT12H
+fn parse_duration(value: &str) -> Result<chrono::Duration, String> {
+    let invalid = || format!("invalid-duration; value={value}");
+
+    let value = value.trim().trim_start_matches('+');
+    let value = value.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut duration = chrono::Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            _ => {
+                let n = std::mem::take(&mut number)
+                    .parse::<i64>()
+                    .map_err(|_| invalid())?;
+
+                duration += match (c, in_time) {
+                    ('W', false) => chrono::Duration::weeks(n),
+                    ('D', false) => chrono::Duration::days(n),
+                    ('H', true) => chrono::Duration::hours(n),
+                    ('M', true) => chrono::Duration::minutes(n),
+                    ('S', true) => chrono::Duration::seconds(n),
+                    _ => return Err(invalid()),
+                };
+            }
+        }
+    }
+
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(duration)
+}
+
+fn span_end(
+    starts_at: &DateTime,
+    is_date: bool,
+    ends_at: Option<(DateTime, bool)>,
+    duration: Option<chrono::Duration>,
+) -> DateTime {
+    match (ends_at, duration) {
+        (Some((ends_at, _)), _) => ends_at,
+        (None, Some(duration)) => DateTime::new(starts_at.clone().value() + duration),
+        // NOTE: 終わりの指定がなければ、日付のみなら1日、日時なら長さ0とする
+        (None, None) if is_date => {
+            DateTime::new(starts_at.clone().value() + chrono::Duration::days(1))
+        }
+        (None, None) => starts_at.clone(),
+    }
+}
+
+// EXDATE をすべて集める。日付のみの場合は繰り返しのタイムゾーンで最初の回と同じ時刻とする
+fn exdates(
+    component: &Component,
+    starts_at: &DateTime,
+    timezone: &Timezone,
+    default_timezone: &Timezone,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<DateTime>, String> {
+    component
+        .properties("EXDATE")
+        .flat_map(|p| {
+            p.value.split(',').map(|value| ContentLine {
+                name: p.name.clone(),
+                params: p.params.clone(),
+                value: value.to_string(),
+            })
+        })
+        .map(|line| {
+            let (exdate, is_date) = parse_date_time(&line, default_timezone, warnings)?;
+
+            if is_date {
+                Ok(timezone.to_utc(
+                    exdate
+                        .value()
+                        .date_naive()
+                        .and_time(timezone.to_local(starts_at).time()),
+                ))
+            } else {
+                Ok(exdate)
+            }
+        })
+        .collect()
+}
+
+fn schedules_from_rrule(
+    rrule: &str,
+    starts_at: &DateTime,
+    ends_at: &DateTime,
+    timezone: &Timezone,
+) -> Result<Vec<TodoSchedule>, String> {
+    let unsupported = || format!("unsupported-rrule; rule={rrule}");
+
+    let mut freq = None;
+    let mut by_day: Vec<chrono::Weekday> = Vec::new();
+    let mut by_month_day: Vec<u8> = Vec::new();
+
+    for part in rrule.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part.split_once('=').ok_or_else(unsupported)?;
+
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => freq = Some(value.to_ascii_uppercase()),
+            "INTERVAL" if value == "1" => {}
+            "WKST" => {}
+            "BYDAY" => {
+                by_day = value
+                    .split(',')
+                    .map(|day| parse_weekday(day).ok_or_else(unsupported))
+                    .collect::<Result<_, _>>()?
+            }
+            "BYMONTHDAY" => {
+                by_month_day = value
+                    .split(',')
+                    .map(|day| {
+                        day.parse::<u8>()
+                            .ok()
+                            .filter(|day| (1..=31).contains(day))
+                            .ok_or_else(unsupported)
+                    })
+                    .collect::<Result<_, _>>()?
+            }
+            _ => return Err(unsupported()),
+        }
+    }
+
+    // NOTE: 時刻や曜日、日付はタイムゾーンでの値を使う
+    let start = timezone.to_local(starts_at);
+    let duration = ends_at.clone().value() - starts_at.clone().value();
+    let end = start + duration;
+
+    if duration <= chrono::Duration::zero() {
+        return Err("recurrence-without-duration".to_string());
+    }
+
+    let too_long = || format!("duration-too-long; rule={rrule}");
+    let invalid_time = |_| format!("invalid-time; rule={rrule}");
+
+    match freq.as_deref() {
+        Some("DAILY") if by_day.is_empty() && by_month_day.is_empty() => {
+            if chrono::Duration::days(1) < duration {
+                return Err(too_long());
+            }
+
+            Ok(vec![TodoSchedule::Daily(
+                Time::try_new(start.hour(), start.minute(), start.second())
+                    .map_err(invalid_time)?,
+                Time::try_new(end.hour(), end.minute(), end.second()).map_err(invalid_time)?,
+                timezone.clone(),
+            )])
+        }
+        Some("WEEKLY") if by_month_day.is_empty() => {
+            if chrono::Duration::weeks(1) < duration {
+                return Err(too_long());
+            }
+
+            let days = if by_day.is_empty() {
+                vec![start.weekday()]
+            } else {
+                by_day
+            };
+
+            days.into_iter()
+                .map(|day| {
+                    let offset = day.num_days_from_monday() as i64
+                        - start.weekday().num_days_from_monday() as i64;
+                    let start = start + chrono::Duration::days(offset);
+                    let end = start + duration;
+
+                    Ok(TodoSchedule::Weekly(
+                        WeeklyTime::try_new(day, start.hour(), start.minute(), start.second())
+                            .map_err(invalid_time)?,
+                        WeeklyTime::try_new(end.weekday(), end.hour(), end.minute(), end.second())
+                            .map_err(invalid_time)?,
+                        timezone.clone(),
+                    ))
+                })
+                .collect()
+        }
+        Some("MONTHLY") if by_day.is_empty() => {
+            if chrono::Duration::days(28) < duration {
+                return Err(too_long());
+            }
+
+            let days = if by_month_day.is_empty() {
+                vec![start.day() as u8]
+            } else {
+                by_month_day
+            };
+
+            days.into_iter()
+                .map(|day| {
+                    // NOTE: 31日まである月を基準に終わりの日付を求める
+                    let start = chrono::NaiveDate::from_ymd_opt(1970, 1, day as u32)
+                        .ok_or_else(unsupported)?
+                        .and_time(start.time());
+                    let end = start + duration;
+
+                    Ok(TodoSchedule::Monthly(
+                        MonthlyTime::try_new(day, start.hour(), start.minute(), start.second())
+                            .map_err(invalid_time)?,
+                        MonthlyTime::try_new(
+                            end.day() as u8,
+                            end.hour(),
+                            end.minute(),
+                            end.second(),
+                        )
+                        .map_err(invalid_time)?,
+                        timezone.clone(),
+                    ))
+                })
+                .collect()
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<chrono::Weekday> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(chrono::Weekday::Mon),
+        "TU" => Some(chrono::Weekday::Tue),
+        "WE" => Some(chrono::Weekday::Wed),
+        "TH" => Some(chrono::Weekday::Thu),
+        "FR" => Some(chrono::Weekday::Fri),
+        "SA" => Some(chrono::Weekday::Sat),
+        "SU" => Some(chrono::Weekday::Sun),
+        // NOTE: 1MO や -1FR のような序数付きには未対応
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(lines: &[&str]) -> String {
+        let mut text = vec!["BEGIN:VCALENDAR", "VERSION:2.0"];
+        text.extend_from_slice(lines);
+        text.push("END:VCALENDAR");
+
+        text.join("\r\n")
+    }
+
+    fn import(text: &str) -> Vec<TodoImportEntryDto> {
+        todo_import_entries_from_ics(text, &Timezone::utc())
+    }
+
+    fn datetime(value: &str) -> DateTime {
+        DateTime::new(
+            chrono::DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        )
+    }
+
+    #[test]
+    fn unfolds_continuation_lines() {
+        let text = calendar(&[
+            "BEGIN:VEVENT",
+            "UID:folded",
+            "SUMMARY:release ",
+            " v2",
+            "DESCRIPTION:first line\\nsecond",
+            "\t line",
+            "DTSTART:20250301T090000Z",
+            "END:VEVENT",
+        ]);
+
+        let entries = import(&text);
+        assert_eq!(entries.len(), 1);
+
+        let draft = entries[0].draft.as_ref().unwrap();
+        assert_eq!(draft.name.clone().value(), "release v2");
+        assert_eq!(draft.description.clone().value(), "first line\nsecond line");
+    }
+
+    #[test]
+    fn quoted_param_may_contain_colon() {
+        let line = parse_content_line(
+            "DTSTART;X-NOTE=\"see: agenda\";TZID=\"America/New_York\":20250301T090000",
+        )
+        .unwrap();
+
+        assert_eq!(line.name, "DTSTART");
+        assert_eq!(line.param("X-NOTE"), Some("see: agenda"));
+        assert_eq!(line.param("TZID"), Some("America/New_York"));
+        assert_eq!(line.value, "20250301T090000");
+
+        let text = calendar(&[
+            "BEGIN:VEVENT",
+            "SUMMARY:meeting",
+            "DTSTART;X-NOTE=\"see: agenda\";TZID=\"America/New_York\":20250301T090000",
+            "DURATION:PT1H",
+            "END:VEVENT",
+        ]);
+
+        let entries = import(&text);
+        let draft = entries[0].draft.as_ref().unwrap();
+
+        assert_eq!(
+            draft.schedules,
+            vec![TodoSchedule::Once(
+                datetime("2025-03-01T14:00:00Z"),
+                datetime("2025-03-01T15:00:00Z"),
+            )]
+        );
+        assert!(entries[0].warnings.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_events() {
+        let text = calendar(&[
+            "BEGIN:VEVENT",
+            "UID:multiple-rrules",
+            "SUMMARY:standup",
+            "DTSTART:20250303T090000Z",
+            "RRULE:FREQ=DAILY",
+            "RRULE:FREQ=WEEKLY;BYDAY=MO",
+            "END:VEVENT",
+            "BEGIN:VEVENT",
+            "UID:missing-summary",
+            "DTSTART:20250303T090000Z",
+            "END:VEVENT",
+            "BEGIN:VEVENT",
+            "UID:missing-dtstart",
+            "SUMMARY:standup",
+            "RRULE:FREQ=DAILY",
+            "END:VEVENT",
+            "BEGIN:VEVENT",
+            "UID:invalid-dtstart",
+            "SUMMARY:standup",
+            "DTSTART:2025-03-03",
+            "END:VEVENT",
+        ]);
+
+        let errors = import(&text)
+            .into_iter()
+            .map(|entry| (entry.uid.unwrap(), entry.draft.unwrap_err()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            vec![
+                ("multiple-rrules".to_string(), "multiple-rrules".to_string()),
+                ("missing-summary".to_string(), "missing-summary".to_string()),
+                ("missing-dtstart".to_string(), "missing-dtstart".to_string()),
+                (
+                    "invalid-dtstart".to_string(),
+                    "invalid-date-time; name=DTSTART; value=2025-03-03".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_nested_components() {
+        let text = calendar(&[
+            "BEGIN:VTODO",
+            "SUMMARY:pay rent",
+            "DUE;VALUE=DATE:20250401",
+            "BEGIN:VALARM",
+            "ACTION:DISPLAY",
+            "DESCRIPTION:reminder",
+            "END:VALARM",
+            "END:VTODO",
+        ]);
+
+        let entries = import(&text);
+        assert_eq!(entries.len(), 1);
+
+        let draft = entries[0].draft.as_ref().unwrap();
+        assert_eq!(draft.description.clone().value(), "");
+        assert_eq!(draft.deadlined_at, Some(datetime("2025-04-01T00:00:00Z")));
+        assert!(draft.schedules.is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use todoroki_domain::{
+    entities::{
+        comment::{Comment, CommentBody, CommentId, CommentTarget},
+        user::UserId,
+    },
+    value_objects::error::ErrorCode,
+};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CommentRequest {
+    pub body: String,
+    /// 返信先のコメントのID
+    pub parent_id: Option<String>,
+}
+
+impl CommentRequest {
+    pub fn try_into_with_target_and_created_by(
+        self,
+        target: CommentTarget,
+        created_by: UserId,
+    ) -> Result<Comment, ErrorCode> {
+        Ok(Comment::generate(
+            target,
+            self.parent_id.map(CommentId::try_from).transpose()?,
+            CommentBody::new(self.body),
+            created_by,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CommentUpdateRequest {
+    pub body: String,
+}
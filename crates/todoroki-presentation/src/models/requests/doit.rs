@@ -4,12 +4,22 @@ use serde::Deserialize;
 use todoroki_domain::{
     entities::{
         self,
-        doit::{DoitDescription, DoitId, DoitName, DoitPublishment},
+        doit::{DoitDescription, DoitId, DoitName, DoitPublishment, DoitReviewCommand, DoitStatus},
+        todo::TodoId,
         user::UserId,
     },
+    repositories::{
+        doit::{DoitAffectsToFilter, DoitFilter, DoitSortKey},
+        pagination::PageRequest,
+    },
     value_objects::{datetime::DateTime, error::ErrorCode},
 };
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::{
+    label::{label_condition_try_from_query, LabelMatchQuery},
+    page::{page_request_try_from_query, SortOrderQuery},
+};
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct DoitRequest {
@@ -60,9 +70,7 @@ impl DoitRequest {
                 DoitPublishment::Private(self.alternative_name)
             },
             requested_labels,
-            self.deadlined_at
-                .map(|t| DateTime::try_from(t))
-                .transpose()?,
+            self.deadlined_at.map(DateTime::try_from).transpose()?,
             created_by,
         ))
     }
@@ -93,10 +101,175 @@ impl DoitUpdateCommand {
                     DoitPublishment::Private(self.alternative_name)
                 }
             }),
-            None, // NOTE: affects_to is not updated by PATCH /doits/{doit_id}
+            None, // NOTE: affects_to is set only by POST /doits/{doit_id}/accept
             self.deadlined_at
                 .map(|opt_t| opt_t.map(DateTime::try_from).transpose())
                 .transpose()?,
         ))
     }
 }
+
+// todo_id を指定しなければ Doit の内容から Todo を作る
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DoitAcceptRequest {
+    pub todo_id: Option<String>,
+}
+
+impl DoitAcceptRequest {
+    pub fn try_into_todo_id(self) -> Result<Option<TodoId>, ErrorCode> {
+        self.todo_id.map(TodoId::try_from).transpose()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DoitHoldRequest {
+    pub reason: Option<String>,
+}
+
+impl DoitHoldRequest {
+    pub fn into_with_id(self, id: DoitId) -> DoitReviewCommand {
+        DoitReviewCommand::hold(id, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DoitRejectRequest {
+    pub reason: String,
+}
+
+impl DoitRejectRequest {
+    pub fn into_with_id(self, id: DoitId) -> DoitReviewCommand {
+        DoitReviewCommand::reject(id, self.reason)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DoitDuplicateRequest {
+    /// 重複している元の Doit のID
+    pub doit_id: String,
+    pub reason: Option<String>,
+}
+
+impl DoitDuplicateRequest {
+    pub fn try_into_with_id(self, id: DoitId) -> Result<DoitReviewCommand, ErrorCode> {
+        Ok(DoitReviewCommand::duplicate(
+            id,
+            DoitId::try_from(self.doit_id)?,
+            self.reason,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DoitListQuery {
+    /// カンマ区切りのラベルID
+    pub labels: Option<String>,
+    /// `labels` のいずれかを含むか(any)、すべてを含むか(all)。既定は any
+    #[param(inline)]
+    pub label_match: Option<LabelMatchQuery>,
+    pub is_public: Option<bool>,
+    /// この日時以降の締め切り (RFC 3339)
+    pub deadlined_from: Option<String>,
+    /// この日時より前の締め切り (RFC 3339)
+    pub deadlined_to: Option<String>,
+    /// 作成したユーザーのID
+    pub created_by: Option<String>,
+    /// `linked`, `unlinked` または紐づく Todo のID
+    pub affects_to: Option<String>,
+    #[param(inline)]
+    pub status: Option<DoitStatusQuery>,
+    /// 既定は updated_at
+    #[param(inline)]
+    pub sort: Option<DoitSortQuery>,
+    /// 既定は desc
+    #[param(inline)]
+    pub order: Option<SortOrderQuery>,
+    /// 1ページの件数 (最大200)。既定は50
+    pub limit: Option<u32>,
+    /// 直前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+pub enum DoitStatusQuery {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "on-hold")]
+    OnHold,
+    #[serde(rename = "accepted")]
+    Accepted,
+    #[serde(rename = "rejected")]
+    Rejected,
+    #[serde(rename = "duplicate")]
+    Duplicate,
+}
+
+impl From<DoitStatusQuery> for DoitStatus {
+    fn from(value: DoitStatusQuery) -> Self {
+        match value {
+            DoitStatusQuery::Pending => Self::Pending,
+            DoitStatusQuery::OnHold => Self::OnHold,
+            DoitStatusQuery::Accepted => Self::Accepted,
+            DoitStatusQuery::Rejected => Self::Rejected,
+            DoitStatusQuery::Duplicate => Self::Duplicate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub enum DoitSortQuery {
+    #[serde(rename = "created_at")]
+    CreatedAt,
+    #[serde(rename = "updated_at")]
+    UpdatedAt,
+    /// 締め切りがないものは末尾
+    #[serde(rename = "deadline")]
+    Deadline,
+    /// 非公開のものは代替名で並べる
+    #[serde(rename = "name")]
+    Name,
+    /// 投票数
+    #[serde(rename = "votes")]
+    Votes,
+}
+
+impl DoitListQuery {
+    pub fn try_into_page_request(&self) -> Result<PageRequest<DoitSortKey>, ErrorCode> {
+        page_request_try_from_query(
+            match self.sort {
+                Some(DoitSortQuery::CreatedAt) => DoitSortKey::CreatedAt,
+                Some(DoitSortQuery::UpdatedAt) | None => DoitSortKey::UpdatedAt,
+                Some(DoitSortQuery::Deadline) => DoitSortKey::Deadline,
+                Some(DoitSortQuery::Name) => DoitSortKey::Name,
+                Some(DoitSortQuery::Votes) => DoitSortKey::Votes,
+            },
+            self.order.clone(),
+            self.limit,
+            self.cursor.clone(),
+        )
+    }
+}
+
+impl TryFrom<DoitListQuery> for DoitFilter {
+    type Error = ErrorCode;
+
+    fn try_from(value: DoitListQuery) -> Result<Self, Self::Error> {
+        Ok(DoitFilter::new(
+            label_condition_try_from_query(value.labels, value.label_match)?,
+            value.is_public,
+            value.deadlined_from.map(DateTime::try_from).transpose()?,
+            value.deadlined_to.map(DateTime::try_from).transpose()?,
+            value.created_by.map(UserId::try_from).transpose()?,
+            value
+                .affects_to
+                .map(|s| match s.as_str() {
+                    "linked" => Ok(DoitAffectsToFilter::Linked),
+                    "unlinked" => Ok(DoitAffectsToFilter::Unlinked),
+                    _ => TodoId::try_from(s).map(DoitAffectsToFilter::Todo),
+                })
+                .transpose()?,
+            value.status.map(DoitStatus::from),
+        ))
+    }
+}
@@ -2,11 +2,17 @@ use serde::Deserialize;
 use todoroki_domain::{
     entities::{
         self,
-        label::{LabelColor, LabelDescription, LabelName},
+        label::{LabelColor, LabelDescription, LabelId, LabelName},
+    },
+    repositories::{
+        label::{LabelCondition, LabelSortKey},
+        pagination::PageRequest,
     },
     value_objects::error::ErrorCode,
 };
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::page::{page_request_try_from_query, SortOrderQuery};
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct LabelRequest {
@@ -47,3 +53,72 @@ fn color_try_from_str(s: &str) -> Result<LabelColor, ErrorCode> {
 
     Ok(LabelColor::new(red, green, blue))
 }
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub enum LabelMatchQuery {
+    #[serde(rename = "any")]
+    Any,
+    #[serde(rename = "all")]
+    All,
+}
+
+// labels はカンマ区切りのラベルIDの列
+pub(crate) fn label_condition_try_from_query(
+    labels: Option<String>,
+    label_match: Option<LabelMatchQuery>,
+) -> Result<Option<LabelCondition>, ErrorCode> {
+    let Some(labels) = labels else {
+        return Ok(None);
+    };
+
+    let ids = labels
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| LabelId::try_from(s.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(match label_match.unwrap_or(LabelMatchQuery::Any) {
+        LabelMatchQuery::Any => LabelCondition::Any(ids),
+        LabelMatchQuery::All => LabelCondition::All(ids),
+    }))
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LabelListQuery {
+    /// 既定は updated_at
+    #[param(inline)]
+    pub sort: Option<LabelSortQuery>,
+    /// 既定は desc
+    #[param(inline)]
+    pub order: Option<SortOrderQuery>,
+    /// 1ページの件数 (最大200)。既定は50
+    pub limit: Option<u32>,
+    /// 直前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub enum LabelSortQuery {
+    #[serde(rename = "created_at")]
+    CreatedAt,
+    #[serde(rename = "updated_at")]
+    UpdatedAt,
+    #[serde(rename = "name")]
+    Name,
+}
+
+impl LabelListQuery {
+    pub fn try_into_page_request(self) -> Result<PageRequest<LabelSortKey>, ErrorCode> {
+        page_request_try_from_query(
+            match self.sort {
+                Some(LabelSortQuery::CreatedAt) => LabelSortKey::CreatedAt,
+                Some(LabelSortQuery::UpdatedAt) | None => LabelSortKey::UpdatedAt,
+                Some(LabelSortQuery::Name) => LabelSortKey::Name,
+            },
+            self.order,
+            self.limit,
+            self.cursor,
+        )
+    }
+}
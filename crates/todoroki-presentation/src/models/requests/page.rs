@@ -0,0 +1,35 @@
+use serde::Deserialize;
+use todoroki_domain::{
+    repositories::pagination::{PageCursor, PageRequest, SortOrder},
+    value_objects::error::ErrorCode,
+};
+use utoipa::ToSchema;
+
+const DEFAULT_PAGE_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub enum SortOrderQuery {
+    #[serde(rename = "asc")]
+    Asc,
+    #[serde(rename = "desc")]
+    Desc,
+}
+
+// cursor は直前のレスポンスの next_cursor をそのまま受け取る
+// NOTE: sort と order も直前のリクエストと同じでなければならない
+pub(crate) fn page_request_try_from_query<K: std::fmt::Display>(
+    sort: K,
+    order: Option<SortOrderQuery>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+) -> Result<PageRequest<K>, ErrorCode> {
+    PageRequest::try_new(
+        sort,
+        match order.unwrap_or(SortOrderQuery::Desc) {
+            SortOrderQuery::Asc => SortOrder::Asc,
+            SortOrderQuery::Desc => SortOrder::Desc,
+        },
+        limit.unwrap_or(DEFAULT_PAGE_LIMIT),
+        cursor.map(PageCursor::try_from).transpose()?,
+    )
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// 検索語。`"..."` でフレーズ検索、`or` で OR 検索、`-` で除外
+    pub q: String,
+    /// 種類ごとの最大件数 (最大100)。既定は20
+    pub limit: Option<u32>,
+}
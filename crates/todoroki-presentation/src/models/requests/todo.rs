@@ -6,12 +6,22 @@ use todoroki_domain::{
         self,
         todo::{TodoDescription, TodoId, TodoName, TodoPublishment},
     },
+    repositories::{
+        pagination::PageRequest,
+        todo::{TodoFilter, TodoSortKey},
+    },
     value_objects::{
-        datetime::{DateTime, MonthlyTime, Time, WeeklyTime},
+        datetime::{DateTime, MonthlyTime, Time, Timezone, WeeklyTime},
         error::ErrorCode,
+        recurrence::RecurrenceRule,
     },
 };
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::{
+    label::{label_condition_try_from_query, LabelMatchQuery},
+    page::{page_request_try_from_query, SortOrderQuery},
+};
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct TodoRequest {
@@ -20,6 +30,8 @@ pub struct TodoRequest {
     pub is_public: bool,
     pub alternative_name: Option<String>,
     pub scheduled_at: Option<String>,
+    /// 親の todo の ID
+    pub parent_id: Option<String>,
     pub labels: Vec<TodoLabel>,
     pub schedules: Vec<TodoScheduleRequest>,
 }
@@ -29,11 +41,25 @@ pub struct TodoLabel {
     pub id: String,
 }
 
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct TodoBlockerRequest {
+    pub id: String,
+}
+
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct TodoScheduleRequest {
     pub interval: TodoScheduleIntervalRequest,
+    /// recurrence の場合は最初の回の開始日時
     pub starts_at: String,
+    /// recurrence の場合は最初の回の終了日時
     pub ends_at: String,
+    /// recurrence の場合のみ。RFC 5545 の RRULE (例: FREQ=WEEKLY;INTERVAL=2;BYDAY=TU)
+    pub rrule: Option<String>,
+    /// recurrence の場合のみ。除外する回の開始日時
+    pub exdates: Option<Vec<String>>,
+    /// 時刻を解釈する IANA のタイムゾーン (例: Asia/Tokyo)。既定はユーザーのタイムゾーン
+    /// daily, weekly, monthly の starts_at と ends_at は 1970-01 のこのタイムゾーンでの時刻として表す
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
@@ -46,56 +72,94 @@ pub enum TodoScheduleIntervalRequest {
     Weekly,
     #[serde(rename = "monthly")]
     Monthly,
+    #[serde(rename = "recurrence")]
+    Recurrence,
 }
 
-impl TryFrom<TodoScheduleRequest> for entities::todo::TodoSchedule {
-    type Error = ErrorCode;
+impl TodoScheduleRequest {
+    // timezone を指定しなかった場合は default_timezone で解釈する
+    pub fn try_into_with_timezone(
+        self,
+        default_timezone: &Timezone,
+    ) -> Result<entities::todo::TodoSchedule, ErrorCode> {
+        let timezone = match self.timezone {
+            Some(timezone) => Timezone::try_from(timezone)?,
+            None => default_timezone.clone(),
+        };
 
-    fn try_from(value: TodoScheduleRequest) -> Result<Self, Self::Error> {
-        match value.interval {
+        match self.interval {
             TodoScheduleIntervalRequest::Once => Ok(entities::todo::TodoSchedule::Once(
-                DateTime::try_from(value.starts_at)?,
-                DateTime::try_from(value.ends_at)?,
+                DateTime::try_from(self.starts_at)?,
+                DateTime::try_from(self.ends_at)?,
             )),
             TodoScheduleIntervalRequest::Daily => Ok(entities::todo::TodoSchedule::Daily(
-                Time::try_from(DateTime::try_from(value.starts_at)?)?,
-                Time::try_from(DateTime::try_from(value.ends_at)?)?,
+                Time::try_from(DateTime::try_from(self.starts_at)?)?,
+                Time::try_from(DateTime::try_from(self.ends_at)?)?,
+                timezone,
             )),
             TodoScheduleIntervalRequest::Weekly => Ok(entities::todo::TodoSchedule::Weekly(
-                WeeklyTime::try_from(DateTime::try_from(value.starts_at)?)?,
-                WeeklyTime::try_from(DateTime::try_from(value.ends_at)?)?,
+                WeeklyTime::try_from(DateTime::try_from(self.starts_at)?)?,
+                WeeklyTime::try_from(DateTime::try_from(self.ends_at)?)?,
+                timezone,
             )),
             TodoScheduleIntervalRequest::Monthly => Ok(entities::todo::TodoSchedule::Monthly(
-                MonthlyTime::try_from(DateTime::try_from(value.starts_at)?)?,
-                MonthlyTime::try_from(DateTime::try_from(value.ends_at)?)?,
+                MonthlyTime::try_from(DateTime::try_from(self.starts_at)?)?,
+                MonthlyTime::try_from(DateTime::try_from(self.ends_at)?)?,
+                timezone,
             )),
+            TodoScheduleIntervalRequest::Recurrence => {
+                let rrule = self.rrule.ok_or(ErrorCode::InvalidRecurrenceRule(
+                    "missing-rrule".to_string(),
+                ))?;
+                let exdates = self
+                    .exdates
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(DateTime::try_from)
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                entities::todo::TodoSchedule::try_recurrence(
+                    DateTime::try_from(self.starts_at)?,
+                    DateTime::try_from(self.ends_at)?,
+                    RecurrenceRule::try_new(&rrule, exdates)?,
+                    timezone,
+                )
+            }
         }
     }
 }
 
+// 存在するラベルのうち、リクエストされたものを返す
+fn labels_try_from_requests(
+    requested: Vec<TodoLabel>,
+    labels: Vec<entities::label::Label>,
+) -> Result<Vec<entities::label::Label>, ErrorCode> {
+    let exists_labels: HashMap<uuid::Uuid, entities::label::Label> = labels
+        .into_iter()
+        .map(|l| (l.id().clone().value(), l))
+        .collect();
+
+    requested
+        .into_iter()
+        .map(|l| uuid::Uuid::parse_str(&l.id).map_err(|_| ErrorCode::InvalidUuidFormat(l.id)))
+        .collect::<Result<Vec<uuid::Uuid>, ErrorCode>>()?
+        .into_iter()
+        .map(|id| {
+            exists_labels
+                .get(&id)
+                .cloned()
+                .ok_or(ErrorCode::LabelNotFound(entities::label::LabelId::new(id)))
+        })
+        .collect()
+}
+
 impl TodoRequest {
-    pub fn try_into_with_labels(
+    pub fn try_into_with_labels_and_timezone(
         self,
         labels: Vec<entities::label::Label>,
+        default_timezone: Timezone,
     ) -> Result<entities::todo::Todo, ErrorCode> {
-        let exists_labels: HashMap<uuid::Uuid, entities::label::Label> = labels
-            .into_iter()
-            .map(|l| (l.id().clone().value(), l))
-            .collect();
-
-        let requested_labels: Vec<entities::label::Label> = self
-            .labels
-            .into_iter()
-            .map(|l| uuid::Uuid::parse_str(&l.id).map_err(|_| ErrorCode::InvalidUuidFormat(l.id)))
-            .collect::<Result<Vec<uuid::Uuid>, ErrorCode>>()?
-            .into_iter()
-            .map(|id| {
-                exists_labels
-                    .get(&id)
-                    .cloned()
-                    .ok_or(ErrorCode::LabelNotFound(entities::label::LabelId::new(id)))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let requested_labels = labels_try_from_requests(self.labels, labels)?;
 
         Ok(entities::todo::Todo::generate(
             TodoName::new(self.name),
@@ -105,14 +169,13 @@ impl TodoRequest {
             } else {
                 TodoPublishment::Private(self.alternative_name)
             },
+            self.parent_id.map(TodoId::try_from).transpose()?,
             requested_labels,
             self.schedules
                 .into_iter()
-                .map(|s| entities::todo::TodoSchedule::try_from(s))
+                .map(|schedule| schedule.try_into_with_timezone(&default_timezone))
                 .collect::<Result<Vec<_>, _>>()?,
-            self.scheduled_at
-                .map(|t| DateTime::try_from(t))
-                .transpose()?,
+            self.scheduled_at.map(DateTime::try_from).transpose()?,
         ))
     }
 }
@@ -124,22 +187,50 @@ pub struct TodoUpdateCommand {
     pub is_public: Option<bool>,
     pub alternative_name: Option<String>,
     pub scheduled_at: Option<Option<String>>,
-    pub status: Option<TodoUpdateProgressStatus>,
+    /// 親の todo の ID。null を指定すると親から外す
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[schema(value_type = Option<String>)]
+    pub parent_id: Option<Option<String>>,
+    pub status: Option<TodoStatusRequest>,
+    /// status を completed にする場合に、子孫の todo もすべて完了にするか。既定は false
+    pub cascade: Option<bool>,
+    /// status を on-progress にする場合に、完了していない blocker があっても始めるか。既定は false
+    pub force: Option<bool>,
+    pub add_labels: Option<Vec<TodoLabel>>,
+    pub remove_labels: Option<Vec<TodoLabel>>,
+    /// この todo より先に終わらせる必要のある todo
+    pub add_blockers: Option<Vec<TodoBlockerRequest>>,
+    pub remove_blockers: Option<Vec<TodoBlockerRequest>>,
+    /// 指定した場合は schedules をすべて置き換える
+    pub schedules: Option<Vec<TodoScheduleRequest>>,
 }
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
-pub enum TodoUpdateProgressStatus {
+pub enum TodoStatusRequest {
+    #[serde(rename = "not-started")]
+    NotStarted,
     #[serde(rename = "on-progress")]
     OnProgress,
+    #[serde(rename = "paused")]
+    Paused,
     #[serde(rename = "completed")]
     Completed,
+    #[serde(rename = "cancelled")]
+    Cancelled,
 }
 
 impl TodoUpdateCommand {
-    pub fn try_into_with_id(
+    pub fn try_into_with_id_labels_and_timezone(
         self,
         id: TodoId,
+        labels: Vec<entities::label::Label>,
+        default_timezone: Timezone,
     ) -> Result<entities::todo::TodoUpdateCommand, ErrorCode> {
+        let add_labels =
+            labels_try_from_requests(self.add_labels.unwrap_or_default(), labels.clone())?;
+        let remove_labels =
+            labels_try_from_requests(self.remove_labels.unwrap_or_default(), labels)?;
+
         Ok(entities::todo::TodoUpdateCommand::new(
             id,
             self.name.map(TodoName::new),
@@ -154,17 +245,138 @@ impl TodoUpdateCommand {
             self.scheduled_at
                 .map(|opt_t| opt_t.map(DateTime::try_from).transpose())
                 .transpose()?,
-            self.status
-                .map(entities::todo::TodoUpdateProgressStatus::from),
+            self.parent_id
+                .map(|opt_id| opt_id.map(TodoId::try_from).transpose())
+                .transpose()?,
+            self.status.map(entities::todo::TodoStatus::from),
+            add_labels.into_iter().map(|l| l.id().clone()).collect(),
+            remove_labels.into_iter().map(|l| l.id().clone()).collect(),
+            self.add_blockers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|b| TodoId::try_from(b.id))
+                .collect::<Result<Vec<_>, _>>()?,
+            self.remove_blockers
+                .unwrap_or_default()
+                .into_iter()
+                .map(|b| TodoId::try_from(b.id))
+                .collect::<Result<Vec<_>, _>>()?,
+            self.schedules
+                .map(|schedules| {
+                    schedules
+                        .into_iter()
+                        .map(|schedule| schedule.try_into_with_timezone(&default_timezone))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
         ))
     }
 }
 
-impl From<TodoUpdateProgressStatus> for entities::todo::TodoUpdateProgressStatus {
-    fn from(value: TodoUpdateProgressStatus) -> Self {
+// フィールドがない場合は None、null の場合は Some(None) にする
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl From<TodoStatusRequest> for entities::todo::TodoStatus {
+    fn from(value: TodoStatusRequest) -> Self {
         match value {
-            TodoUpdateProgressStatus::OnProgress => Self::OnProgress,
-            TodoUpdateProgressStatus::Completed => Self::Completed,
+            TodoStatusRequest::NotStarted => Self::NotStarted,
+            TodoStatusRequest::OnProgress => Self::OnProgress,
+            TodoStatusRequest::Paused => Self::Paused,
+            TodoStatusRequest::Completed => Self::Completed,
+            TodoStatusRequest::Cancelled => Self::Cancelled,
         }
     }
 }
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoListQuery {
+    /// カンマ区切りのラベルID
+    pub labels: Option<String>,
+    /// `labels` のいずれかを含むか(any)、すべてを含むか(all)。既定は any
+    #[param(inline)]
+    pub label_match: Option<LabelMatchQuery>,
+    #[param(inline)]
+    pub status: Option<TodoStatusRequest>,
+    pub is_public: Option<bool>,
+    /// この日時以降の締め切り (RFC 3339)
+    pub deadlined_from: Option<String>,
+    /// この日時より前の締め切り (RFC 3339)
+    pub deadlined_to: Option<String>,
+    /// 既定は updated_at
+    #[param(inline)]
+    pub sort: Option<TodoSortQuery>,
+    /// 既定は desc
+    #[param(inline)]
+    pub order: Option<SortOrderQuery>,
+    /// 1ページの件数 (最大200)。既定は50
+    pub limit: Option<u32>,
+    /// 直前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub enum TodoSortQuery {
+    #[serde(rename = "created_at")]
+    CreatedAt,
+    #[serde(rename = "updated_at")]
+    UpdatedAt,
+    /// 締め切りがないものは末尾
+    #[serde(rename = "deadline")]
+    Deadline,
+    /// 非公開のものは代替名で並べる
+    #[serde(rename = "name")]
+    Name,
+}
+
+impl TodoListQuery {
+    pub fn try_into_page_request(&self) -> Result<PageRequest<TodoSortKey>, ErrorCode> {
+        page_request_try_from_query(
+            match self.sort {
+                Some(TodoSortQuery::CreatedAt) => TodoSortKey::CreatedAt,
+                Some(TodoSortQuery::UpdatedAt) | None => TodoSortKey::UpdatedAt,
+                Some(TodoSortQuery::Deadline) => TodoSortKey::Deadline,
+                Some(TodoSortQuery::Name) => TodoSortKey::Name,
+            },
+            self.order.clone(),
+            self.limit,
+            self.cursor.clone(),
+        )
+    }
+}
+
+impl TryFrom<TodoListQuery> for TodoFilter {
+    type Error = ErrorCode;
+
+    fn try_from(value: TodoListQuery) -> Result<Self, Self::Error> {
+        Ok(TodoFilter::new(
+            label_condition_try_from_query(value.labels, value.label_match)?,
+            value.status.map(entities::todo::TodoStatus::from),
+            value.is_public,
+            value.deadlined_from.map(DateTime::try_from).transpose()?,
+            value.deadlined_to.map(DateTime::try_from).transpose()?,
+            None,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TodoOccurrenceQuery {
+    /// この日時以降に終わる回 (RFC 3339)
+    pub from: String,
+    /// この日時より前に始まる回 (RFC 3339)。`from` から366日以内
+    pub to: String,
+}
+
+impl TodoOccurrenceQuery {
+    pub fn try_into_range(self) -> Result<(DateTime, DateTime), ErrorCode> {
+        Ok((DateTime::try_from(self.from)?, DateTime::try_from(self.to)?))
+    }
+}
@@ -1,10 +1,21 @@
 use serde::Deserialize;
-use todoroki_domain::{entities, value_objects::error::ErrorCode};
+use todoroki_domain::{
+    entities,
+    value_objects::{datetime::Timezone, error::ErrorCode},
+};
 use utoipa::ToSchema;
 
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UserRequest {
     pub name: String,
+    /// IANA のタイムゾーン (例: Asia/Tokyo)。既定は UTC
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UserTimezoneRequest {
+    /// IANA のタイムゾーン (例: Asia/Tokyo)
+    pub timezone: String,
 }
 
 impl UserRequest {
@@ -17,6 +28,10 @@ impl UserRequest {
             role,
             entities::user::UserName::new(self.name),
             email,
+            self.timezone
+                .map(Timezone::try_from)
+                .transpose()?
+                .unwrap_or_default(),
         ))
     }
 }
@@ -0,0 +1,56 @@
+use serde::Deserialize;
+use todoroki_domain::{
+    entities::{
+        user::UserId,
+        webhook::{Webhook, WebhookEventType, WebhookUrl},
+    },
+    repositories::{pagination::PageRequest, webhook::WebhookDeliverySortKey},
+    value_objects::error::ErrorCode,
+};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::page::{page_request_try_from_query, SortOrderQuery};
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    /// 送信先の URL (http:// または https://)
+    pub url: String,
+    /// 購読するイベントの種類 (例: `todo.created`, `doit.accepted`)
+    pub event_types: Vec<String>,
+}
+
+impl WebhookRequest {
+    pub fn try_into_with_created_by(self, created_by: UserId) -> Result<Webhook, ErrorCode> {
+        Ok(Webhook::generate(
+            WebhookUrl::try_from(self.url)?,
+            self.event_types
+                .into_iter()
+                .map(WebhookEventType::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            created_by,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveryListQuery {
+    /// 作成日時の順。既定は desc
+    #[param(inline)]
+    pub order: Option<SortOrderQuery>,
+    /// 1ページの件数 (最大200)。既定は50
+    pub limit: Option<u32>,
+    /// 直前のレスポンスの `next_cursor`
+    pub cursor: Option<String>,
+}
+
+impl WebhookDeliveryListQuery {
+    pub fn try_into_page_request(&self) -> Result<PageRequest<WebhookDeliverySortKey>, ErrorCode> {
+        page_request_try_from_query(
+            WebhookDeliverySortKey::CreatedAt,
+            self.order.clone(),
+            self.limit,
+            self.cursor.clone(),
+        )
+    }
+}
@@ -0,0 +1,110 @@
+use serde::Deserialize;
+use todoroki_domain::{
+    entities::{
+        change::ChangeTopic,
+        label::LabelId,
+        todo::{TodoId, TodoStatus, TodoUpdateCommand},
+    },
+    value_objects::error::ErrorCode,
+};
+use todoroki_use_case::todo::dto::TodoUpdateOptions;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::models::requests::todo::TodoStatusRequest;
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct WebSocketQuery {
+    /// Firebase の ID トークン。ブラウザは Authorization ヘッダを送れないのでクエリでも受け付ける
+    pub token: Option<String>,
+}
+
+/// 接続後にクライアントから送るメッセージ
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WebSocketRequest {
+    /// 指定すると、このメッセージへの応答にそのまま入れて返す
+    pub request_id: Option<String>,
+    #[serde(flatten)]
+    pub body: WebSocketRequestBody,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum WebSocketRequestBody {
+    /// 新しいトークンで認証し直す。購読はそのまま続く
+    Auth {
+        token: String,
+    },
+    Subscribe {
+        topic: WebSocketTopicRequest,
+    },
+    Unsubscribe {
+        topic: WebSocketTopicRequest,
+    },
+    UpdateTodoStatus(WebSocketTodoStatusRequest),
+    VoteDoit {
+        doit_id: String,
+    },
+    UnvoteDoit {
+        doit_id: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum WebSocketTopicRequest {
+    /// すべての todo
+    Todos,
+    /// ラベルのついた todo / doit と、ラベルそのもの
+    Label { label_id: String },
+    /// todo と、それに紐づく doit
+    Todo { todo_id: String },
+    /// 自分が作った doit
+    MyDoits,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WebSocketTodoStatusRequest {
+    pub todo_id: String,
+    pub status: TodoStatusRequest,
+    /// status を completed にする場合に、子孫の todo もすべて完了にするか。既定は false
+    pub cascade: Option<bool>,
+    /// status を on-progress にする場合に、完了していない blocker があっても始めるか。既定は false
+    pub force: Option<bool>,
+}
+
+impl WebSocketTopicRequest {
+    pub fn try_into_topic(self) -> Result<ChangeTopic, ErrorCode> {
+        Ok(match self {
+            Self::Todos => ChangeTopic::Todos,
+            Self::Label { label_id } => ChangeTopic::Label(LabelId::try_from(label_id)?),
+            Self::Todo { todo_id } => ChangeTopic::Todo(TodoId::try_from(todo_id)?),
+            Self::MyDoits => ChangeTopic::MyDoits,
+        })
+    }
+}
+
+impl WebSocketTodoStatusRequest {
+    pub fn try_into_command(self) -> Result<(TodoUpdateCommand, TodoUpdateOptions), ErrorCode> {
+        let options = TodoUpdateOptions {
+            cascade: self.cascade.unwrap_or(false),
+            force: self.force.unwrap_or(false),
+        };
+
+        let cmd = TodoUpdateCommand::new(
+            TodoId::try_from(self.todo_id)?,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(TodoStatus::from(self.status)),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            None,
+        );
+
+        Ok((cmd, options))
+    }
+}
@@ -1,6 +1,14 @@
+pub mod audit;
+pub mod calendar;
+pub mod change;
+pub mod comment;
 pub mod doit;
 pub mod error;
 pub mod label;
+pub mod page;
+pub mod search;
 pub mod success;
 pub mod todo;
 pub mod user;
+pub mod webhook;
+pub mod websocket;
@@ -0,0 +1,86 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use todoroki_domain::entities::audit::{AuditAction, AuditEntity, AuditEvent};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    pub id: String,
+    pub entity: AuditEntityResponse,
+    pub entity_id: String,
+    pub action: AuditActionResponse,
+    /// 未登録のユーザーによるユーザー登録では null
+    pub actor_id: Option<String>,
+    /// 操作を許可した権限
+    pub permission: String,
+    /// 変更のあったフィールドの変更前の値。作成では null
+    pub before: Option<serde_json::Value>,
+    /// 変更のあったフィールドの変更後の値
+    pub after: Option<serde_json::Value>,
+    pub occurred_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum AuditEntityResponse {
+    #[serde(rename = "todo")]
+    Todo,
+    #[serde(rename = "doit")]
+    Doit,
+    #[serde(rename = "label")]
+    Label,
+    #[serde(rename = "user")]
+    User,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum AuditActionResponse {
+    #[serde(rename = "create")]
+    Create,
+    #[serde(rename = "update")]
+    Update,
+    #[serde(rename = "delete")]
+    Delete,
+    #[serde(rename = "status-change")]
+    StatusChange,
+}
+
+impl From<AuditEntity> for AuditEntityResponse {
+    fn from(value: AuditEntity) -> Self {
+        match value {
+            AuditEntity::Todo => Self::Todo,
+            AuditEntity::Doit => Self::Doit,
+            AuditEntity::Label => Self::Label,
+            AuditEntity::User => Self::User,
+        }
+    }
+}
+
+impl From<AuditAction> for AuditActionResponse {
+    fn from(value: AuditAction) -> Self {
+        match value {
+            AuditAction::Create => Self::Create,
+            AuditAction::Update => Self::Update,
+            AuditAction::Delete => Self::Delete,
+            AuditAction::StatusChange => Self::StatusChange,
+        }
+    }
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(value: AuditEvent) -> Self {
+        Self {
+            id: value.id().clone().value().as_hyphenated().to_string(),
+            entity: AuditEntityResponse::from(*value.entity()),
+            entity_id: value.entity_id().as_hyphenated().to_string(),
+            action: AuditActionResponse::from(*value.action()),
+            actor_id: value
+                .actor_id()
+                .clone()
+                .map(|id| id.value().as_hyphenated().to_string()),
+            permission: value.permission().clone(),
+            before: value.before().clone(),
+            after: value.after().clone(),
+            occurred_at: value.occurred_at().clone().value().to_rfc3339(),
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{Datelike, Timelike};
+use serde::Serialize;
+use todoroki_domain::{
+    entities::todo::TodoSchedule,
+    services::occurrence,
+    value_objects::datetime::{DateTime, Timezone},
+};
+use todoroki_use_case::todo::dto::{TodoDto, TodoImportResultDto};
+use utoipa::ToSchema;
+
+const CALENDAR_PRODUCT_ID: &str = "-//todoroki//todoroki-server//JA";
+const CALENDAR_NAME: &str = "todoroki";
+const CALENDAR_UID_DOMAIN: &str = "todoroki";
+// VTIMEZONE には現在から何年先までの切り替わりを出すか
+const TIMEZONE_YEARS_AHEAD: i64 = 10;
+
+// RFC 5545 形式のカレンダー
+#[derive(Debug, Clone)]
+pub struct CalendarResponse(String);
+
+impl From<Vec<TodoDto>> for CalendarResponse {
+    fn from(value: Vec<TodoDto>) -> Self {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:{CALENDAR_PRODUCT_ID}"),
+            "CALSCALE:GREGORIAN".to_string(),
+            format!("X-WR-CALNAME:{CALENDAR_NAME}"),
+        ];
+
+        let mut events = Vec::new();
+        // TZID で参照するタイムゾーンと、その中で最も早い回の開始日時
+        let mut timezones: Vec<(Timezone, DateTime)> = Vec::new();
+
+        for todo in value {
+            for (i, schedule) in todo.schedules.iter().enumerate() {
+                let timezone = schedule.timezone();
+
+                if let Some((starts_at, _)) = first_occurrence(&todo, schedule) {
+                    match timezones.iter_mut().find(|(tz, _)| *tz == timezone) {
+                        Some((_, earliest))
+                            if starts_at.clone().value() < earliest.clone().value() =>
+                        {
+                            *earliest = starts_at
+                        }
+                        Some(_) => {}
+                        None if timezone != Timezone::utc() => {
+                            timezones.push((timezone, starts_at))
+                        }
+                        None => {}
+                    }
+                }
+
+                events.extend(schedule_event(&todo, i, schedule));
+            }
+
+            events.extend(deadline_event(&todo));
+        }
+
+        for (timezone, from) in &timezones {
+            lines.extend(timezone_component(timezone, from));
+        }
+
+        lines.extend(events);
+        lines.push("END:VCALENDAR".to_string());
+
+        Self(
+            lines
+                .iter()
+                .map(|line| fold_line(line))
+                .collect::<Vec<String>>()
+                .join(""),
+        )
+    }
+}
+
+impl IntoResponse for CalendarResponse {
+    fn into_response(self) -> axum::response::Response {
+        (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+            self.0,
+        )
+            .into_response()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoImportReportResponse {
+    pub created: usize,
+    pub skipped: usize,
+    pub items: Vec<TodoImportItemResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoImportItemResponse {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub status: TodoImportItemStatusResponse,
+    pub todo_id: Option<String>,
+    pub reason: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum TodoImportItemStatusResponse {
+    #[serde(rename = "created")]
+    Created,
+    #[serde(rename = "skipped")]
+    Skipped,
+}
+
+impl From<Vec<TodoImportResultDto>> for TodoImportReportResponse {
+    fn from(value: Vec<TodoImportResultDto>) -> Self {
+        let items = value
+            .into_iter()
+            .map(TodoImportItemResponse::from)
+            .collect::<Vec<TodoImportItemResponse>>();
+
+        let created = items
+            .iter()
+            .filter(|item| matches!(item.status, TodoImportItemStatusResponse::Created))
+            .count();
+
+        Self {
+            created,
+            skipped: items.len() - created,
+            items,
+        }
+    }
+}
+
+impl From<TodoImportResultDto> for TodoImportItemResponse {
+    fn from(value: TodoImportResultDto) -> Self {
+        let (status, todo_id, reason) = match value.result {
+            Ok(id) => (
+                TodoImportItemStatusResponse::Created,
+                Some(id.value().as_hyphenated().to_string()),
+                None,
+            ),
+            Err(reason) => (TodoImportItemStatusResponse::Skipped, None, Some(reason)),
+        };
+
+        Self {
+            uid: value.uid,
+            summary: value.summary,
+            status,
+            todo_id,
+            reason,
+            warnings: value.warnings,
+        }
+    }
+}
+
+// NOTE: 繰り返しの起点は todo の作成日時以降で最初の回とする
+fn first_occurrence(todo: &TodoDto, schedule: &TodoSchedule) -> Option<(DateTime, DateTime)> {
+    let anchor = todo.created_at.clone();
+    let window_end = DateTime::new(anchor.clone().value() + chrono::Duration::days(62));
+
+    match schedule {
+        TodoSchedule::Once(s, e) | TodoSchedule::Recurrence(s, e, _, _) => {
+            Some((s.clone(), e.clone()))
+        }
+        _ => occurrence::expand_schedule(schedule, &anchor, &window_end)
+            .into_iter()
+            .next()
+            .map(|o| (o.starts_at().clone(), o.ends_at().clone())),
+    }
+}
+
+fn schedule_event(todo: &TodoDto, index: usize, schedule: &TodoSchedule) -> Vec<String> {
+    let Some((starts_at, ends_at)) = first_occurrence(todo, schedule) else {
+        return Vec::new();
+    };
+
+    let rrule = match schedule {
+        TodoSchedule::Once(_, _) => None,
+        TodoSchedule::Daily(_, _, _) => Some("FREQ=DAILY".to_string()),
+        TodoSchedule::Weekly(s, _, _) => {
+            Some(format!("FREQ=WEEKLY;BYDAY={}", weekday_code(s.weekday())))
+        }
+        // NOTE: 月に存在しない日付は末日に丸めるので、その日付と末日のうち早い方を選ぶ
+        TodoSchedule::Monthly(s, _, _) if s.date() > 28 => Some(format!(
+            "FREQ=MONTHLY;BYMONTHDAY={},-1;BYSETPOS=1",
+            s.date()
+        )),
+        TodoSchedule::Monthly(s, _, _) => Some(format!("FREQ=MONTHLY;BYMONTHDAY={}", s.date())),
+        TodoSchedule::Recurrence(_, _, rule, _) => Some(rule.to_string()),
+    }
+    // NOTE: 完了や中止したものは以降の回を出さない。ただし既に終わりが決まっている規則はそのまま出す
+    .map(|rrule| match (&todo.ended_at, schedule) {
+        (Some(_), TodoSchedule::Recurrence(_, _, rule, _))
+            if rule.count().is_some() || rule.until().is_some() =>
+        {
+            rrule
+        }
+        (Some(ended_at), _) => format!("{rrule};UNTIL={}", format_datetime(ended_at)),
+        (None, _) => rrule,
+    });
+
+    let timezone = schedule.timezone();
+
+    let exdates = match schedule {
+        TodoSchedule::Recurrence(_, _, rule, _) if !rule.exdates().is_empty() => Some(
+            rule.exdates()
+                .iter()
+                .map(|exdate| format_local_datetime(exdate, &timezone))
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+        _ => None,
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!(
+            "UID:{}-schedule-{index}@{CALENDAR_UID_DOMAIN}",
+            todo.id.as_hyphenated()
+        ),
+        format!("DTSTAMP:{}", format_datetime(&todo.updated_at)),
+        format!(
+            "DTSTART{}:{}",
+            timezone_parameter(&timezone),
+            format_local_datetime(&starts_at, &timezone)
+        ),
+        format!(
+            "DTEND{}:{}",
+            timezone_parameter(&timezone),
+            format_local_datetime(&ends_at, &timezone)
+        ),
+    ];
+
+    if let Some(rrule) = rrule {
+        lines.push(format!("RRULE:{rrule}"));
+    }
+
+    if let Some(exdates) = exdates {
+        lines.push(format!("EXDATE{}:{exdates}", timezone_parameter(&timezone)));
+    }
+
+    lines.extend([
+        format!("SUMMARY:{}", escape_text(&todo.name)),
+        format!("DESCRIPTION:{}", escape_text(&todo.description)),
+        "END:VEVENT".to_string(),
+    ]);
+
+    lines
+}
+
+fn deadline_event(todo: &TodoDto) -> Vec<String> {
+    let Some(deadlined_at) = &todo.deadlined_at else {
+        return Vec::new();
+    };
+
+    let deadline = deadlined_at.clone().value();
+
+    // NOTE: 0時ちょうどの締め切りは終日の予定とする
+    let (dtstart, dtend) = if deadline.num_seconds_from_midnight() == 0 {
+        (
+            format!("DTSTART;VALUE=DATE:{}", format_date(deadline.date_naive())),
+            format!(
+                "DTEND;VALUE=DATE:{}",
+                format_date(deadline.date_naive() + chrono::Days::new(1))
+            ),
+        )
+    } else {
+        (
+            format!("DTSTART:{}", format_datetime(deadlined_at)),
+            format!("DTEND:{}", format_datetime(deadlined_at)),
+        )
+    };
+
+    vec![
+        "BEGIN:VEVENT".to_string(),
+        format!(
+            "UID:{}-deadline@{CALENDAR_UID_DOMAIN}",
+            todo.id.as_hyphenated()
+        ),
+        format!("DTSTAMP:{}", format_datetime(&todo.updated_at)),
+        dtstart,
+        dtend,
+        format!(
+            "SUMMARY:{}",
+            escape_text(&format!("[締め切り] {}", todo.name))
+        ),
+        format!("DESCRIPTION:{}", escape_text(&todo.description)),
+        "END:VEVENT".to_string(),
+    ]
+}
+
+fn format_datetime(value: &DateTime) -> String {
+    value.clone().value().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// NOTE: UTC 以外では TZID を付けてそのタイムゾーンでの日時を出す。繰り返しの曜日や日付がずれないようにするため
+// TZID の定義は timezone_component で VTIMEZONE として出す
+fn timezone_parameter(timezone: &Timezone) -> String {
+    if *timezone == Timezone::utc() {
+        String::new()
+    } else {
+        format!(";TZID={}", timezone.name())
+    }
+}
+
+// TZID で参照するタイムゾーンの定義。from から数年先までの切り替わりをすべて並べる
+fn timezone_component(timezone: &Timezone, from: &DateTime) -> Vec<String> {
+    let to = DateTime::new(
+        DateTime::now().value().max(from.clone().value())
+            + chrono::Duration::days(365 * TIMEZONE_YEARS_AHEAD),
+    );
+
+    let mut lines = vec![
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", timezone.name()),
+    ];
+
+    let mut offset = timezone.utc_offset(from);
+    lines.extend(timezone_observance(timezone, from, offset));
+
+    for transition in timezone.transitions(from, &to) {
+        lines.extend(timezone_observance(timezone, &transition, offset));
+        offset = timezone.utc_offset(&transition);
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+
+    lines
+}
+
+// at から始まる時間帯。DTSTART は切り替わる前のずれでの日時とする
+fn timezone_observance(timezone: &Timezone, at: &DateTime, offset_from: i32) -> Vec<String> {
+    let kind = if timezone.is_dst(at) {
+        "DAYLIGHT"
+    } else {
+        "STANDARD"
+    };
+    let onset = at.clone().value() + chrono::Duration::seconds(offset_from.into());
+
+    vec![
+        format!("BEGIN:{kind}"),
+        format!("DTSTART:{}", onset.format("%Y%m%dT%H%M%S")),
+        format!("TZOFFSETFROM:{}", format_utc_offset(offset_from)),
+        format!("TZOFFSETTO:{}", format_utc_offset(timezone.utc_offset(at))),
+        format!("END:{kind}"),
+    ]
+}
+
+fn format_utc_offset(secs: i32) -> String {
+    let sign = if secs < 0 { '-' } else { '+' };
+    let secs = secs.unsigned_abs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if seconds == 0 {
+        format!("{sign}{hours:02}{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}{minutes:02}{seconds:02}")
+    }
+}
+
+fn format_local_datetime(value: &DateTime, timezone: &Timezone) -> String {
+    if *timezone == Timezone::utc() {
+        format_datetime(value)
+    } else {
+        timezone.to_local(value).format("%Y%m%dT%H%M%S").to_string()
+    }
+}
+
+fn format_date(value: chrono::NaiveDate) -> String {
+    format!("{:04}{:02}{:02}", value.year(), value.month(), value.day())
+}
+
+fn weekday_code(weekday: chrono::Weekday) -> &'static str {
+    match weekday {
+        chrono::Weekday::Mon => "MO",
+        chrono::Weekday::Tue => "TU",
+        chrono::Weekday::Wed => "WE",
+        chrono::Weekday::Thu => "TH",
+        chrono::Weekday::Fri => "FR",
+        chrono::Weekday::Sat => "SA",
+        chrono::Weekday::Sun => "SU",
+    }
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// 1行が75オクテットを超えないように折り返し、CRLF で終える
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+
+        folded.push(c);
+        width += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> DateTime {
+        DateTime::new(
+            chrono::DateTime::parse_from_rfc3339(value)
+                .unwrap()
+                .with_timezone(&chrono::Utc),
+        )
+    }
+
+    // BEGIN から TZOFFSETTO までの4行ずつ
+    fn observances(lines: &[String]) -> Vec<&[String]> {
+        lines.chunks(5).map(|c| &c[..4]).collect()
+    }
+
+    #[test]
+    fn timezone_component_lists_dst_transitions() {
+        let timezone = Timezone::try_from("America/New_York".to_string())
+            .ok()
+            .unwrap();
+        let lines = timezone_component(&timezone, &datetime("2025-01-06T14:00:00Z"));
+
+        assert_eq!(lines[0], "BEGIN:VTIMEZONE");
+        assert_eq!(lines[1], "TZID:America/New_York");
+        assert_eq!(lines.last().unwrap(), "END:VTIMEZONE");
+
+        let observances = observances(&lines[2..lines.len() - 1]);
+        let expected = [
+            ("STANDARD", "20250106T090000", "-0500", "-0500"),
+            ("DAYLIGHT", "20250309T020000", "-0500", "-0400"),
+            ("STANDARD", "20251102T020000", "-0400", "-0500"),
+        ];
+
+        for (observance, (kind, start, from, to)) in observances.iter().zip(expected) {
+            assert_eq!(
+                *observance,
+                [
+                    format!("BEGIN:{kind}"),
+                    format!("DTSTART:{start}"),
+                    format!("TZOFFSETFROM:{from}"),
+                    format!("TZOFFSETTO:{to}"),
+                ]
+            );
+        }
+        assert!(observances.len() > expected.len());
+    }
+
+    #[test]
+    fn timezone_component_without_dst_has_single_observance() {
+        let timezone = Timezone::try_from("Asia/Tokyo".to_string()).ok().unwrap();
+        let lines = timezone_component(&timezone, &datetime("2025-01-06T00:00:00Z"));
+
+        assert_eq!(
+            lines,
+            [
+                "BEGIN:VTIMEZONE",
+                "TZID:Asia/Tokyo",
+                "BEGIN:STANDARD",
+                "DTSTART:20250106T090000",
+                "TZOFFSETFROM:+0900",
+                "TZOFFSETTO:+0900",
+                "END:STANDARD",
+                "END:VTIMEZONE",
+            ]
+        );
+    }
+}
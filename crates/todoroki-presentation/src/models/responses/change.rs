@@ -0,0 +1,38 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use todoroki_use_case::change::dto::{ChangeEventDto, ChangeTargetDto};
+
+use crate::models::responses::{doit::DoitResponse, label::LabelResponse, todo::TodoResponse};
+
+pub struct ChangeEventResponse {
+    pub id: u64,
+    /// `todo.created` のような、対象と操作の組
+    pub event: String,
+    pub data: ChangeDataResponse,
+}
+
+/// 変更後の Todo / Doit / ラベル。削除では削除される直前のもの
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ChangeDataResponse {
+    Todo(TodoResponse),
+    Doit(DoitResponse),
+    Label(LabelResponse),
+}
+
+impl From<ChangeEventDto> for ChangeEventResponse {
+    fn from(value: ChangeEventDto) -> Self {
+        let (target, data) = match value.target {
+            ChangeTargetDto::Todo(todo) => ("todo", ChangeDataResponse::Todo(todo.into())),
+            ChangeTargetDto::Doit(doit) => ("doit", ChangeDataResponse::Doit(doit.into())),
+            ChangeTargetDto::Label(label) => ("label", ChangeDataResponse::Label(label.into())),
+        };
+
+        Self {
+            id: value.id,
+            event: format!("{}.{}", target, value.action),
+            data,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Serialize;
+use todoroki_use_case::comment::dto::CommentDto;
+use utoipa::ToSchema;
+
+/// parent_id を辿るとスレッドになる
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CommentResponse {
+    pub id: String,
+    pub todo_id: Option<String>,
+    pub doit_id: Option<String>,
+    pub parent_id: Option<String>,
+    /// 削除されたものは本文の代わりに固定の文字列を返す
+    pub body: String,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub deleted_at: Option<String>,
+}
+
+impl From<CommentDto> for CommentResponse {
+    fn from(value: CommentDto) -> Self {
+        Self {
+            id: value.id.as_hyphenated().to_string(),
+            todo_id: value.todo_id.map(|id| id.as_hyphenated().to_string()),
+            doit_id: value.doit_id.map(|id| id.as_hyphenated().to_string()),
+            parent_id: value.parent_id.map(|id| id.as_hyphenated().to_string()),
+            body: value.body,
+            created_by: value.created_by.as_hyphenated().to_string(),
+            created_at: value.created_at.value().to_rfc3339(),
+            updated_at: value.updated_at.value().to_rfc3339(),
+            deleted_at: value.deleted_at.map(|t| t.value().to_rfc3339()),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use serde::Serialize;
+use todoroki_domain::entities;
 use todoroki_use_case::doit::dto::DoitDto;
 use utoipa::ToSchema;
 
@@ -12,6 +13,17 @@ pub struct DoitResponse {
     pub description: String,
     pub alternative_name: Option<String>,
     pub labels: Vec<LabelResponse>,
+    pub vote_count: u32,
+    /// リクエストしたユーザーが投票しているか
+    pub has_voted: bool,
+    /// 受け入れられた場合に紐づく Todo のID
+    pub affects_to: Option<String>,
+    pub status: DoitStatusResponse,
+    /// 保留・却下・重複とした理由。作成者と owner にのみ返す
+    pub status_reason: Option<String>,
+    /// 重複とした場合の元の Doit のID
+    pub duplicate_of: Option<String>,
+    pub reviewed_at: Option<String>,
     pub deadlined_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
@@ -27,6 +39,13 @@ impl From<DoitDto> for DoitResponse {
             description: value.description,
             alternative_name: value.alternative_name,
             labels: value.labels.into_iter().map(LabelResponse::from).collect(),
+            vote_count: value.vote_count,
+            has_voted: value.has_voted,
+            affects_to: value.affects_to.map(|id| id.as_hyphenated().to_string()),
+            status: DoitStatusResponse::from(value.status),
+            status_reason: value.status_reason,
+            duplicate_of: value.duplicate_of.map(|id| id.as_hyphenated().to_string()),
+            reviewed_at: value.reviewed_at.clone().map(|t| t.value().to_rfc3339()),
             deadlined_at: value.deadlined_at.clone().map(|t| t.value().to_rfc3339()),
             created_at: value.created_at.clone().value().to_rfc3339(),
             updated_at: value.updated_at.clone().value().to_rfc3339(),
@@ -34,3 +53,29 @@ impl From<DoitDto> for DoitResponse {
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum DoitStatusResponse {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "on-hold")]
+    OnHold,
+    #[serde(rename = "accepted")]
+    Accepted,
+    #[serde(rename = "rejected")]
+    Rejected,
+    #[serde(rename = "duplicate")]
+    Duplicate,
+}
+
+impl From<entities::doit::DoitStatus> for DoitStatusResponse {
+    fn from(value: entities::doit::DoitStatus) -> Self {
+        match value {
+            entities::doit::DoitStatus::Pending => Self::Pending,
+            entities::doit::DoitStatus::OnHold => Self::OnHold,
+            entities::doit::DoitStatus::Accepted => Self::Accepted,
+            entities::doit::DoitStatus::Rejected => Self::Rejected,
+            entities::doit::DoitStatus::Duplicate => Self::Duplicate,
+        }
+    }
+}
@@ -18,20 +18,56 @@ pub struct ErrorResponse {
 enum ErrorResponseCode {
     #[serde(rename = "todo/not-found")]
     TodoNotFound,
+    #[serde(rename = "todo/status-transition-invalid")]
+    TodoStatusTransitionInvalid,
+    #[serde(rename = "todo/occurrence-not-found")]
+    TodoOccurrenceNotFound,
+    #[serde(rename = "todo/version-not-found")]
+    TodoVersionNotFound,
+    #[serde(rename = "todo/hierarchy-cycle")]
+    TodoHierarchyCycle,
+    #[serde(rename = "todo/dependency-cycle")]
+    TodoDependencyCycle,
+    #[serde(rename = "todo/blocked")]
+    TodoBlocked,
     #[serde(rename = "doit/not-found")]
     DoitNotFound,
+    #[serde(rename = "doit/already-accepted")]
+    DoitAlreadyAccepted,
+    #[serde(rename = "doit/status-transition-invalid")]
+    DoitStatusTransitionInvalid,
+    #[serde(rename = "doit/duplicate-of-self")]
+    DoitDuplicateOfSelf,
+    #[serde(rename = "comment/not-found")]
+    CommentNotFound,
+    #[serde(rename = "comment/parent-invalid")]
+    CommentParentInvalid,
     #[serde(rename = "label/not-found")]
     LabelNotFound,
+    #[serde(rename = "webhook/not-found")]
+    WebhookNotFound,
+    #[serde(rename = "webhook/delivery-not-found")]
+    WebhookDeliveryNotFound,
     #[serde(rename = "permission/denied")]
     PermissionDenied,
     #[serde(rename = "todo/repository-internal-error")]
     TodoRepositoryInternalError,
     #[serde(rename = "doit/repository-internal-error")]
     DoitRepositoryInternalError,
+    #[serde(rename = "comment/repository-internal-error")]
+    CommentRepositoryInternalError,
     #[serde(rename = "label/repository-internal-error")]
     LabelRepositoryInternalError,
     #[serde(rename = "user/repository-internal-error")]
     UserRepositoryInternalError,
+    #[serde(rename = "audit/repository-internal-error")]
+    AuditRepositoryInternalError,
+    #[serde(rename = "webhook/repository-internal-error")]
+    WebhookRepositoryInternalError,
+    #[serde(rename = "webhook/sender-internal-error")]
+    WebhookSenderRepositoryInternalError,
+    #[serde(rename = "change/stream-internal-error")]
+    ChangeStreamRepositoryInternalError,
     #[serde(rename = "user-auth/token-verification-error")]
     UserAuthTokenVerificationError,
     #[serde(rename = "user-auth/not-verified")]
@@ -42,10 +78,26 @@ enum ErrorResponseCode {
     UserAlreadyExists,
     #[serde(rename = "datetime/invalid-format")]
     InvalidDateTimeFormat,
+    #[serde(rename = "recurrence/invalid-rule")]
+    InvalidRecurrenceRule,
     #[serde(rename = "uuid/invalid-format")]
     InvalidUuidFormat,
     #[serde(rename = "color/invalid-format")]
     InvalidColorFormat,
+    #[serde(rename = "cursor/invalid-format")]
+    InvalidCursorFormat,
+    #[serde(rename = "cursor/sort-mismatch")]
+    CursorSortMismatch,
+    #[serde(rename = "occurrence/range-invalid")]
+    OccurrenceRangeInvalid,
+    #[serde(rename = "webhook/invalid-url")]
+    InvalidWebhookUrl,
+    #[serde(rename = "webhook/invalid-event-type")]
+    InvalidWebhookEventType,
+    #[serde(rename = "change/invalid-event-id")]
+    InvalidChangeEventId,
+    #[serde(rename = "websocket/invalid-message")]
+    InvalidWebSocketMessage,
 }
 
 impl From<ErrorCode> for ErrorResponse {
@@ -61,20 +113,50 @@ impl IntoResponse for ErrorResponse {
     fn into_response(self) -> Response {
         let status_code = match self.code {
             ErrorResponseCode::TodoNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::TodoStatusTransitionInvalid => StatusCode::CONFLICT,
+            ErrorResponseCode::TodoOccurrenceNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::TodoVersionNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::TodoHierarchyCycle => StatusCode::CONFLICT,
+            ErrorResponseCode::TodoDependencyCycle => StatusCode::CONFLICT,
+            ErrorResponseCode::TodoBlocked => StatusCode::CONFLICT,
             ErrorResponseCode::DoitNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::DoitAlreadyAccepted => StatusCode::CONFLICT,
+            ErrorResponseCode::DoitStatusTransitionInvalid => StatusCode::CONFLICT,
+            ErrorResponseCode::DoitDuplicateOfSelf => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::CommentNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::CommentParentInvalid => StatusCode::BAD_REQUEST,
             ErrorResponseCode::LabelNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::WebhookNotFound => StatusCode::NOT_FOUND,
+            ErrorResponseCode::WebhookDeliveryNotFound => StatusCode::NOT_FOUND,
             ErrorResponseCode::PermissionDenied => StatusCode::FORBIDDEN,
             ErrorResponseCode::TodoRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorResponseCode::DoitRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorResponseCode::CommentRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorResponseCode::LabelRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
             ErrorResponseCode::UserRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorResponseCode::AuditRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorResponseCode::WebhookRepositoryInternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorResponseCode::WebhookSenderRepositoryInternalError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ErrorResponseCode::ChangeStreamRepositoryInternalError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
             ErrorResponseCode::UserAuthTokenVerificationError => StatusCode::UNAUTHORIZED,
             ErrorResponseCode::UserNotVerified => StatusCode::UNAUTHORIZED,
             ErrorResponseCode::UserNotFound => StatusCode::NOT_FOUND,
             ErrorResponseCode::UserAlreadyExists => StatusCode::CONFLICT,
             ErrorResponseCode::InvalidDateTimeFormat => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidRecurrenceRule => StatusCode::BAD_REQUEST,
             ErrorResponseCode::InvalidUuidFormat => StatusCode::BAD_REQUEST,
             ErrorResponseCode::InvalidColorFormat => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidCursorFormat => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::CursorSortMismatch => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::OccurrenceRangeInvalid => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidWebhookUrl => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidWebhookEventType => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidChangeEventId => StatusCode::BAD_REQUEST,
+            ErrorResponseCode::InvalidWebSocketMessage => StatusCode::BAD_REQUEST,
         };
 
         (status_code, Json(self)).into_response()
@@ -85,20 +167,50 @@ impl From<&ErrorCode> for ErrorResponseCode {
     fn from(value: &ErrorCode) -> Self {
         match value {
             ErrorCode::TodoNotFound(_) => Self::TodoNotFound,
+            ErrorCode::TodoStatusTransitionInvalid(_, _) => Self::TodoStatusTransitionInvalid,
+            ErrorCode::TodoOccurrenceNotFound(_, _) => Self::TodoOccurrenceNotFound,
+            ErrorCode::TodoVersionNotFound(_, _) => Self::TodoVersionNotFound,
+            ErrorCode::TodoHierarchyCycle(_, _) => Self::TodoHierarchyCycle,
+            ErrorCode::TodoDependencyCycle(_, _) => Self::TodoDependencyCycle,
+            ErrorCode::TodoBlocked(_, _) => Self::TodoBlocked,
             ErrorCode::DoitNotFound(_) => Self::DoitNotFound,
+            ErrorCode::DoitAlreadyAccepted(_, _) => Self::DoitAlreadyAccepted,
+            ErrorCode::DoitStatusTransitionInvalid(_, _) => Self::DoitStatusTransitionInvalid,
+            ErrorCode::DoitDuplicateOfSelf(_) => Self::DoitDuplicateOfSelf,
+            ErrorCode::CommentNotFound(_) => Self::CommentNotFound,
+            ErrorCode::CommentParentInvalid(_) => Self::CommentParentInvalid,
             ErrorCode::LabelNotFound(_) => Self::LabelNotFound,
+            ErrorCode::WebhookNotFound(_) => Self::WebhookNotFound,
+            ErrorCode::WebhookDeliveryNotFound(_) => Self::WebhookDeliveryNotFound,
             ErrorCode::PermissionDenied(_) => Self::PermissionDenied,
             ErrorCode::TodoRepositoryInternalError(_) => Self::TodoRepositoryInternalError,
             ErrorCode::DoitRepositoryInternalError(_) => Self::DoitRepositoryInternalError,
+            ErrorCode::CommentRepositoryInternalError(_) => Self::CommentRepositoryInternalError,
             ErrorCode::LabelRepositoryInternalError(_) => Self::LabelRepositoryInternalError,
             ErrorCode::UserRepositoryInternalError(_) => Self::UserRepositoryInternalError,
+            ErrorCode::AuditRepositoryInternalError(_) => Self::AuditRepositoryInternalError,
+            ErrorCode::WebhookRepositoryInternalError(_) => Self::WebhookRepositoryInternalError,
+            ErrorCode::WebhookSenderRepositoryInternalError(_) => {
+                Self::WebhookSenderRepositoryInternalError
+            }
+            ErrorCode::ChangeStreamRepositoryInternalError(_) => {
+                Self::ChangeStreamRepositoryInternalError
+            }
             ErrorCode::UserAuthTokenVerificationError(_) => Self::UserAuthTokenVerificationError,
             ErrorCode::UserNotVerified => Self::UserNotVerified,
             ErrorCode::UserNotFound(_) => Self::UserNotFound,
             ErrorCode::UserAlreadyExistsForEmail(_) => Self::UserAlreadyExists,
             ErrorCode::InvalidDateTimeFormat(_) => Self::InvalidDateTimeFormat,
+            ErrorCode::InvalidRecurrenceRule(_) => Self::InvalidRecurrenceRule,
             ErrorCode::InvalidUuidFormat(_) => Self::InvalidUuidFormat,
             ErrorCode::InvalidColorFormat(_) => Self::InvalidColorFormat,
+            ErrorCode::InvalidCursorFormat(_) => Self::InvalidCursorFormat,
+            ErrorCode::CursorSortMismatch(_, _) => Self::CursorSortMismatch,
+            ErrorCode::OccurrenceRangeInvalid(_, _) => Self::OccurrenceRangeInvalid,
+            ErrorCode::InvalidWebhookUrl(_) => Self::InvalidWebhookUrl,
+            ErrorCode::InvalidWebhookEventType(_) => Self::InvalidWebhookEventType,
+            ErrorCode::InvalidChangeEventId(_) => Self::InvalidChangeEventId,
+            ErrorCode::InvalidWebSocketMessage(_) => Self::InvalidWebSocketMessage,
         }
     }
 }
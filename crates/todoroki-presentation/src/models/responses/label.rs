@@ -19,7 +19,7 @@ impl From<entities::label::Label> for LabelResponse {
             id: value.id().clone().value().as_hyphenated().to_string(),
             name: value.name().clone().value(),
             description: value.description().clone().value(),
-            color: value.color().clone().map(|c| color_into_string(c)),
+            color: value.color().clone().map(color_into_string),
             created_at: value.created_at().clone().value().to_rfc3339(),
             updated_at: value.updated_at().clone().value().to_rfc3339(),
         }
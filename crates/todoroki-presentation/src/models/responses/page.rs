@@ -0,0 +1,25 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use todoroki_domain::repositories::pagination::Page;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PageResponse<T> {
+    pub items: Vec<T>,
+    // 次のページがなければ null
+    pub next_cursor: Option<String>,
+}
+
+impl<T, U> From<Page<U>> for PageResponse<T>
+where
+    T: From<U>,
+{
+    fn from(value: Page<U>) -> Self {
+        let next_cursor = value.next_cursor().as_ref().map(|c| c.encode());
+
+        Self {
+            items: value.into_items().into_iter().map(T::from).collect(),
+            next_cursor,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::Serialize;
+use todoroki_use_case::search::dto::SearchResultDto;
+use utoipa::ToSchema;
+
+use crate::models::responses::{doit::DoitResponse, label::LabelResponse, todo::TodoResponse};
+
+// それぞれ関連度の高い順
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchResponse {
+    pub todos: Vec<TodoResponse>,
+    pub doits: Vec<DoitResponse>,
+    pub labels: Vec<LabelResponse>,
+}
+
+impl From<SearchResultDto> for SearchResponse {
+    fn from(value: SearchResultDto) -> Self {
+        Self {
+            todos: value.todos.into_iter().map(TodoResponse::from).collect(),
+            doits: value.doits.into_iter().map(DoitResponse::from).collect(),
+            labels: value.labels.into_iter().map(LabelResponse::from).collect(),
+        }
+    }
+}
@@ -1,8 +1,14 @@
 use serde::Serialize;
-use todoroki_use_case::todo::dto::TodoDto;
+use todoroki_use_case::todo::dto::{
+    TodoDto, TodoGraphDto, TodoGraphEdgeDto, TodoOccurrenceDto, TodoOccurrenceEntryDto,
+    TodoVersionDto,
+};
 use utoipa::ToSchema;
 
-use todoroki_domain::{entities, value_objects::datetime::DateTime};
+use todoroki_domain::{
+    entities,
+    value_objects::datetime::{DateTime, Timezone},
+};
 
 use crate::models::responses::label::LabelResponse;
 
@@ -13,13 +19,52 @@ pub struct TodoResponse {
     pub is_public: bool,
     pub description: String,
     pub alternative_name: Option<String>,
+    pub parent_id: Option<String>,
     pub labels: Vec<LabelResponse>,
+    /// この todo より先に終わらせる必要のある todo の ID
+    pub blocker_ids: Vec<String>,
+    /// 完了していない blocker があるか
+    pub is_blocked: bool,
     pub schedules: Vec<TodoScheduleResponse>,
+    pub status: TodoStatusResponse,
+    /// 子の todo の進み具合。子がない場合は null
+    pub progress: Option<TodoProgressResponse>,
+    pub occurrences: Vec<TodoOccurrenceResponse>,
     pub deadlined_at: Option<String>,
     pub started_at: Option<String>,
     pub ended_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub deleted_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoGraphResponse {
+    pub nodes: Vec<TodoGraphNodeResponse>,
+    pub edges: Vec<TodoGraphEdgeResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoGraphNodeResponse {
+    pub id: String,
+    pub name: String,
+    pub is_public: bool,
+    pub status: TodoStatusResponse,
+    pub is_blocked: bool,
+}
+
+/// todo_id は blocker_id が完了するまで始められない
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoGraphEdgeResponse {
+    pub todo_id: String,
+    pub blocker_id: String,
+}
+
+// 中止したものと削除したものは数えない
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoProgressResponse {
+    pub completed: u32,
+    pub total: u32,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -27,6 +72,9 @@ pub struct TodoScheduleResponse {
     pub interval: TodoScheduleIntervalResponse,
     pub starts_at: String,
     pub ends_at: String,
+    pub rrule: Option<String>,
+    pub exdates: Vec<String>,
+    pub timezone: String,
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
@@ -39,6 +87,130 @@ pub enum TodoScheduleIntervalResponse {
     Weekly,
     #[serde(rename = "monthly")]
     Monthly,
+    #[serde(rename = "recurrence")]
+    Recurrence,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum TodoStatusResponse {
+    #[serde(rename = "not-started")]
+    NotStarted,
+    #[serde(rename = "on-progress")]
+    OnProgress,
+    #[serde(rename = "paused")]
+    Paused,
+    #[serde(rename = "completed")]
+    Completed,
+    #[serde(rename = "cancelled")]
+    Cancelled,
+}
+
+impl From<entities::todo::TodoStatus> for TodoStatusResponse {
+    fn from(value: entities::todo::TodoStatus) -> Self {
+        match value {
+            entities::todo::TodoStatus::NotStarted => Self::NotStarted,
+            entities::todo::TodoStatus::OnProgress => Self::OnProgress,
+            entities::todo::TodoStatus::Paused => Self::Paused,
+            entities::todo::TodoStatus::Completed => Self::Completed,
+            entities::todo::TodoStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoOccurrenceResponse {
+    pub starts_at: String,
+    pub ends_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl From<TodoOccurrenceDto> for TodoOccurrenceResponse {
+    fn from(value: TodoOccurrenceDto) -> Self {
+        Self {
+            starts_at: value.starts_at.value().to_rfc3339(),
+            ends_at: value.ends_at.value().to_rfc3339(),
+            completed_at: value.completed_at.map(|t| t.value().to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoOccurrenceEntryResponse {
+    pub todo_id: String,
+    pub name: String,
+    pub is_public: bool,
+    pub starts_at: String,
+    pub ends_at: String,
+}
+
+impl From<TodoOccurrenceEntryDto> for TodoOccurrenceEntryResponse {
+    fn from(value: TodoOccurrenceEntryDto) -> Self {
+        Self {
+            todo_id: value.todo_id.as_hyphenated().to_string(),
+            name: value.name,
+            is_public: value.is_public,
+            starts_at: value.starts_at.value().to_rfc3339(),
+            ends_at: value.ends_at.value().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoStatusTransitionResponse {
+    pub from: TodoStatusResponse,
+    pub to: TodoStatusResponse,
+    pub transitioned_at: String,
+}
+
+impl From<entities::todo::TodoStatusTransition> for TodoStatusTransitionResponse {
+    fn from(value: entities::todo::TodoStatusTransition) -> Self {
+        Self {
+            from: TodoStatusResponse::from(*value.from_status()),
+            to: TodoStatusResponse::from(*value.to_status()),
+            transitioned_at: value.transitioned_at().clone().value().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoVersionResponse {
+    pub version: u32,
+    pub name: String,
+    pub description: String,
+    pub is_public: bool,
+    pub alternative_name: Option<String>,
+    pub deadlined_at: Option<String>,
+    pub labels: Vec<LabelResponse>,
+    pub schedules: Vec<TodoScheduleResponse>,
+    /// 履歴を残し始める前の内容から作った版では null
+    pub created_by: Option<String>,
+    pub created_at: String,
+    /// 直前の版からの変更。最初の版では空
+    pub changes: Vec<TodoVersionChangeResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TodoVersionChangeResponse {
+    pub field: TodoVersionFieldResponse,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub enum TodoVersionFieldResponse {
+    #[serde(rename = "name")]
+    Name,
+    #[serde(rename = "description")]
+    Description,
+    /// is_public と alternative_name
+    #[serde(rename = "publishment")]
+    Publishment,
+    #[serde(rename = "deadline")]
+    Deadline,
+    #[serde(rename = "labels")]
+    Labels,
+    #[serde(rename = "schedules")]
+    Schedules,
 }
 
 impl From<entities::todo::TodoSchedule> for TodoScheduleResponse {
@@ -48,21 +220,45 @@ impl From<entities::todo::TodoSchedule> for TodoScheduleResponse {
                 interval: TodoScheduleIntervalResponse::Once,
                 starts_at: s.value().to_rfc3339(),
                 ends_at: e.value().to_rfc3339(),
+                rrule: None,
+                exdates: Vec::new(),
+                timezone: Timezone::utc().name().to_string(),
             },
-            entities::todo::TodoSchedule::Daily(s, e) => Self {
+            entities::todo::TodoSchedule::Daily(s, e, tz) => Self {
                 interval: TodoScheduleIntervalResponse::Daily,
                 starts_at: DateTime::from(s).value().to_rfc3339(),
                 ends_at: DateTime::from(e).value().to_rfc3339(),
+                rrule: None,
+                exdates: Vec::new(),
+                timezone: tz.name().to_string(),
             },
-            entities::todo::TodoSchedule::Weekly(s, e) => Self {
+            entities::todo::TodoSchedule::Weekly(s, e, tz) => Self {
                 interval: TodoScheduleIntervalResponse::Weekly,
                 starts_at: DateTime::from(s).value().to_rfc3339(),
                 ends_at: DateTime::from(e).value().to_rfc3339(),
+                rrule: None,
+                exdates: Vec::new(),
+                timezone: tz.name().to_string(),
             },
-            entities::todo::TodoSchedule::Monthly(s, e) => Self {
+            entities::todo::TodoSchedule::Monthly(s, e, tz) => Self {
                 interval: TodoScheduleIntervalResponse::Monthly,
                 starts_at: DateTime::from(s).value().to_rfc3339(),
                 ends_at: DateTime::from(e).value().to_rfc3339(),
+                rrule: None,
+                exdates: Vec::new(),
+                timezone: tz.name().to_string(),
+            },
+            entities::todo::TodoSchedule::Recurrence(s, e, rule, tz) => Self {
+                interval: TodoScheduleIntervalResponse::Recurrence,
+                starts_at: s.value().to_rfc3339(),
+                ends_at: e.value().to_rfc3339(),
+                rrule: Some(rule.to_string()),
+                exdates: rule
+                    .exdates()
+                    .iter()
+                    .map(|t| t.clone().value().to_rfc3339())
+                    .collect(),
+                timezone: tz.name().to_string(),
             },
         }
     }
@@ -76,18 +272,189 @@ impl From<TodoDto> for TodoResponse {
             is_public: value.is_public,
             description: value.description,
             alternative_name: value.alternative_name,
+            parent_id: value.parent_id.map(|id| id.as_hyphenated().to_string()),
             labels: value.labels.into_iter().map(LabelResponse::from).collect(),
+            blocker_ids: value
+                .blocker_ids
+                .into_iter()
+                .map(|id| id.as_hyphenated().to_string())
+                .collect(),
+            is_blocked: value.is_blocked,
             schedules: value
                 .schedules
                 .clone()
                 .into_iter()
                 .map(TodoScheduleResponse::from)
                 .collect(),
+            status: TodoStatusResponse::from(value.status),
+            progress: value.progress.map(|progress| TodoProgressResponse {
+                completed: *progress.completed(),
+                total: *progress.total(),
+            }),
+            occurrences: value
+                .occurrences
+                .into_iter()
+                .map(TodoOccurrenceResponse::from)
+                .collect(),
             started_at: value.started_at.clone().map(|t| t.value().to_rfc3339()),
             ended_at: value.ended_at.clone().map(|t| t.value().to_rfc3339()),
             deadlined_at: value.deadlined_at.clone().map(|t| t.value().to_rfc3339()),
             created_at: value.created_at.clone().value().to_rfc3339(),
             updated_at: value.updated_at.clone().value().to_rfc3339(),
+            deleted_at: value.deleted_at.clone().map(|t| t.value().to_rfc3339()),
+        }
+    }
+}
+
+impl From<TodoGraphDto> for TodoGraphResponse {
+    fn from(value: TodoGraphDto) -> Self {
+        Self {
+            nodes: value
+                .nodes
+                .into_iter()
+                .map(TodoGraphNodeResponse::from)
+                .collect(),
+            edges: value
+                .edges
+                .into_iter()
+                .map(TodoGraphEdgeResponse::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<TodoDto> for TodoGraphNodeResponse {
+    fn from(value: TodoDto) -> Self {
+        Self {
+            id: value.id.as_hyphenated().to_string(),
+            name: value.name,
+            is_public: value.is_public,
+            status: TodoStatusResponse::from(value.status),
+            is_blocked: value.is_blocked,
+        }
+    }
+}
+
+impl From<TodoGraphEdgeDto> for TodoGraphEdgeResponse {
+    fn from(value: TodoGraphEdgeDto) -> Self {
+        Self {
+            todo_id: value.todo_id.as_hyphenated().to_string(),
+            blocker_id: value.blocker_id.as_hyphenated().to_string(),
+        }
+    }
+}
+
+fn publishment_value(value: entities::todo::TodoPublishment) -> serde_json::Value {
+    match value {
+        entities::todo::TodoPublishment::Public => serde_json::json!({
+            "is_public": true,
+            "alternative_name": null,
+        }),
+        entities::todo::TodoPublishment::Private(alt) => serde_json::json!({
+            "is_public": false,
+            "alternative_name": alt,
+        }),
+    }
+}
+
+fn labels_value(value: Vec<entities::label::Label>) -> serde_json::Value {
+    serde_json::json!(value
+        .into_iter()
+        .map(LabelResponse::from)
+        .collect::<Vec<_>>())
+}
+
+fn schedules_value(value: Vec<entities::todo::TodoSchedule>) -> serde_json::Value {
+    serde_json::json!(value
+        .into_iter()
+        .map(TodoScheduleResponse::from)
+        .collect::<Vec<_>>())
+}
+
+impl From<entities::todo::TodoVersionChange> for TodoVersionChangeResponse {
+    fn from(value: entities::todo::TodoVersionChange) -> Self {
+        use entities::todo::TodoVersionChange;
+
+        let (field, before, after) = match value {
+            TodoVersionChange::Name(b, a) => (
+                TodoVersionFieldResponse::Name,
+                serde_json::json!(b.value()),
+                serde_json::json!(a.value()),
+            ),
+            TodoVersionChange::Description(b, a) => (
+                TodoVersionFieldResponse::Description,
+                serde_json::json!(b.value()),
+                serde_json::json!(a.value()),
+            ),
+            TodoVersionChange::Publishment(b, a) => (
+                TodoVersionFieldResponse::Publishment,
+                publishment_value(b),
+                publishment_value(a),
+            ),
+            TodoVersionChange::Deadline(b, a) => (
+                TodoVersionFieldResponse::Deadline,
+                serde_json::json!(b.map(|t| t.value().to_rfc3339())),
+                serde_json::json!(a.map(|t| t.value().to_rfc3339())),
+            ),
+            TodoVersionChange::Labels(b, a) => (
+                TodoVersionFieldResponse::Labels,
+                labels_value(b),
+                labels_value(a),
+            ),
+            TodoVersionChange::Schedules(b, a) => (
+                TodoVersionFieldResponse::Schedules,
+                schedules_value(b),
+                schedules_value(a),
+            ),
+        };
+
+        Self {
+            field,
+            before,
+            after,
+        }
+    }
+}
+
+impl From<TodoVersionDto> for TodoVersionResponse {
+    fn from(value: TodoVersionDto) -> Self {
+        let version = value.version;
+
+        Self {
+            version: *version.version(),
+            name: version.name().clone().value(),
+            description: version.description().clone().value(),
+            is_public: matches!(version.is_public(), entities::todo::TodoPublishment::Public),
+            alternative_name: match version.is_public() {
+                entities::todo::TodoPublishment::Public => None,
+                entities::todo::TodoPublishment::Private(alt) => alt.clone(),
+            },
+            deadlined_at: version
+                .deadlined_at()
+                .clone()
+                .map(|t| t.value().to_rfc3339()),
+            labels: version
+                .labels()
+                .clone()
+                .into_iter()
+                .map(LabelResponse::from)
+                .collect(),
+            schedules: version
+                .schedules()
+                .clone()
+                .into_iter()
+                .map(TodoScheduleResponse::from)
+                .collect(),
+            created_by: version
+                .created_by()
+                .clone()
+                .map(|id| id.value().as_hyphenated().to_string()),
+            created_at: version.created_at().clone().value().to_rfc3339(),
+            changes: value
+                .changes
+                .into_iter()
+                .map(TodoVersionChangeResponse::from)
+                .collect(),
         }
     }
 }
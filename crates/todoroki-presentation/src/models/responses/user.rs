@@ -8,6 +8,7 @@ pub struct UserResponse {
     pub id: String,
     pub name: String,
     pub role: UserRoleResponse,
+    pub timezone: String,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -34,9 +35,28 @@ impl From<entities::user::User> for UserResponse {
         Self {
             id: value.id().clone().value().as_hyphenated().to_string(),
             name: value.name().clone().value(),
-            role: UserRoleResponse::from(value.role().clone()),
+            role: UserRoleResponse::from(*value.role()),
+            timezone: value.timezone().name().to_string(),
             created_at: value.created_at().clone().value().to_rfc3339(),
             updated_at: value.updated_at().clone().value().to_rfc3339(),
         }
     }
 }
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserCalendarTokenResponse {
+    pub token: String,
+    /// カレンダーアプリに登録する購読 URL のパス
+    pub path: String,
+}
+
+impl From<entities::user::UserCalendarToken> for UserCalendarTokenResponse {
+    fn from(value: entities::user::UserCalendarToken) -> Self {
+        let token = value.value();
+
+        Self {
+            path: format!("/calendar.ics?token={token}"),
+            token,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use todoroki_domain::entities::webhook::{
+    Webhook, WebhookDelivery, WebhookDeliveryAttempt, WebhookDeliveryStatus,
+};
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub created_by: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(value: Webhook) -> Self {
+        Self {
+            id: value.id().clone().value().as_hyphenated().to_string(),
+            url: value.url().clone().value(),
+            event_types: value.event_types().iter().map(|t| t.to_string()).collect(),
+            created_by: value
+                .created_by()
+                .clone()
+                .value()
+                .as_hyphenated()
+                .to_string(),
+            created_at: value.created_at().clone().value().to_rfc3339(),
+            updated_at: value.updated_at().clone().value().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookCreatedResponse {
+    pub id: String,
+    /// payload の署名に使う鍵。登録時にしか返さない
+    ///
+    /// `X-Todoroki-Signature` ヘッダーには body の HMAC-SHA256 が `sha256=<hex>` の形式で入る
+    pub secret: String,
+}
+
+impl From<Webhook> for WebhookCreatedResponse {
+    fn from(value: Webhook) -> Self {
+        Self {
+            id: value.id().clone().value().as_hyphenated().to_string(),
+            secret: value.secret().clone().value(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub webhook_id: String,
+    pub event_type: String,
+    /// 送信する body
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatusResponse,
+    pub attempts: u32,
+    /// 次に送信する日時。送信を終えた配送では null
+    pub next_attempt_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+pub enum WebhookDeliveryStatusResponse {
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "succeeded")]
+    Succeeded,
+    #[serde(rename = "failed")]
+    Failed,
+}
+
+impl From<WebhookDeliveryStatus> for WebhookDeliveryStatusResponse {
+    fn from(value: WebhookDeliveryStatus) -> Self {
+        match value {
+            WebhookDeliveryStatus::Pending => Self::Pending,
+            WebhookDeliveryStatus::Succeeded => Self::Succeeded,
+            WebhookDeliveryStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(value: WebhookDelivery) -> Self {
+        let next_attempt_at = match value.status() {
+            WebhookDeliveryStatus::Pending => {
+                Some(value.next_attempt_at().clone().value().to_rfc3339())
+            }
+            WebhookDeliveryStatus::Succeeded | WebhookDeliveryStatus::Failed => None,
+        };
+
+        Self {
+            id: value.id().clone().value().as_hyphenated().to_string(),
+            webhook_id: value
+                .webhook_id()
+                .clone()
+                .value()
+                .as_hyphenated()
+                .to_string(),
+            event_type: value.event().event_type().to_string(),
+            payload: value.event().payload(),
+            status: WebhookDeliveryStatusResponse::from(*value.status()),
+            attempts: *value.attempts(),
+            next_attempt_at,
+            created_at: value.created_at().clone().value().to_rfc3339(),
+            updated_at: value.updated_at().clone().value().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WebhookDeliveryAttemptResponse {
+    pub attempt: u32,
+    /// 応答がなかった場合は null
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub attempted_at: String,
+}
+
+impl From<WebhookDeliveryAttempt> for WebhookDeliveryAttemptResponse {
+    fn from(value: WebhookDeliveryAttempt) -> Self {
+        Self {
+            attempt: *value.attempt(),
+            status_code: *value.status_code(),
+            error: value.error().clone(),
+            attempted_at: value.attempted_at().clone().value().to_rfc3339(),
+        }
+    }
+}
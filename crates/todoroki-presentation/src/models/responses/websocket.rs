@@ -0,0 +1,55 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use todoroki_domain::value_objects::datetime::DateTime;
+
+use crate::models::responses::{
+    change::{ChangeDataResponse, ChangeEventResponse},
+    error::ErrorResponse,
+};
+
+/// サーバーから送るメッセージ
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum WebSocketResponse {
+    /// 接続した時と認証し直した時
+    Authenticated {
+        request_id: Option<String>,
+        /// トークンの有効期限。ログインしていなければ null
+        expires_at: Option<String>,
+    },
+    /// トークンの有効期限が切れた。認証し直すまで変更は届かず、しばらくすると切断する
+    AuthExpired,
+    /// 購読している範囲の変更
+    Event {
+        id: u64,
+        /// `todo.created` のような、対象と操作の組
+        event: String,
+        data: Box<ChangeDataResponse>,
+    },
+    /// メッセージを受け付けた
+    Ack { request_id: Option<String> },
+    Error {
+        request_id: Option<String>,
+        error: ErrorResponse,
+    },
+}
+
+impl WebSocketResponse {
+    pub fn authenticated(request_id: Option<String>, expires_at: Option<&DateTime>) -> Self {
+        Self::Authenticated {
+            request_id,
+            expires_at: expires_at.map(|t| t.clone().value().to_rfc3339()),
+        }
+    }
+}
+
+impl From<ChangeEventResponse> for WebSocketResponse {
+    fn from(value: ChangeEventResponse) -> Self {
+        Self::Event {
+            id: value.id,
+            event: value.event,
+            data: Box::new(value.data),
+        }
+    }
+}
@@ -6,7 +6,9 @@ use todoroki_infrastructure::shared::{DefaultRepositories, DefaultRepositoriesEr
 
 use thiserror::Error;
 use todoroki_use_case::{
-    doit::DoitUseCase, label::LabelUseCase, todo::TodoUseCase, user::UserUseCase,
+    audit::AuditUseCase, change::ChangeUseCase, comment::CommentUseCase, doit::DoitUseCase,
+    label::LabelUseCase, search::SearchUseCase, todo::TodoUseCase, user::UserUseCase,
+    webhook::WebhookUseCase,
 };
 
 pub struct Modules<R: Repositories> {
@@ -17,6 +19,11 @@ pub struct Modules<R: Repositories> {
     doit_use_case: DoitUseCase<R>,
     label_use_case: LabelUseCase<R>,
     user_use_case: UserUseCase<R>,
+    search_use_case: SearchUseCase<R>,
+    comment_use_case: CommentUseCase<R>,
+    audit_use_case: AuditUseCase<R>,
+    webhook_use_case: WebhookUseCase<R>,
+    change_use_case: ChangeUseCase<R>,
 }
 
 impl<R: Repositories> Modules<R> {
@@ -43,6 +50,26 @@ impl<R: Repositories> Modules<R> {
     pub fn user_use_case(&self) -> &UserUseCase<R> {
         &self.user_use_case
     }
+
+    pub fn search_use_case(&self) -> &SearchUseCase<R> {
+        &self.search_use_case
+    }
+
+    pub fn comment_use_case(&self) -> &CommentUseCase<R> {
+        &self.comment_use_case
+    }
+
+    pub fn audit_use_case(&self) -> &AuditUseCase<R> {
+        &self.audit_use_case
+    }
+
+    pub fn webhook_use_case(&self) -> &WebhookUseCase<R> {
+        &self.webhook_use_case
+    }
+
+    pub fn change_use_case(&self) -> &ChangeUseCase<R> {
+        &self.change_use_case
+    }
 }
 
 #[derive(Debug, Error)]
@@ -65,5 +92,10 @@ pub async fn default(config: Config) -> Result<Modules<DefaultRepositories>, Def
         doit_use_case: DoitUseCase::new(Arc::clone(&repositories)),
         label_use_case: LabelUseCase::new(Arc::clone(&repositories)),
         user_use_case: UserUseCase::new(Arc::clone(&repositories)),
+        search_use_case: SearchUseCase::new(Arc::clone(&repositories)),
+        comment_use_case: CommentUseCase::new(Arc::clone(&repositories)),
+        audit_use_case: AuditUseCase::new(Arc::clone(&repositories)),
+        webhook_use_case: WebhookUseCase::new(Arc::clone(&repositories)),
+        change_use_case: ChangeUseCase::new(Arc::clone(&repositories)),
     })
 }
@@ -3,21 +3,34 @@ pub mod user;
 pub mod health;
 pub mod label;
 pub mod doit;
+pub mod search;
+pub mod calendar;
+pub mod comment;
+pub mod audit;
+pub mod change;
+pub mod webhook;
+pub mod websocket;
 
 use crate::{middlewares, modules::Modules};
 use todoroki_infrastructure::shared::DefaultRepositories;
 
-use axum::{http::{header, Method}, routing::{get, patch, post}, Router};
+use axum::{http::{header, Method}, routing::{delete, get, patch, post, put}, Router};
 use tracing::Level;
 use std::sync::Arc;
-use tower_http::{cors::{Any, CorsLayer}, trace::{DefaultMakeSpan, DefaultOnRequest, DefaultOnResponse, TraceLayer}};
+use tower_http::{cors::{Any, CorsLayer}, trace::{DefaultOnRequest, DefaultOnResponse, TraceLayer}};
 use utoipa::OpenApi;
 
 pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     // todo の作成/更新操作は常に認証を要する
     let todo_auth_routes = Router::new()
         .route("/", post(todo::handle_post))
-        .route("/{todo_id}", patch(todo::handle_patch))
+        .route("/trash", get(todo::handle_get_trash))
+        .route("/import/ics", post(calendar::handle_import_ics))
+        .route("/{todo_id}", patch(todo::handle_patch).delete(todo::handle_delete))
+        .route("/{todo_id}/restore", post(todo::handle_restore))
+        .route("/{todo_id}/revert/{version}", post(todo::handle_revert))
+        .route("/{todo_id}/comments", post(comment::handle_post_todo_comment))
+        .route("/{todo_id}/occurrences/{occurrence_start}/completion", post(todo::handle_complete_occurrence).delete(todo::handle_uncomplete_occurrence))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::jwt_auth,
@@ -26,6 +39,14 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     // todo の取得は必ずしも認証しなくても良い
     let todo_opt_auth_routes = Router::new()
         .route("/", get(todo::handle_get))
+        .route("/occurrences", get(todo::handle_get_occurrences))
+        .route("/graph", get(todo::handle_get_graph))
+        .route("/{todo_id}", get(todo::handle_get_by_id))
+        .route("/{todo_id}/children", get(todo::handle_get_children))
+        .route("/{todo_id}/doits", get(doit::handle_get_by_todo))
+        .route("/{todo_id}/comments", get(comment::handle_get_todo_comments))
+        .route("/{todo_id}/transitions", get(todo::handle_get_transitions))
+        .route("/{todo_id}/history", get(todo::handle_get_history))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::optional_jwt_auth,
@@ -39,6 +60,13 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     let doit_auth_routes = Router::new()
         .route("/", post(doit::handle_post))
         .route("/{doit_id}", patch(doit::handle_patch))
+        .route("/{doit_id}/accept", post(doit::handle_accept))
+        .route("/{doit_id}/hold", post(doit::handle_hold))
+        .route("/{doit_id}/reject", post(doit::handle_reject))
+        .route("/{doit_id}/duplicate", post(doit::handle_duplicate))
+        .route("/{doit_id}/reopen", post(doit::handle_reopen))
+        .route("/{doit_id}/vote", put(doit::handle_put_vote).delete(doit::handle_delete_vote))
+        .route("/{doit_id}/comments", post(comment::handle_post_doit_comment))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::jwt_auth,
@@ -47,6 +75,8 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     // doit の取得は必ずしも認証しなくても良い
     let doit_opt_auth_routes = Router::new()
         .route("/", get(doit::handle_get))
+        .route("/{doit_id}", get(doit::handle_get_by_id))
+        .route("/{doit_id}/comments", get(comment::handle_get_doit_comments))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::optional_jwt_auth,
@@ -56,6 +86,17 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
         .nest("/doits", doit_opt_auth_routes)
         .nest("/doits", doit_auth_routes);
     
+    // コメントの編集/削除は常に認証を要する
+    let comment_auth_routes = Router::new()
+        .route("/{comment_id}", patch(comment::handle_patch).delete(comment::handle_delete))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::jwt_auth,
+        ));
+    
+    let comment_routes = Router::new()
+        .nest("/comments", comment_auth_routes);
+    
     // label の作成/更新操作は常に認証を要する
     let label_auth_routes = Router::new()
         .route("/", post(label::handle_post))
@@ -67,6 +108,7 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     // label の取得は必ずしも認証しなくても良い
     let label_opt_auth_routes = Router::new()
         .route("/", get(label::handle_get))
+        .route("/{label_id}", get(label::handle_get_by_id))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::optional_jwt_auth,
@@ -80,6 +122,9 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     let user_auth_routes = Router::new()
         .route("/", post(user::handle_post))
         .route("/me", get(user::handle_get_me))
+        .route("/me/calendar-token", post(user::handle_post_calendar_token).delete(user::handle_delete_calendar_token))
+        .route("/me/timezone", patch(user::handle_patch_timezone))
+        .route("/{user_id}", get(user::handle_get_by_id))
         .route_layer(axum::middleware::from_fn_with_state(
             Arc::clone(&modules),
             middlewares::auth::jwt_auth,
@@ -88,23 +133,80 @@ pub fn router(modules: Arc<Modules<DefaultRepositories>>) -> Router {
     let user_routes = Router::new()
         .nest("/users", user_auth_routes);
     
+    // 検索は必ずしも認証しなくても良い
+    let search_routes = Router::new()
+        .route("/search", get(search::handle_search))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::optional_jwt_auth,
+        ));
+    
+    // 監査ログは常に認証を要する
+    let audit_routes = Router::new()
+        .route("/audit", get(audit::handle_get))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::jwt_auth,
+        ));
+    
+    // 変更の購読は必ずしも認証しなくても良い
+    let change_routes = Router::new()
+        .route("/events", get(change::handle_get_events))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::optional_jwt_auth,
+        ));
+    
+    // ブラウザは WebSocket の接続時に Authorization ヘッダを送れないので、ハンドラでクエリのトークンも見て認証する
+    let websocket_routes = Router::new()
+        .route("/ws", get(websocket::handle_get_ws));
+    
+    // webhook の管理は常に認証を要する
+    let webhook_auth_routes = Router::new()
+        .route("/", get(webhook::handle_get).post(webhook::handle_post))
+        .route("/{webhook_id}", delete(webhook::handle_delete))
+        .route("/{webhook_id}/deliveries", get(webhook::handle_get_deliveries))
+        .route("/{webhook_id}/deliveries/{delivery_id}/attempts", get(webhook::handle_get_attempts))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::jwt_auth,
+        ));
+    
+    let webhook_routes = Router::new()
+        .nest("/webhooks", webhook_auth_routes);
+    
+    // カレンダーはトークン付きの URL でも取得できる
+    let calendar_routes = Router::new()
+        .route("/calendar.ics", get(calendar::handle_get_calendar))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&modules),
+            middlewares::auth::calendar_token_auth,
+        ));
+    
     Router::new()
         .route("/health", get(health::handle_health))
         .merge(todo_routes)
         .merge(doit_routes)
+        .merge(comment_routes)
         .merge(label_routes)
         .merge(user_routes)
+        .merge(search_routes)
+        .merge(calendar_routes)
+        .merge(audit_routes)
+        .merge(webhook_routes)
+        .merge(change_routes)
+        .merge(websocket_routes)
         .with_state(modules)
         .layer(
             TraceLayer::new_for_http()
-                .make_span_with(DefaultMakeSpan::new().level(Level::INFO))
+                .make_span_with(middlewares::trace::make_span)
                 .on_request(DefaultOnRequest::new().level(Level::INFO))
                 .on_response(DefaultOnResponse::new().level(Level::INFO)),
         )
         .layer(
             CorsLayer::new()
-                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
-                .allow_methods([Method::GET, Method::PATCH, Method::POST])
+                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE, header::HeaderName::from_static("last-event-id")])
+                .allow_methods([Method::GET, Method::PATCH, Method::POST, Method::PUT, Method::DELETE])
                 .allow_origin(Any),
         )
 }
@@ -117,21 +219,72 @@ use crate::routes;
         (name = "health", description = "APIの死活チェック"),
         (name = "todo", description = "Todo関連の操作"),
         (name = "doit", description = "Do it! 関連の操作"),
+        (name = "comment", description = "Todo と Do it! へのコメント"),
         (name = "label", description = "ラベル関連の操作"),
         (name = "user", description = "ユーザー関連の操作"),
+        (name = "search", description = "全文検索"),
+        (name = "calendar", description = "iCalendar 形式での購読と取り込み"),
+        (name = "audit", description = "変更の監査ログ"),
+        (name = "webhook", description = "署名付きの Webhook の配送"),
+        (name = "change", description = "変更の購読 (Server-Sent Events)"),
+        (name = "websocket", description = "変更の購読と簡単な操作 (WebSocket)"),
     ), 
     paths(
         routes::health::handle_health,
         routes::todo::handle_get,
+        routes::todo::handle_get_occurrences,
+        routes::todo::handle_get_graph,
+        routes::todo::handle_get_by_id,
+        routes::todo::handle_get_children,
+        routes::todo::handle_get_transitions,
         routes::todo::handle_post,
         routes::todo::handle_patch,
+        routes::todo::handle_delete,
+        routes::todo::handle_get_trash,
+        routes::todo::handle_restore,
+        routes::todo::handle_get_history,
+        routes::todo::handle_revert,
+        routes::todo::handle_complete_occurrence,
+        routes::todo::handle_uncomplete_occurrence,
         routes::doit::handle_get,
+        routes::doit::handle_get_by_id,
         routes::doit::handle_post,
         routes::doit::handle_patch,
+        routes::doit::handle_accept,
+        routes::doit::handle_hold,
+        routes::doit::handle_reject,
+        routes::doit::handle_duplicate,
+        routes::doit::handle_reopen,
+        routes::doit::handle_put_vote,
+        routes::doit::handle_delete_vote,
+        routes::comment::handle_get_todo_comments,
+        routes::comment::handle_post_todo_comment,
+        routes::comment::handle_get_doit_comments,
+        routes::comment::handle_post_doit_comment,
+        routes::comment::handle_patch,
+        routes::comment::handle_delete,
+        routes::doit::handle_get_by_todo,
         routes::label::handle_get,
+        routes::label::handle_get_by_id,
         routes::label::handle_post,
         routes::user::handle_post,
         routes::user::handle_get_me,
-    )
+        routes::user::handle_get_by_id,
+        routes::user::handle_post_calendar_token,
+        routes::user::handle_delete_calendar_token,
+        routes::user::handle_patch_timezone,
+        routes::search::handle_search,
+        routes::calendar::handle_get_calendar,
+        routes::calendar::handle_import_ics,
+        routes::audit::handle_get,
+        routes::webhook::handle_get,
+        routes::webhook::handle_post,
+        routes::webhook::handle_delete,
+        routes::webhook::handle_get_deliveries,
+        routes::webhook::handle_get_attempts,
+        routes::change::handle_get_events,
+        routes::websocket::handle_get_ws,
+    ),
+    components(schemas(crate::models::requests::websocket::WebSocketRequest)),
 )]
 pub struct ApiDocs;
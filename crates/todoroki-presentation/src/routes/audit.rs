@@ -0,0 +1,50 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::sync::Arc;
+use todoroki_domain::repositories::audit::AuditFilter;
+
+use crate::{
+    context::Context,
+    models::{
+        requests,
+        responses::{self, error::ErrorResponse, page::PageResponse},
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+#[utoipa::path(
+    get,
+    path = "/audit",
+    operation_id = "getAuditEvents",
+    tag = "audit",
+    params(requests::audit::AuditListQuery),
+    responses(
+        (status = 200, description = "OK", body = PageResponse<responses::audit::AuditEventResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 422, description = "Unprocessable Entity", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::audit::AuditListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let page = raw_query.try_into_page_request()?;
+    let filter = AuditFilter::try_from(raw_query)?;
+
+    let res = modules.audit_use_case().list(filter, page, &ctx).await;
+
+    match res {
+        Ok(events) => Ok(Json(
+            PageResponse::<responses::audit::AuditEventResponse>::from(events),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,90 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::sync::Arc;
+use todoroki_domain::repositories::{pagination::PageRequest, todo::TodoFilter};
+
+use crate::{
+    context::Context,
+    models::{
+        requests,
+        responses::{
+            calendar::{CalendarResponse, TodoImportReportResponse},
+            error::ErrorResponse,
+        },
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+use todoroki_use_case::shared::ContextProvider;
+
+#[utoipa::path(
+    get,
+    path = "/calendar.ics",
+    operation_id = "getCalendar",
+    tag = "calendar",
+    params(
+        ("token" = Option<String>, Query, description = "`POST /users/me/calendar-token` で発行したトークン"),
+    ),
+    responses(
+        (status = 200, description = "OK", body = String, content_type = "text/calendar"),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_calendar(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules
+        .todo_use_case()
+        .list(TodoFilter::default(), PageRequest::default(), &ctx)
+        .await;
+
+    match res {
+        Ok(todos) => Ok(CalendarResponse::from(todos.into_items())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/import/ics",
+    operation_id = "importTodosFromIcs",
+    tag = "calendar",
+    params(requests::calendar::TodoImportQuery),
+    request_body(content = String, content_type = "text/calendar"),
+    responses(
+        (status = 200, description = "OK", body = TodoImportReportResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_import_ics(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::calendar::TodoImportQuery>,
+    body: String,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let entries =
+        requests::calendar::todo_import_entries_from_ics(&body, &ctx.client().client().timezone());
+
+    let res = modules
+        .todo_use_case()
+        .import(
+            entries,
+            raw_query.create_missing_labels.unwrap_or(false),
+            &ctx,
+        )
+        .await;
+
+    match res {
+        Ok(results) => Ok(Json(TodoImportReportResponse::from(results))),
+        Err(e) => Err(e.into()),
+    }
+}
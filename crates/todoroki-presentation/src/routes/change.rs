@@ -0,0 +1,73 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    Extension,
+};
+use futures_util::stream;
+use std::sync::Arc;
+use todoroki_domain::{entities::change::ChangeEventId, value_objects::error::ErrorCode};
+
+use crate::{
+    context::Context,
+    models::responses::{
+        change::{ChangeDataResponse, ChangeEventResponse},
+        error::ErrorResponse,
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+#[utoipa::path(
+    get,
+    path = "/events",
+    operation_id = "getEvents",
+    tag = "change",
+    params(
+        ("Last-Event-ID" = Option<String>, Header, description = "最後に受け取ったイベントの ID。これより後の変更から再開する"),
+    ),
+    responses(
+        (status = 200, description = "Todo / Doit / ラベルが変更されるたびに `todo.created` のような名前のイベントを送る", content_type = "text/event-stream", body = ChangeDataResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_events(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .map(|v| {
+            v.to_str()
+                .map_err(|_| ErrorCode::InvalidChangeEventId(format!("{v:?}")))
+                .and_then(|s| ChangeEventId::try_from(s.to_string()))
+        })
+        .transpose()?;
+
+    let listener = modules
+        .change_use_case()
+        .subscribe(last_event_id, ctx)
+        .await?;
+
+    let events = stream::unfold(listener, |mut listener| async move {
+        let res = ChangeEventResponse::from(listener.next().await?);
+
+        let event = Event::default()
+            .id(res.id.to_string())
+            .event(res.event)
+            .json_data(res.data);
+
+        Some((event, listener))
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
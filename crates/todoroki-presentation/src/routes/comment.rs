@@ -0,0 +1,247 @@
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::sync::Arc;
+use todoroki_domain::{
+    entities::{
+        client::Client,
+        comment::{CommentBody, CommentId, CommentTarget},
+        doit::DoitId,
+        todo::TodoId,
+    },
+    value_objects::error::ErrorCode,
+};
+use todoroki_use_case::shared::ContextProvider;
+
+use crate::{
+    context::Context,
+    models::{
+        requests,
+        responses::{self, error::ErrorResponse, success::SuccessResponse},
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}/comments",
+    operation_id = "getTodoComments",
+    tag = "comment",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::comment::CommentResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_todo_comments(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let target = CommentTarget::Todo(TodoId::try_from(raw_id)?);
+
+    let res = modules
+        .comment_use_case()
+        .list_by_target(target, &ctx)
+        .await;
+
+    match res {
+        Ok(comments) => Ok(Json(
+            comments
+                .into_iter()
+                .map(responses::comment::CommentResponse::from)
+                .collect::<Vec<responses::comment::CommentResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/{todo_id}/comments",
+    operation_id = "postTodoComment",
+    tag = "comment",
+    request_body = requests::comment::CommentRequest,
+    responses(
+        (status = 201, description = "Created", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_post_todo_comment(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_comment): Json<requests::comment::CommentRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let target = CommentTarget::Todo(TodoId::try_from(raw_id)?);
+
+    let user_id = if let Client::User(u) = ctx.client().client() {
+        u.id().clone()
+    } else {
+        return Err(ErrorResponse::from(ErrorCode::UserNotVerified));
+    };
+
+    let comment = raw_comment.try_into_with_target_and_created_by(target, user_id)?;
+
+    let res = modules.comment_use_case().create(comment, &ctx).await;
+
+    match res {
+        Ok(id) => Ok(SuccessResponse::new(format!(
+            "comment/created; id={}",
+            id.value().as_hyphenated()
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/doits/{doit_id}/comments",
+    operation_id = "getDoitComments",
+    tag = "comment",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::comment::CommentResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_doit_comments(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let target = CommentTarget::Doit(DoitId::try_from(raw_id)?);
+
+    let res = modules
+        .comment_use_case()
+        .list_by_target(target, &ctx)
+        .await;
+
+    match res {
+        Ok(comments) => Ok(Json(
+            comments
+                .into_iter()
+                .map(responses::comment::CommentResponse::from)
+                .collect::<Vec<responses::comment::CommentResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/comments",
+    operation_id = "postDoitComment",
+    tag = "comment",
+    request_body = requests::comment::CommentRequest,
+    responses(
+        (status = 201, description = "Created", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_post_doit_comment(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_comment): Json<requests::comment::CommentRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let target = CommentTarget::Doit(DoitId::try_from(raw_id)?);
+
+    let user_id = if let Client::User(u) = ctx.client().client() {
+        u.id().clone()
+    } else {
+        return Err(ErrorResponse::from(ErrorCode::UserNotVerified));
+    };
+
+    let comment = raw_comment.try_into_with_target_and_created_by(target, user_id)?;
+
+    let res = modules.comment_use_case().create(comment, &ctx).await;
+
+    match res {
+        Ok(id) => Ok(SuccessResponse::new(format!(
+            "comment/created; id={}",
+            id.value().as_hyphenated()
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/comments/{comment_id}",
+    operation_id = "patchCommentById",
+    tag = "comment",
+    request_body = requests::comment::CommentUpdateRequest,
+    responses(
+        (status = 201, description = "Updated", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_patch(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_req): Json<requests::comment::CommentUpdateRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = CommentId::try_from(raw_id)?;
+
+    let res = modules
+        .comment_use_case()
+        .update(id, CommentBody::new(raw_req.body), &ctx)
+        .await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("comment/updated".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/comments/{comment_id}",
+    operation_id = "deleteCommentById",
+    tag = "comment",
+    responses(
+        (status = 201, description = "Deleted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_delete(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = CommentId::try_from(raw_id)?;
+
+    let res = modules.comment_use_case().delete(id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("comment/deleted".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
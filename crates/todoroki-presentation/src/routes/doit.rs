@@ -1,11 +1,16 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
     Extension, Json,
 };
 use std::sync::Arc;
 use todoroki_domain::{
-    entities::{client::Client, doit::DoitId},
+    entities::{
+        client::Client,
+        doit::{DoitId, DoitReviewCommand},
+        todo::TodoId,
+    },
+    repositories::{doit::DoitFilter, pagination::PageRequest},
     value_objects::error::ErrorCode,
 };
 use todoroki_use_case::shared::ContextProvider;
@@ -14,7 +19,7 @@ use crate::{
     context::Context,
     models::{
         requests,
-        responses::{self, error::ErrorResponse, success::SuccessResponse},
+        responses::{self, error::ErrorResponse, page::PageResponse, success::SuccessResponse},
     },
     modules::Modules,
 };
@@ -25,8 +30,9 @@ use todoroki_infrastructure::shared::DefaultRepositories;
     path = "/doits",
     operation_id = "getDoits",
     tag = "doit",
+    params(requests::doit::DoitListQuery),
     responses(
-        (status = 200, description = "OK", body = Vec<responses::doit::DoitResponse>),
+        (status = 200, description = "OK", body = PageResponse<responses::doit::DoitResponse>),
         (status = 400, description = "Bad Request", body = ErrorResponse),
         (status = 422, description = "Unprocessable Entity", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse),
@@ -36,16 +42,45 @@ use todoroki_infrastructure::shared::DefaultRepositories;
 pub async fn handle_get(
     State(modules): State<Arc<Modules<DefaultRepositories>>>,
     Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::doit::DoitListQuery>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let res = modules.doit_use_case().list(&ctx).await;
+    let page = raw_query.try_into_page_request()?;
+    let filter = DoitFilter::try_from(raw_query)?;
+
+    let res = modules.doit_use_case().list(filter, page, &ctx).await;
 
     match res {
-        Ok(doits) => Ok(Json(
-            doits
-                .into_iter()
-                .map(responses::doit::DoitResponse::from)
-                .collect::<Vec<responses::doit::DoitResponse>>(),
-        )),
+        Ok(doits) => Ok(Json(PageResponse::<responses::doit::DoitResponse>::from(
+            doits,
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/doits/{doit_id}",
+    operation_id = "getDoitById",
+    tag = "doit",
+    responses(
+        (status = 200, description = "OK", body = responses::doit::DoitResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_by_id(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+
+    let res = modules.doit_use_case().get_by_id(id, &ctx).await;
+
+    match res {
+        Ok(doit) => Ok(Json(responses::doit::DoitResponse::from(doit))),
         Err(e) => Err(e.into()),
     }
 }
@@ -70,9 +105,9 @@ pub async fn handle_post(
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let labels = modules
         .label_use_case()
-        .list(&ctx)
-        .await
-        .map_err(ErrorCode::from)?;
+        .list(PageRequest::default(), &ctx)
+        .await?
+        .into_items();
 
     let user_id = if let Client::User(u) = ctx.client().client() {
         u.id().clone()
@@ -118,7 +153,276 @@ pub async fn handle_patch(
     let res = modules.doit_use_case().update(cmd, &ctx).await;
 
     match res {
-        Ok(()) => Ok(SuccessResponse::new(format!("doit/updated"))),
+        Ok(()) => Ok(SuccessResponse::new("doit/updated".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/accept",
+    operation_id = "postDoitAccept",
+    tag = "doit",
+    request_body = requests::doit::DoitAcceptRequest,
+    responses(
+        (status = 201, description = "Accepted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_accept(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_req): Json<requests::doit::DoitAcceptRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+    let todo_id = raw_req.try_into_todo_id()?;
+
+    let res = modules.doit_use_case().accept(id, todo_id, &ctx).await;
+
+    match res {
+        Ok(todo_id) => Ok(SuccessResponse::new(format!(
+            "doit/accepted; todo_id={}",
+            todo_id.value().as_hyphenated()
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}/doits",
+    operation_id = "getTodoDoits",
+    tag = "doit",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::doit::DoitResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_by_todo(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let todo_id = TodoId::try_from(raw_id)?;
+
+    let res = modules.doit_use_case().list_by_todo(todo_id, &ctx).await;
+
+    match res {
+        Ok(doits) => Ok(Json(
+            doits
+                .into_iter()
+                .map(responses::doit::DoitResponse::from)
+                .collect::<Vec<responses::doit::DoitResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/hold",
+    operation_id = "postDoitHold",
+    tag = "doit",
+    request_body = requests::doit::DoitHoldRequest,
+    responses(
+        (status = 201, description = "Reviewed", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_hold(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_req): Json<requests::doit::DoitHoldRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+    let cmd = raw_req.into_with_id(id);
+
+    let res = modules.doit_use_case().review(cmd, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/held".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/reject",
+    operation_id = "postDoitReject",
+    tag = "doit",
+    request_body = requests::doit::DoitRejectRequest,
+    responses(
+        (status = 201, description = "Reviewed", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_reject(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_req): Json<requests::doit::DoitRejectRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+    let cmd = raw_req.into_with_id(id);
+
+    let res = modules.doit_use_case().review(cmd, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/rejected".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/duplicate",
+    operation_id = "postDoitDuplicate",
+    tag = "doit",
+    request_body = requests::doit::DoitDuplicateRequest,
+    responses(
+        (status = 201, description = "Reviewed", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_duplicate(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_req): Json<requests::doit::DoitDuplicateRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+    let cmd = raw_req.try_into_with_id(id)?;
+
+    let res = modules.doit_use_case().review(cmd, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/marked-duplicate".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/doits/{doit_id}/reopen",
+    operation_id = "postDoitReopen",
+    tag = "doit",
+    responses(
+        (status = 201, description = "Reviewed", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_reopen(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+    let cmd = DoitReviewCommand::reopen(id);
+
+    let res = modules.doit_use_case().review(cmd, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/reopened".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/doits/{doit_id}/vote",
+    operation_id = "putDoitVote",
+    tag = "doit",
+    responses(
+        (status = 201, description = "Voted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_put_vote(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+
+    let user_id = if let Client::User(u) = ctx.client().client() {
+        u.id().clone()
+    } else {
+        return Err(ErrorResponse::from(ErrorCode::UserNotVerified));
+    };
+
+    let res = modules.doit_use_case().vote(id, user_id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/voted".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/doits/{doit_id}/vote",
+    operation_id = "deleteDoitVote",
+    tag = "doit",
+    responses(
+        (status = 201, description = "Unvoted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_delete_vote(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = DoitId::try_from(raw_id)?;
+
+    let user_id = if let Client::User(u) = ctx.client().client() {
+        u.id().clone()
+    } else {
+        return Err(ErrorResponse::from(ErrorCode::UserNotVerified));
+    };
+
+    let res = modules.doit_use_case().unvote(id, user_id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("doit/unvoted".to_string())),
         Err(e) => Err(e.into()),
     }
 }
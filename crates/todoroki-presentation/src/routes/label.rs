@@ -1,11 +1,16 @@
-use axum::{extract::State, response::IntoResponse, Extension, Json};
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
 use std::sync::Arc;
+use todoroki_domain::entities::label::LabelId;
 
 use crate::{
     context::Context,
     models::{
         requests,
-        responses::{self, error::ErrorResponse, success::SuccessResponse},
+        responses::{self, error::ErrorResponse, page::PageResponse, success::SuccessResponse},
     },
     modules::Modules,
 };
@@ -16,8 +21,9 @@ use todoroki_infrastructure::shared::DefaultRepositories;
     path = "/labels",
     operation_id = "getLabels",
     tag = "label",
+    params(requests::label::LabelListQuery),
     responses(
-        (status = 200, description = "OK", body = Vec<responses::label::LabelResponse>),
+        (status = 200, description = "OK", body = PageResponse<responses::label::LabelResponse>),
         (status = 400, description = "Bad Request", body = ErrorResponse),
         (status = 422, description = "Unprocessable Entity", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse),
@@ -27,16 +33,44 @@ use todoroki_infrastructure::shared::DefaultRepositories;
 pub async fn handle_get(
     State(modules): State<Arc<Modules<DefaultRepositories>>>,
     Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::label::LabelListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let page = raw_query.try_into_page_request()?;
+
+    let res = modules.label_use_case().list(page, &ctx).await;
+
+    match res {
+        Ok(labels) => Ok(Json(PageResponse::<responses::label::LabelResponse>::from(
+            labels,
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/labels/{label_id}",
+    operation_id = "getLabelById",
+    tag = "label",
+    responses(
+        (status = 200, description = "OK", body = responses::label::LabelResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(),
+)]
+pub async fn handle_get_by_id(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let res = modules.label_use_case().list(&ctx).await;
+    let id = LabelId::try_from(raw_id)?;
+
+    let res = modules.label_use_case().get_by_id(id, &ctx).await;
 
     match res {
-        Ok(labels) => Ok(Json(
-            labels
-                .into_iter()
-                .map(responses::label::LabelResponse::from)
-                .collect::<Vec<responses::label::LabelResponse>>(),
-        )),
+        Ok(label) => Ok(Json(responses::label::LabelResponse::from(label))),
         Err(e) => Err(e.into()),
     }
 }
@@ -0,0 +1,51 @@
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::sync::Arc;
+
+use crate::{
+    context::Context,
+    models::{
+        requests,
+        responses::{self, error::ErrorResponse},
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    operation_id = "search",
+    tag = "search",
+    params(requests::search::SearchQuery),
+    responses(
+        (status = 200, description = "OK", body = responses::search::SearchResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_search(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::search::SearchQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules
+        .search_use_case()
+        .search(
+            raw_query.q,
+            raw_query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+            &ctx,
+        )
+        .await;
+
+    match res {
+        Ok(result) => Ok(Json(responses::search::SearchResponse::from(result))),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -1,28 +1,34 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     response::IntoResponse,
     Extension, Json,
 };
 use std::sync::Arc;
-use todoroki_domain::{entities::todo::TodoId, value_objects::error::ErrorCode};
+use todoroki_domain::{
+    entities::todo::TodoId,
+    repositories::{pagination::PageRequest, todo::TodoFilter},
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
 
 use crate::{
     context::Context,
     models::{
         requests,
-        responses::{self, error::ErrorResponse, success::SuccessResponse},
+        responses::{self, error::ErrorResponse, page::PageResponse, success::SuccessResponse},
     },
     modules::Modules,
 };
 use todoroki_infrastructure::shared::DefaultRepositories;
+use todoroki_use_case::{shared::ContextProvider, todo::dto::TodoUpdateOptions};
 
 #[utoipa::path(
     get,
     path = "/todos",
     operation_id = "getTodos",
     tag = "todo",
+    params(requests::todo::TodoListQuery),
     responses(
-        (status = 200, description = "OK", body = Vec<responses::todo::TodoResponse>),
+        (status = 200, description = "OK", body = PageResponse<responses::todo::TodoResponse>),
         (status = 400, description = "Bad Request", body = ErrorResponse),
         (status = 422, description = "Unprocessable Entity", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse),
@@ -32,8 +38,129 @@ use todoroki_infrastructure::shared::DefaultRepositories;
 pub async fn handle_get(
     State(modules): State<Arc<Modules<DefaultRepositories>>>,
     Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::todo::TodoListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let page = raw_query.try_into_page_request()?;
+    let filter = TodoFilter::try_from(raw_query)?;
+
+    let res = modules.todo_use_case().list(filter, page, &ctx).await;
+
+    match res {
+        Ok(todos) => Ok(Json(PageResponse::<responses::todo::TodoResponse>::from(
+            todos,
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/occurrences",
+    operation_id = "getTodoOccurrences",
+    tag = "todo",
+    params(requests::todo::TodoOccurrenceQuery),
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::todo::TodoOccurrenceEntryResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_occurrences(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::todo::TodoOccurrenceQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let (from, to) = raw_query.try_into_range()?;
+
+    let res = modules
+        .todo_use_case()
+        .list_occurrences(from, to, &ctx)
+        .await;
+
+    match res {
+        Ok(entries) => Ok(Json(
+            entries
+                .into_iter()
+                .map(responses::todo::TodoOccurrenceEntryResponse::from)
+                .collect::<Vec<responses::todo::TodoOccurrenceEntryResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/graph",
+    operation_id = "getTodoGraph",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = responses::todo::TodoGraphResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_graph(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules.todo_use_case().graph(&ctx).await;
+
+    match res {
+        Ok(graph) => Ok(Json(responses::todo::TodoGraphResponse::from(graph))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}",
+    operation_id = "getTodoById",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = responses::todo::TodoResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_by_id(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().get_by_id(id, &ctx).await;
+
+    match res {
+        Ok(todo) => Ok(Json(responses::todo::TodoResponse::from(todo))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}/children",
+    operation_id = "getTodoChildren",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::todo::TodoResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_children(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    let res = modules.todo_use_case().list(&ctx).await;
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().list_children(id, &ctx).await;
 
     match res {
         Ok(todos) => Ok(Json(
@@ -46,6 +173,42 @@ pub async fn handle_get(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}/transitions",
+    operation_id = "getTodoStatusTransitions",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::todo::TodoStatusTransitionResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_transitions(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules
+        .todo_use_case()
+        .list_status_transitions(id, &ctx)
+        .await;
+
+    match res {
+        Ok(transitions) => Ok(Json(
+            transitions
+                .into_iter()
+                .map(responses::todo::TodoStatusTransitionResponse::from)
+                .collect::<Vec<responses::todo::TodoStatusTransitionResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/todos",
@@ -66,11 +229,12 @@ pub async fn handle_post(
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let labels = modules
         .label_use_case()
-        .list(&ctx)
-        .await
-        .map_err(ErrorCode::from)?;
+        .list(PageRequest::default(), &ctx)
+        .await?
+        .into_items();
 
-    let todo = raw_todo.try_into_with_labels(labels)?;
+    let todo =
+        raw_todo.try_into_with_labels_and_timezone(labels, ctx.client().client().timezone())?;
 
     let res = modules.todo_use_case().create(todo, &ctx).await;
 
@@ -91,6 +255,8 @@ pub async fn handle_post(
     responses(
         (status = 201, description = "Updated", body = SuccessResponse),
         (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 409, description = "Conflict", body = ErrorResponse),
         (status = 422, description = "Unprocessable Entity", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse),
     ),
@@ -103,12 +269,244 @@ pub async fn handle_patch(
     Json(raw_cmd): Json<requests::todo::TodoUpdateCommand>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let id = TodoId::try_from(raw_id)?;
-    let cmd = raw_cmd.try_into_with_id(id)?;
+    let options = TodoUpdateOptions {
+        cascade: raw_cmd.cascade.unwrap_or(false),
+        force: raw_cmd.force.unwrap_or(false),
+    };
+
+    let labels = modules
+        .label_use_case()
+        .list(PageRequest::default(), &ctx)
+        .await?
+        .into_items();
+
+    let cmd = raw_cmd.try_into_with_id_labels_and_timezone(
+        id,
+        labels,
+        ctx.client().client().timezone(),
+    )?;
+
+    let res = modules.todo_use_case().update(cmd, options, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("todo/updated".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/todos/{todo_id}",
+    operation_id = "deleteTodoById",
+    tag = "todo",
+    responses(
+        (status = 200, description = "Deleted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_delete(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().delete(id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("todo/deleted".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/trash",
+    operation_id = "getDeletedTodos",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::todo::TodoResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get_trash(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules.todo_use_case().list_deleted(&ctx).await;
+
+    match res {
+        Ok(todos) => Ok(Json(
+            todos
+                .into_iter()
+                .map(responses::todo::TodoResponse::from)
+                .collect::<Vec<responses::todo::TodoResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/todos/{todo_id}/history",
+    operation_id = "getTodoHistory",
+    tag = "todo",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::todo::TodoVersionResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_history(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().history(id, &ctx).await;
+
+    match res {
+        Ok(versions) => Ok(Json(
+            versions
+                .into_iter()
+                .map(responses::todo::TodoVersionResponse::from)
+                .collect::<Vec<responses::todo::TodoVersionResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/{todo_id}/revert/{version}",
+    operation_id = "revertTodoById",
+    tag = "todo",
+    responses(
+        (status = 200, description = "Reverted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_revert(
+    Path((raw_id, version)): Path<(String, u32)>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().revert(id, version, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("todo/reverted".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/{todo_id}/restore",
+    operation_id = "restoreTodoById",
+    tag = "todo",
+    responses(
+        (status = 200, description = "Restored", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_restore(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+
+    let res = modules.todo_use_case().restore(id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("todo/restored".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/todos/{todo_id}/occurrences/{occurrence_start}/completion",
+    operation_id = "completeTodoOccurrence",
+    tag = "todo",
+    responses(
+        (status = 200, description = "Completed", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_complete_occurrence(
+    Path((raw_id, raw_occurrence_start)): Path<(String, String)>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+    let occurrence_start = DateTime::try_from(raw_occurrence_start).map_err(ErrorCode::from)?;
 
-    let res = modules.todo_use_case().update(cmd, &ctx).await;
+    let res = modules
+        .todo_use_case()
+        .complete_occurrence(id, occurrence_start, &ctx)
+        .await;
 
     match res {
-        Ok(()) => Ok(SuccessResponse::new(format!("todo/updated"))),
+        Ok(()) => Ok(SuccessResponse::new(
+            "todo/occurrence-completed".to_string(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/todos/{todo_id}/occurrences/{occurrence_start}/completion",
+    operation_id = "uncompleteTodoOccurrence",
+    tag = "todo",
+    responses(
+        (status = 200, description = "Uncompleted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_uncomplete_occurrence(
+    Path((raw_id, raw_occurrence_start)): Path<(String, String)>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = TodoId::try_from(raw_id)?;
+    let occurrence_start = DateTime::try_from(raw_occurrence_start).map_err(ErrorCode::from)?;
+
+    let res = modules
+        .todo_use_case()
+        .uncomplete_occurrence(id, occurrence_start, &ctx)
+        .await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new(
+            "todo/occurrence-uncompleted".to_string(),
+        )),
         Err(e) => Err(e.into()),
     }
 }
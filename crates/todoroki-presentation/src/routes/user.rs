@@ -1,8 +1,15 @@
-use axum::{extract::State, response::IntoResponse, Extension, Json};
+use axum::{
+    extract::{Path, State},
+    response::IntoResponse,
+    Extension, Json,
+};
 use std::sync::Arc;
 use todoroki_domain::{
-    entities::{client::Client, user::UserRole},
-    value_objects::error::ErrorCode,
+    entities::{
+        client::Client,
+        user::{UserId, UserRole},
+    },
+    value_objects::{datetime::Timezone, error::ErrorCode},
 };
 use todoroki_use_case::shared::{ConfigProvider, ContextProvider};
 
@@ -40,6 +47,35 @@ pub async fn handle_get_me(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    operation_id = "getUserById",
+    tag = "user",
+    responses(
+        (status = 200, description = "OK", body = responses::user::UserResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get_by_id(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = UserId::try_from(raw_id)?;
+
+    let res = modules.user_use_case().get_by_id(id, &ctx).await;
+
+    match res {
+        Ok(user) => Ok(Json(responses::user::UserResponse::from(user))),
+        Err(e) => Err(e.into()),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/users",
@@ -86,3 +122,85 @@ pub async fn handle_post(
         Err(e) => Err(e.into()),
     }
 }
+
+#[utoipa::path(
+    post,
+    path = "/users/me/calendar-token",
+    operation_id = "postUserOwnCalendarToken",
+    tag = "user",
+    responses(
+        (status = 200, description = "Issued", body = responses::user::UserCalendarTokenResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_post_calendar_token(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules.user_use_case().issue_calendar_token(&ctx).await;
+
+    match res {
+        Ok(token) => Ok(Json(responses::user::UserCalendarTokenResponse::from(
+            token,
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/me/calendar-token",
+    operation_id = "deleteUserOwnCalendarToken",
+    tag = "user",
+    responses(
+        (status = 200, description = "Revoked", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_delete_calendar_token(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules.user_use_case().revoke_calendar_token(&ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new(
+            "user/calendar-token-revoked".to_string(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/users/me/timezone",
+    operation_id = "patchUserOwnTimezone",
+    tag = "user",
+    responses(
+        (status = 200, description = "Updated", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_patch_timezone(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_timezone): Json<requests::user::UserTimezoneRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let timezone = Timezone::try_from(raw_timezone.timezone).map_err(ErrorCode::from)?;
+
+    let res = modules
+        .user_use_case()
+        .update_timezone(timezone, &ctx)
+        .await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("user/timezone-updated".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use std::sync::Arc;
+use todoroki_domain::{
+    entities::{
+        client::Client,
+        webhook::{WebhookDeliveryId, WebhookId},
+    },
+    value_objects::error::ErrorCode,
+};
+use todoroki_use_case::shared::ContextProvider;
+
+use crate::{
+    context::Context,
+    models::{
+        requests,
+        responses::{self, error::ErrorResponse, page::PageResponse, success::SuccessResponse},
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    operation_id = "getWebhooks",
+    tag = "webhook",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::webhook::WebhookResponse>),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let res = modules.webhook_use_case().list(&ctx).await;
+
+    match res {
+        Ok(webhooks) => Ok(Json(
+            webhooks
+                .into_iter()
+                .map(responses::webhook::WebhookResponse::from)
+                .collect::<Vec<responses::webhook::WebhookResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    operation_id = "postWebhook",
+    tag = "webhook",
+    request_body = requests::webhook::WebhookRequest,
+    responses(
+        (status = 200, description = "OK", body = responses::webhook::WebhookCreatedResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_post(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Json(raw_webhook): Json<requests::webhook::WebhookRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let user_id = if let Client::User(u) = ctx.client().client() {
+        u.id().clone()
+    } else {
+        return Err(ErrorResponse::from(ErrorCode::UserNotVerified));
+    };
+
+    let webhook = raw_webhook.try_into_with_created_by(user_id)?;
+
+    // NOTE: secret は登録時にしか返さないので、作成した値をそのまま返す
+    let res = modules
+        .webhook_use_case()
+        .create(webhook.clone(), &ctx)
+        .await;
+
+    match res {
+        Ok(_) => Ok(Json(responses::webhook::WebhookCreatedResponse::from(
+            webhook,
+        ))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{webhook_id}",
+    operation_id = "deleteWebhookById",
+    tag = "webhook",
+    responses(
+        (status = 201, description = "Deleted", body = SuccessResponse),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_delete(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = WebhookId::try_from(raw_id)?;
+
+    let res = modules.webhook_use_case().delete(id, &ctx).await;
+
+    match res {
+        Ok(()) => Ok(SuccessResponse::new("webhook/deleted".to_string())),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/deliveries",
+    operation_id = "getWebhookDeliveries",
+    tag = "webhook",
+    params(requests::webhook::WebhookDeliveryListQuery),
+    responses(
+        (status = 200, description = "OK", body = PageResponse<responses::webhook::WebhookDeliveryResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get_deliveries(
+    Path(raw_id): Path<String>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+    Query(raw_query): Query<requests::webhook::WebhookDeliveryListQuery>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = WebhookId::try_from(raw_id)?;
+    let page = raw_query.try_into_page_request()?;
+
+    let res = modules
+        .webhook_use_case()
+        .list_deliveries(id, page, &ctx)
+        .await;
+
+    match res {
+        Ok(deliveries) => Ok(Json(PageResponse::<
+            responses::webhook::WebhookDeliveryResponse,
+        >::from(deliveries))),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/deliveries/{delivery_id}/attempts",
+    operation_id = "getWebhookDeliveryAttempts",
+    tag = "webhook",
+    responses(
+        (status = 200, description = "OK", body = Vec<responses::webhook::WebhookDeliveryAttemptResponse>),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = [])),
+)]
+pub async fn handle_get_attempts(
+    Path((raw_id, raw_delivery_id)): Path<(String, String)>,
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Extension(ctx): Extension<Context>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let id = WebhookId::try_from(raw_id)?;
+    let delivery_id = WebhookDeliveryId::try_from(raw_delivery_id)?;
+
+    let res = modules
+        .webhook_use_case()
+        .list_attempts(id, delivery_id, &ctx)
+        .await;
+
+    match res {
+        Ok(attempts) => Ok(Json(
+            attempts
+                .into_iter()
+                .map(responses::webhook::WebhookDeliveryAttemptResponse::from)
+                .collect::<Vec<responses::webhook::WebhookDeliveryAttemptResponse>>(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
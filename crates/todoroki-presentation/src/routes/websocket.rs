@@ -0,0 +1,349 @@
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::HeaderMap,
+    response::IntoResponse,
+};
+use std::{future, sync::Arc, time::Duration};
+use todoroki_domain::{
+    entities::{
+        change::ChangeTopic, client::Client, doit::DoitId, user::UserId, user_auth::UserAuthToken,
+    },
+    value_objects::{datetime::DateTime, error::ErrorCode},
+};
+use todoroki_use_case::{
+    change::{dto::ChangeEventDto, ChangeListener},
+    shared::ContextProvider,
+};
+use tokio::time::{self, Instant};
+
+use crate::{
+    context::Context,
+    models::{
+        requests::websocket::{WebSocketQuery, WebSocketRequest, WebSocketRequestBody},
+        responses::{
+            change::ChangeEventResponse, error::ErrorResponse, websocket::WebSocketResponse,
+        },
+    },
+    modules::Modules,
+};
+use todoroki_infrastructure::{change_stream::PgChangeSubscription, shared::DefaultRepositories};
+
+// 有効期限が切れてから、認証し直されないまま切断するまでの時間
+const WEBSOCKET_REAUTH_GRACE_SECS: u64 = 300;
+// 認証し直されなかった場合の close code (4000 番台はアプリケーションで自由に使える)
+const WEBSOCKET_CLOSE_AUTH_EXPIRED: u16 = 4001;
+
+type Listener = ChangeListener<PgChangeSubscription, Context>;
+
+#[utoipa::path(
+    get,
+    path = "/ws",
+    operation_id = "getWebSocket",
+    tag = "websocket",
+    params(WebSocketQuery),
+    responses(
+        (status = 101, description = "WebSocket に切り替える。クライアントは WebSocketRequest を送り、サーバーは WebSocketResponse を送る", body = WebSocketResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Forbidden", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse),
+    ),
+    security(("jwt_token" = []), ("nothing" = [])),
+)]
+pub async fn handle_get_ws(
+    State(modules): State<Arc<Modules<DefaultRepositories>>>,
+    Query(query): Query<WebSocketQuery>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let token = match headers.get("Authorization") {
+        Some(h) => Some(bearer_token(h.to_str().unwrap_or_default())?),
+        None => query.token,
+    };
+
+    // NOTE: トークンがなければ optional_jwt_auth と同じく認証なしとみなす
+    let (client, expires_at) = match token {
+        Some(token) => {
+            let (client, expires_at) = modules
+                .user_use_case()
+                .verify_with_expiry(UserAuthToken::new(token), modules.config())
+                .await?;
+
+            (client, Some(expires_at))
+        }
+        None => (Client::Unverified, None),
+    };
+
+    let ctx = Context::new(client, modules.config().clone());
+
+    let listener = modules
+        .change_use_case()
+        .subscribe(None, ctx.clone())
+        .await?;
+
+    let session = WebSocketSession {
+        modules,
+        ctx,
+        expires_at,
+        expired: false,
+        topics: Vec::new(),
+        listener,
+    };
+
+    Ok(ws.on_upgrade(move |socket| session.run(socket)))
+}
+
+fn bearer_token(authorization: &str) -> Result<String, ErrorCode> {
+    authorization
+        .strip_prefix("Bearer ")
+        .map(|t| t.to_string())
+        .ok_or(ErrorCode::UserAuthTokenVerificationError(
+            "invalid-authorization-header, should be starts with `Bearer`".to_string(),
+        ))
+}
+
+// 1つの接続の状態
+struct WebSocketSession {
+    modules: Arc<Modules<DefaultRepositories>>,
+    ctx: Context,
+    // ログインしていなければ None
+    expires_at: Option<DateTime>,
+    // 有効期限が切れてから認証し直されるまでの間は true
+    expired: bool,
+    topics: Vec<ChangeTopic>,
+    // NOTE: 有効期限が切れても購読は止めずに、認証し直された時点から続きを受け取る
+    listener: Listener,
+}
+
+enum Step {
+    Received(Option<Result<Message, axum::Error>>),
+    Changed(Option<Box<ChangeEventDto>>),
+    Deadline,
+}
+
+impl WebSocketSession {
+    async fn run(mut self, mut socket: WebSocket) {
+        let authenticated = WebSocketResponse::authenticated(None, self.expires_at.as_ref());
+        if send(&mut socket, &authenticated).await.is_err() {
+            return;
+        }
+
+        loop {
+            let deadline = self.deadline();
+
+            let step = tokio::select! {
+                message = socket.recv() => Step::Received(message),
+                event = next_change(&mut self.listener, &self.topics, self.expired) => {
+                    Step::Changed(event.map(Box::new))
+                }
+                _ = wait_until(deadline) => Step::Deadline,
+            };
+
+            let res = match step {
+                Step::Received(Some(Ok(Message::Text(text)))) => {
+                    let res = self.handle_text(text.as_str()).await;
+                    send(&mut socket, &res).await
+                }
+                Step::Received(Some(Ok(Message::Close(_)))) | Step::Received(None) => break,
+                Step::Received(Some(Ok(_))) => Ok(()),
+                Step::Received(Some(Err(e))) => {
+                    tracing::warn!("websocket receive failed: {e}");
+                    break;
+                }
+                Step::Changed(Some(dto)) => {
+                    let res = WebSocketResponse::from(ChangeEventResponse::from(*dto));
+                    send(&mut socket, &res).await
+                }
+                Step::Changed(None) => match self.resubscribe().await {
+                    Ok(()) => Ok(()),
+                    Err(e) => {
+                        let res = WebSocketResponse::Error {
+                            request_id: None,
+                            error: e.into(),
+                        };
+                        let _ = send(&mut socket, &res).await;
+                        break;
+                    }
+                },
+                Step::Deadline if !self.expired => {
+                    self.expired = true;
+                    send(&mut socket, &WebSocketResponse::AuthExpired).await
+                }
+                Step::Deadline => {
+                    let _ = socket
+                        .send(Message::Close(Some(CloseFrame {
+                            code: WEBSOCKET_CLOSE_AUTH_EXPIRED,
+                            reason: "auth-expired".into(),
+                        })))
+                        .await;
+                    break;
+                }
+            };
+
+            if res.is_err() {
+                break;
+            }
+        }
+    }
+
+    // 有効期限が切れる時刻か、切れた後に切断する時刻
+    fn deadline(&self) -> Option<Instant> {
+        let expires_at = self.expires_at.clone()?.value();
+        let remaining = (expires_at - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default();
+
+        if self.expired {
+            Some(Instant::now() + remaining + Duration::from_secs(WEBSOCKET_REAUTH_GRACE_SECS))
+        } else {
+            Some(Instant::now() + remaining)
+        }
+    }
+
+    // 取りこぼした場合などで購読が終わったら、最後に受け取った変更の続きから購読し直す
+    async fn resubscribe(&mut self) -> Result<(), ErrorCode> {
+        self.listener = self
+            .modules
+            .change_use_case()
+            .subscribe(self.listener.last_event_id(), self.ctx.clone())
+            .await?;
+
+        Ok(())
+    }
+
+    async fn handle_text(&mut self, text: &str) -> WebSocketResponse {
+        let req = match serde_json::from_str::<WebSocketRequest>(text) {
+            Ok(req) => req,
+            Err(e) => {
+                return WebSocketResponse::Error {
+                    request_id: None,
+                    error: ErrorCode::InvalidWebSocketMessage(e.to_string()).into(),
+                }
+            }
+        };
+
+        let request_id = req.request_id;
+
+        match self.handle_request(request_id.clone(), req.body).await {
+            Ok(res) => res,
+            Err(e) => WebSocketResponse::Error {
+                request_id,
+                error: e.into(),
+            },
+        }
+    }
+
+    async fn handle_request(
+        &mut self,
+        request_id: Option<String>,
+        body: WebSocketRequestBody,
+    ) -> Result<WebSocketResponse, ErrorCode> {
+        if self.expired && !matches!(body, WebSocketRequestBody::Auth { .. }) {
+            return Err(ErrorCode::UserAuthTokenVerificationError(
+                "token-expired".to_string(),
+            ));
+        }
+
+        match body {
+            WebSocketRequestBody::Auth { token } => {
+                let (client, expires_at) = self
+                    .modules
+                    .user_use_case()
+                    .verify_with_expiry(UserAuthToken::new(token), self.modules.config())
+                    .await?;
+
+                let ctx = Context::new(client, self.modules.config().clone());
+                self.listener.reauthorize(ctx.clone())?;
+
+                self.ctx = ctx;
+                self.expires_at = Some(expires_at);
+                self.expired = false;
+
+                return Ok(WebSocketResponse::authenticated(
+                    request_id,
+                    self.expires_at.as_ref(),
+                ));
+            }
+            WebSocketRequestBody::Subscribe { topic } => {
+                let topic = topic.try_into_topic()?;
+
+                if topic == ChangeTopic::MyDoits && self.user_id().is_none() {
+                    return Err(ErrorCode::UserNotVerified);
+                }
+
+                if !self.topics.contains(&topic) {
+                    self.topics.push(topic);
+                }
+            }
+            WebSocketRequestBody::Unsubscribe { topic } => {
+                let topic = topic.try_into_topic()?;
+
+                self.topics.retain(|t| *t != topic);
+            }
+            WebSocketRequestBody::UpdateTodoStatus(req) => {
+                let (cmd, options) = req.try_into_command()?;
+
+                self.modules
+                    .todo_use_case()
+                    .update(cmd, options, &self.ctx)
+                    .await?;
+            }
+            WebSocketRequestBody::VoteDoit { doit_id } => {
+                let id = DoitId::try_from(doit_id)?;
+                let user_id = self.user_id().ok_or(ErrorCode::UserNotVerified)?;
+
+                self.modules
+                    .doit_use_case()
+                    .vote(id, user_id, &self.ctx)
+                    .await?;
+            }
+            WebSocketRequestBody::UnvoteDoit { doit_id } => {
+                let id = DoitId::try_from(doit_id)?;
+                let user_id = self.user_id().ok_or(ErrorCode::UserNotVerified)?;
+
+                self.modules
+                    .doit_use_case()
+                    .unvote(id, user_id, &self.ctx)
+                    .await?;
+            }
+        }
+
+        Ok(WebSocketResponse::Ack { request_id })
+    }
+
+    fn user_id(&self) -> Option<UserId> {
+        match self.ctx.client().client() {
+            Client::User(u) => Some(u.id().clone()),
+            _ => None,
+        }
+    }
+}
+
+async fn send(socket: &mut WebSocket, res: &WebSocketResponse) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(res).map_err(axum::Error::new)?;
+
+    socket.send(Message::Text(text.into())).await
+}
+
+// 有効期限が切れている間は変更を受け取らない
+// NOTE: 何も購読していない間も読み進めておき、後から購読した時に過去の変更が届かないようにする
+async fn next_change(
+    listener: &mut Listener,
+    topics: &[ChangeTopic],
+    expired: bool,
+) -> Option<ChangeEventDto> {
+    if expired {
+        return future::pending().await;
+    }
+
+    listener.next_in(topics).await
+}
+
+async fn wait_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => time::sleep_until(deadline).await,
+        None => future::pending().await,
+    }
+}
@@ -0,0 +1,35 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::modules::Modules;
+use todoroki_infrastructure::shared::DefaultRepositories;
+
+// 送信時刻を確認する間隔
+const WEBHOOK_DELIVERY_INTERVAL: Duration = Duration::from_secs(5);
+// 続けて送信する配送の最大数
+const WEBHOOK_DELIVERY_BATCH_SIZE: u32 = 50;
+
+// 送信時刻を過ぎた webhook の配送を送信し続ける
+// NOTE: 取り出した配送はロックされるので、複数のプロセスで動かしてもよい
+pub async fn run_webhook_delivery(modules: Arc<Modules<DefaultRepositories>>) {
+    let mut interval = tokio::time::interval(WEBHOOK_DELIVERY_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        loop {
+            match modules
+                .webhook_use_case()
+                .deliver_due(WEBHOOK_DELIVERY_BATCH_SIZE)
+                .await
+            {
+                // 取り出しきれなかった配送があれば続けて送信する
+                Ok(count) if count == WEBHOOK_DELIVERY_BATCH_SIZE as usize => continue,
+                Ok(_) => break,
+                Err(e) => {
+                    tracing::error!("failed to deliver webhooks: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+pub mod error;
+pub mod operations;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+use todoroki_domain::{
+    entities::audit::AuditActor,
+    repositories::{audit::AuditRepositoryError, Repositories},
+    value_objects::permission::Permission,
+};
+
+use crate::shared::{actor_id, ContextProvider};
+
+pub struct AuditUseCase<R: Repositories> {
+    repositories: Arc<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum AuditUseCaseError {
+    #[error(transparent)]
+    AuditRepositoryError(#[from] AuditRepositoryError),
+}
+
+impl<R: Repositories> AuditUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+}
+
+// 変更と同じトランザクションで監査ログに記録するため、リポジトリでの変更に渡す
+pub(crate) fn actor(permission: Permission, ctx: &impl ContextProvider) -> AuditActor {
+    AuditActor::new(actor_id(ctx), permission)
+}
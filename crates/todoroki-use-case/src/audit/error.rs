@@ -0,0 +1,11 @@
+use todoroki_domain::value_objects::error::ErrorCode;
+
+use crate::audit::AuditUseCaseError;
+
+impl From<AuditUseCaseError> for ErrorCode {
+    fn from(value: AuditUseCaseError) -> Self {
+        match value {
+            AuditUseCaseError::AuditRepositoryError(e) => Self::AuditRepositoryInternalError(e),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{
+    audit::{AuditUseCase, AuditUseCaseError},
+    shared::ContextProvider,
+};
+
+use todoroki_domain::{
+    entities::audit::AuditEvent,
+    repositories::{
+        audit::{AuditFilter, AuditRepository, AuditSortKey},
+        pagination::{Page, PageRequest},
+        Repositories,
+    },
+    value_objects::{error::ErrorCode, permission::Permission},
+};
+
+impl<R: Repositories> AuditUseCase<R> {
+    pub async fn list(
+        &self,
+        filter: AuditFilter,
+        page: PageRequest<AuditSortKey>,
+        ctx: &impl ContextProvider,
+    ) -> Result<Page<AuditEvent>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadAudit)?;
+
+        let res = self
+            .repositories
+            .audit_repository()
+            .list(filter, page)
+            .await;
+
+        res.map_err(AuditUseCaseError::AuditRepositoryError)
+            .map_err(|e| e.into())
+    }
+}
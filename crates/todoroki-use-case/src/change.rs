@@ -0,0 +1,37 @@
+pub mod dto;
+pub mod error;
+pub mod operations;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+use todoroki_domain::{
+    entities::change::ChangeEventId,
+    repositories::{change_stream::ChangeStreamRepositoryError, Repositories},
+};
+
+use crate::shared::ContextProvider;
+
+pub struct ChangeUseCase<R: Repositories> {
+    repositories: Arc<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum ChangeUseCaseError {
+    #[error(transparent)]
+    ChangeStreamRepositoryError(#[from] ChangeStreamRepositoryError),
+}
+
+impl<R: Repositories> ChangeUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+}
+
+// 購読者の権限で閲覧できる形にして変更を受け取る
+pub struct ChangeListener<S, C: ContextProvider> {
+    subscription: S,
+    ctx: C,
+    // 購読を続けられなくなった場合に、ここから再開する
+    last_event_id: Option<ChangeEventId>,
+}
@@ -0,0 +1,52 @@
+use todoroki_domain::{
+    entities::{
+        change::{ChangeAction, ChangeEvent, ChangeTarget},
+        client::ContextedClient,
+        label::Label,
+    },
+    value_objects::{datetime::DateTime, error::ErrorCode, permission::Permission},
+};
+
+use crate::{doit::dto::DoitDto, todo::dto::TodoDto};
+
+pub struct ChangeEventDto {
+    pub id: u64,
+    pub action: ChangeAction,
+    pub target: ChangeTargetDto,
+    pub occurred_at: DateTime,
+}
+
+pub enum ChangeTargetDto {
+    Todo(TodoDto),
+    Doit(DoitDto),
+    Label(Label),
+}
+
+impl ChangeEventDto {
+    // NOTE: todo と doit は一覧や取得と同じく、非公開のものは権限がなければ代わりの名前にする
+    pub(crate) fn try_from_with_permission<'a>(
+        value: ChangeEvent,
+        client: ContextedClient<'a>,
+    ) -> Result<Self, ErrorCode> {
+        let target = match value.target().clone() {
+            ChangeTarget::Todo(todo) => {
+                ChangeTargetDto::Todo(TodoDto::try_from_with_permission(todo, client)?)
+            }
+            ChangeTarget::Doit(doit) => {
+                ChangeTargetDto::Doit(DoitDto::try_from_with_permission(doit, client)?)
+            }
+            ChangeTarget::Label(label) => {
+                client.has_permission(Permission::ReadLabel)?;
+
+                ChangeTargetDto::Label(label)
+            }
+        };
+
+        Ok(Self {
+            id: value.id().clone().value(),
+            action: *value.action(),
+            target,
+            occurred_at: value.occurred_at().clone(),
+        })
+    }
+}
@@ -0,0 +1,13 @@
+use todoroki_domain::value_objects::error::ErrorCode;
+
+use crate::change::ChangeUseCaseError;
+
+impl From<ChangeUseCaseError> for ErrorCode {
+    fn from(value: ChangeUseCaseError) -> Self {
+        match value {
+            ChangeUseCaseError::ChangeStreamRepositoryError(e) => {
+                Self::ChangeStreamRepositoryInternalError(e)
+            }
+        }
+    }
+}
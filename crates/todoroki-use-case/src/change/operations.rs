@@ -0,0 +1,96 @@
+use crate::{
+    change::{dto::ChangeEventDto, ChangeListener, ChangeUseCase, ChangeUseCaseError},
+    shared::ContextProvider,
+};
+
+use todoroki_domain::{
+    entities::{
+        change::{ChangeEvent, ChangeEventId, ChangeTopic},
+        client::Client,
+    },
+    repositories::{
+        change_stream::{ChangeStreamRepository, ChangeSubscription},
+        Repositories,
+    },
+    value_objects::{error::ErrorCode, permission::Permission},
+};
+
+type Subscription<R> =
+    <<R as Repositories>::ChangeStreamRepositoryImpl as ChangeStreamRepository>::Subscription;
+
+impl<R: Repositories> ChangeUseCase<R> {
+    // last_event_id より後の変更から購読する
+    pub async fn subscribe<C: ContextProvider>(
+        &self,
+        last_event_id: Option<ChangeEventId>,
+        ctx: C,
+    ) -> Result<ChangeListener<Subscription<R>, C>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        let subscription = self
+            .repositories
+            .change_stream_repository()
+            .subscribe(last_event_id.clone())
+            .await
+            .map_err(ChangeUseCaseError::ChangeStreamRepositoryError)?;
+
+        Ok(ChangeListener {
+            subscription,
+            ctx,
+            last_event_id,
+        })
+    }
+}
+
+impl<S: ChangeSubscription, C: ContextProvider> ChangeListener<S, C> {
+    // 次の変更を待つ。購読を続けられなくなった場合は None
+    pub async fn next(&mut self) -> Option<ChangeEventDto> {
+        self.next_where(|_, _| true).await
+    }
+
+    // topics のいずれかに含まれる次の変更を待つ
+    pub async fn next_in(&mut self, topics: &[ChangeTopic]) -> Option<ChangeEventDto> {
+        self.next_where(|event, client| {
+            let user_id = match client {
+                Client::User(u) => Some(u.id()),
+                _ => None,
+            };
+
+            topics.iter().any(|topic| topic.matches(event, user_id))
+        })
+        .await
+    }
+
+    // 購読を続けたまま、認証し直したクライアントの権限で受け取るようにする
+    pub fn reauthorize(&mut self, ctx: C) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        self.ctx = ctx;
+
+        Ok(())
+    }
+
+    pub fn last_event_id(&self) -> Option<ChangeEventId> {
+        self.last_event_id.clone()
+    }
+
+    async fn next_where(
+        &mut self,
+        filter: impl Fn(&ChangeEvent, &Client) -> bool,
+    ) -> Option<ChangeEventDto> {
+        loop {
+            let event = self.subscription.next().await?;
+
+            self.last_event_id = Some(event.id().clone());
+
+            if !filter(&event, self.ctx.client().client()) {
+                continue;
+            }
+
+            // NOTE: 閲覧する権限のない変更は配信しない
+            if let Ok(dto) = ChangeEventDto::try_from_with_permission(event, self.ctx.client()) {
+                return Some(dto);
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+pub mod dto;
+pub mod error;
+pub mod operations;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+use todoroki_domain::{
+    entities::{comment::CommentId, doit::DoitId, todo::TodoId},
+    repositories::{
+        comment::CommentRepositoryError, doit::DoitRepositoryError, todo::TodoRepositoryError,
+        Repositories,
+    },
+};
+
+pub struct CommentUseCase<R: Repositories> {
+    repositories: Arc<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum CommentUseCaseError {
+    #[error(transparent)]
+    CommentRepositoryError(#[from] CommentRepositoryError),
+    #[error(transparent)]
+    TodoRepositoryError(#[from] TodoRepositoryError),
+    #[error(transparent)]
+    DoitRepositoryError(#[from] DoitRepositoryError),
+    #[error("Comment Not Found: {0:?}")]
+    CommentNotFound(CommentId),
+    #[error("Comment Parent Invalid: {0:?}")]
+    CommentParentInvalid(CommentId),
+    #[error("Todo Not Found: {0:?}")]
+    TodoNotFound(TodoId),
+    #[error("Doit Not Found: {0:?}")]
+    DoitNotFound(DoitId),
+}
+
+impl<R: Repositories> CommentUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+}
@@ -0,0 +1,45 @@
+use todoroki_domain::{
+    entities::comment::{Comment, CommentTarget},
+    value_objects::datetime::DateTime,
+};
+use uuid::Uuid;
+
+pub struct CommentDto {
+    pub id: Uuid,
+    pub todo_id: Option<Uuid>,
+    pub doit_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
+    pub body: String,
+    pub created_by: Uuid,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
+}
+
+const COMMENT_DELETED_BODY: &str = "[削除されたコメント]";
+
+impl From<Comment> for CommentDto {
+    fn from(value: Comment) -> Self {
+        let (todo_id, doit_id) = match value.target().clone() {
+            CommentTarget::Todo(id) => (Some(id.value()), None),
+            CommentTarget::Doit(id) => (None, Some(id.value())),
+        };
+
+        Self {
+            id: value.id().clone().value(),
+            todo_id,
+            doit_id,
+            parent_id: value.parent_id().clone().map(|id| id.value()),
+            // NOTE: スレッドを保つため削除したものも返すが、本文は見せない
+            body: if value.is_deleted() {
+                COMMENT_DELETED_BODY.to_string()
+            } else {
+                value.body().clone().value()
+            },
+            created_by: value.created_by().clone().value(),
+            created_at: value.created_at().clone(),
+            updated_at: value.updated_at().clone(),
+            deleted_at: value.deleted_at().clone(),
+        }
+    }
+}
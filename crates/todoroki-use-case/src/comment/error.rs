@@ -0,0 +1,19 @@
+use todoroki_domain::value_objects::error::ErrorCode;
+
+use crate::comment::CommentUseCaseError;
+
+impl From<CommentUseCaseError> for ErrorCode {
+    fn from(value: CommentUseCaseError) -> Self {
+        match value {
+            CommentUseCaseError::CommentRepositoryError(e) => {
+                Self::CommentRepositoryInternalError(e)
+            }
+            CommentUseCaseError::TodoRepositoryError(e) => Self::TodoRepositoryInternalError(e),
+            CommentUseCaseError::DoitRepositoryError(e) => Self::DoitRepositoryInternalError(e),
+            CommentUseCaseError::CommentNotFound(id) => Self::CommentNotFound(id),
+            CommentUseCaseError::CommentParentInvalid(id) => Self::CommentParentInvalid(id),
+            CommentUseCaseError::TodoNotFound(id) => Self::TodoNotFound(id),
+            CommentUseCaseError::DoitNotFound(id) => Self::DoitNotFound(id),
+        }
+    }
+}
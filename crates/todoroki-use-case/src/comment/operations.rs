@@ -0,0 +1,165 @@
+use crate::{
+    comment::{dto::CommentDto, CommentUseCase, CommentUseCaseError},
+    shared::ContextProvider,
+};
+
+use todoroki_domain::{
+    entities::{
+        comment::{Comment, CommentBody, CommentId, CommentTarget},
+        doit::{Doit, DoitId, DoitPublishment},
+        todo::{Todo, TodoId, TodoPublishment},
+    },
+    repositories::{
+        comment::CommentRepository, doit::DoitRepository, todo::TodoRepository, Repositories,
+    },
+    value_objects::{error::ErrorCode, permission::Permission},
+};
+
+impl<R: Repositories> CommentUseCase<R> {
+    pub async fn create(
+        &self,
+        comment: Comment,
+        ctx: &impl ContextProvider,
+    ) -> Result<CommentId, ErrorCode> {
+        match comment.target().clone() {
+            CommentTarget::Todo(id) => {
+                let todo = self.get_alive_todo(id).await?;
+                ctx.client()
+                    .has_permission(Permission::CreateTodoComment(todo))?;
+            }
+            CommentTarget::Doit(id) => {
+                let doit = self.get_doit(id).await?;
+                ctx.client()
+                    .has_permission(Permission::CreateDoitComment(doit))?;
+            }
+        }
+
+        // NOTE: 返信は同じ対象に付いた、削除されていないコメントにのみできる
+        if let Some(parent_id) = comment.parent_id() {
+            self.repositories
+                .comment_repository()
+                .get_by_id(parent_id.clone())
+                .await
+                .map_err(CommentUseCaseError::CommentRepositoryError)?
+                .filter(|p| p.target() == comment.target() && !p.is_deleted())
+                .ok_or(CommentUseCaseError::CommentParentInvalid(parent_id.clone()))?;
+        }
+
+        let res = self.repositories.comment_repository().create(comment).await;
+
+        res.map_err(CommentUseCaseError::CommentRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_by_target(
+        &self,
+        target: CommentTarget,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<CommentDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadComment)?;
+
+        // NOTE: 非公開のものへのコメントは、その非公開の内容を読める場合にのみ読める
+        match target.clone() {
+            CommentTarget::Todo(id) => {
+                let todo = self.get_alive_todo(id).await?;
+                if matches!(todo.is_public(), TodoPublishment::Private(_)) {
+                    ctx.client().has_permission(Permission::ReadPrivateTodo)?;
+                }
+            }
+            CommentTarget::Doit(id) => {
+                let doit = self.get_doit(id).await?;
+                if matches!(doit.is_public(), DoitPublishment::Private(_)) {
+                    ctx.client()
+                        .has_permission(Permission::ReadPrivateDoit(doit))?;
+                }
+            }
+        }
+
+        let res = self
+            .repositories
+            .comment_repository()
+            .list_by_target(target)
+            .await;
+
+        Ok(res
+            .map_err(CommentUseCaseError::CommentRepositoryError)?
+            .into_iter()
+            .map(CommentDto::from)
+            .collect())
+    }
+
+    pub async fn update(
+        &self,
+        id: CommentId,
+        body: CommentBody,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        let comment = self.get_alive_comment(id.clone()).await?;
+
+        ctx.client()
+            .has_permission(Permission::UpdateComment(comment))?;
+
+        let res = self
+            .repositories
+            .comment_repository()
+            .update(id, body)
+            .await;
+
+        res.map_err(CommentUseCaseError::CommentRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn delete(&self, id: CommentId, ctx: &impl ContextProvider) -> Result<(), ErrorCode> {
+        let comment = self.get_alive_comment(id.clone()).await?;
+
+        ctx.client()
+            .has_permission(Permission::ModerateComment(comment))?;
+
+        let res = self
+            .repositories
+            .comment_repository()
+            .delete_by_id(id)
+            .await;
+
+        res.map_err(CommentUseCaseError::CommentRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    async fn get_alive_comment(&self, id: CommentId) -> Result<Comment, ErrorCode> {
+        let comment = self
+            .repositories
+            .comment_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(CommentUseCaseError::CommentRepositoryError)?
+            .filter(|c| !c.is_deleted())
+            .ok_or(CommentUseCaseError::CommentNotFound(id))?;
+
+        Ok(comment)
+    }
+
+    async fn get_alive_todo(&self, id: TodoId) -> Result<Todo, ErrorCode> {
+        let todo = self
+            .repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(CommentUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(CommentUseCaseError::TodoNotFound(id))?;
+
+        Ok(todo)
+    }
+
+    async fn get_doit(&self, id: DoitId) -> Result<Doit, ErrorCode> {
+        let doit = self
+            .repositories
+            .doit_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(CommentUseCaseError::DoitRepositoryError)?
+            .ok_or(CommentUseCaseError::DoitNotFound(id))?;
+
+        Ok(doit)
+    }
+}
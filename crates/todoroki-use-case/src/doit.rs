@@ -6,8 +6,8 @@ use std::sync::Arc;
 use thiserror::Error;
 
 use todoroki_domain::{
-    entities::doit::DoitId,
-    repositories::{doit::DoitRepositoryError, Repositories},
+    entities::{doit::DoitId, todo::TodoId},
+    repositories::{doit::DoitRepositoryError, todo::TodoRepositoryError, Repositories},
 };
 
 pub struct DoitUseCase<R: Repositories> {
@@ -18,8 +18,14 @@ pub struct DoitUseCase<R: Repositories> {
 pub enum DoitUseCaseError {
     #[error(transparent)]
     DoitRepositoryError(#[from] DoitRepositoryError),
+    #[error(transparent)]
+    TodoRepositoryError(#[from] TodoRepositoryError),
     #[error("Doit Not Found: {0:?}")]
     DoitNotFound(DoitId),
+    #[error("Doit Already Accepted: {0:?} -> {1:?}")]
+    DoitAlreadyAccepted(DoitId, TodoId),
+    #[error("Todo Not Found: {0:?}")]
+    TodoNotFound(TodoId),
 }
 
 impl<R: Repositories> DoitUseCase<R> {
@@ -1,5 +1,10 @@
 use todoroki_domain::{
-    entities::{self, client::ContextedClient, doit::DoitPublishment, label::Label},
+    entities::{
+        self,
+        client::{Client, ContextedClient},
+        doit::{DoitPublishment, DoitStatus},
+        label::Label,
+    },
     value_objects::{datetime::DateTime, error::ErrorCode, permission::Permission},
 };
 use uuid::Uuid;
@@ -11,7 +16,13 @@ pub struct DoitDto {
     pub is_public: bool,
     pub alternative_name: Option<String>,
     pub labels: Vec<Label>,
+    pub vote_count: u32,
+    pub has_voted: bool,
     pub affects_to: Option<Uuid>,
+    pub status: DoitStatus,
+    pub status_reason: Option<String>,
+    pub duplicate_of: Option<Uuid>,
+    pub reviewed_at: Option<DateTime>,
     pub deadlined_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
@@ -38,6 +49,16 @@ impl DoitDto {
                 .is_err()
         };
 
+        // NOTE: 審査の理由は作成者と owner だけが読める
+        let can_read_reason = client
+            .has_permission(Permission::ReadPrivateDoit(value.clone()))
+            .is_ok();
+
+        let has_voted = match client.client() {
+            Client::User(u) => value.has_voted(u.id()),
+            _ => false,
+        };
+
         Ok(Self {
             id: value.id().clone().value(),
             name: if use_alt {
@@ -66,7 +87,17 @@ impl DoitDto {
                 None
             },
             labels: value.labels().clone(),
+            vote_count: value.voters().len() as u32,
+            has_voted,
             affects_to: value.affects_to().clone().map(|id| id.value()),
+            status: *value.status(),
+            status_reason: if can_read_reason {
+                value.status_reason().clone()
+            } else {
+                None
+            },
+            duplicate_of: value.duplicate_of().clone().map(|id| id.value()),
+            reviewed_at: value.reviewed_at().clone(),
             deadlined_at: value.deadlined_at().clone(),
             created_at: value.created_at().clone(),
             updated_at: value.updated_at().clone(),
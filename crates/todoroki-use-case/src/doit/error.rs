@@ -6,7 +6,12 @@ impl From<DoitUseCaseError> for ErrorCode {
     fn from(value: DoitUseCaseError) -> Self {
         match value {
             DoitUseCaseError::DoitRepositoryError(e) => Self::DoitRepositoryInternalError(e),
+            DoitUseCaseError::TodoRepositoryError(e) => Self::TodoRepositoryInternalError(e),
             DoitUseCaseError::DoitNotFound(id) => Self::DoitNotFound(id),
+            DoitUseCaseError::DoitAlreadyAccepted(id, todo_id) => {
+                Self::DoitAlreadyAccepted(id, todo_id)
+            }
+            DoitUseCaseError::TodoNotFound(id) => Self::TodoNotFound(id),
         }
     }
 }
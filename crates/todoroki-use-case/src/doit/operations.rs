@@ -1,11 +1,23 @@
 use crate::{
+    audit,
     doit::{dto::DoitDto, DoitUseCase, DoitUseCaseError},
     shared::ContextProvider,
+    webhook,
 };
 
 use todoroki_domain::{
-    entities::doit::{Doit, DoitId, DoitUpdateCommand},
-    repositories::{doit::DoitRepository, Repositories},
+    entities::{
+        doit::{Doit, DoitAcceptance, DoitId, DoitReviewCommand, DoitStatus, DoitUpdateCommand},
+        todo::TodoId,
+        user::UserId,
+        webhook::WebhookEventType,
+    },
+    repositories::{
+        doit::{DoitAffectsToFilter, DoitFilter, DoitRepository, DoitSortKey},
+        pagination::{Page, PageRequest},
+        todo::TodoRepository,
+        Repositories,
+    },
     value_objects::{error::ErrorCode, permission::Permission},
 };
 
@@ -17,22 +29,51 @@ impl<R: Repositories> DoitUseCase<R> {
     ) -> Result<DoitId, ErrorCode> {
         ctx.client().has_permission(Permission::CreateDoit)?;
 
-        let res = self.repositories.doit_repository().create(doit).await;
+        let id = self
+            .repositories
+            .doit_repository()
+            .create(
+                doit.clone(),
+                audit::actor(Permission::CreateDoit, ctx),
+                webhook::events(WebhookEventType::DoitCreated, &doit),
+            )
+            .await
+            .map_err(DoitUseCaseError::DoitRepositoryError)?;
 
-        res.map_err(DoitUseCaseError::DoitRepositoryError)
-            .map_err(|e| e.into())
+        Ok(id)
     }
 
-    pub async fn list(&self, ctx: &impl ContextProvider) -> Result<Vec<DoitDto>, ErrorCode> {
+    pub async fn list(
+        &self,
+        filter: DoitFilter,
+        page: PageRequest<DoitSortKey>,
+        ctx: &impl ContextProvider,
+    ) -> Result<Page<DoitDto>, ErrorCode> {
         ctx.client().has_permission(Permission::ReadDoit)?;
 
-        let res = self.repositories.doit_repository().list().await;
+        let res = self.repositories.doit_repository().list(filter, page).await;
 
         res.map_err(DoitUseCaseError::DoitRepositoryError)
             .map_err(ErrorCode::from)?
-            .into_iter()
-            .map(|d| DoitDto::try_from_with_permission(d, ctx.client()))
-            .collect()
+            .try_map(|d| DoitDto::try_from_with_permission(d, ctx.client()))
+    }
+
+    pub async fn get_by_id(
+        &self,
+        id: DoitId,
+        ctx: &impl ContextProvider,
+    ) -> Result<DoitDto, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadDoit)?;
+
+        let doit = self
+            .repositories
+            .doit_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(DoitUseCaseError::DoitRepositoryError)?
+            .ok_or(DoitUseCaseError::DoitNotFound(id))?;
+
+        DoitDto::try_from_with_permission(doit, ctx.client())
     }
 
     pub async fn update(
@@ -40,19 +81,227 @@ impl<R: Repositories> DoitUseCase<R> {
         cmd: DoitUpdateCommand,
         ctx: &impl ContextProvider,
     ) -> Result<(), ErrorCode> {
+        let doit = self.get_doit(cmd.id().clone()).await?;
+        let permission = Permission::UpdateDoit(doit.clone());
+
+        ctx.client().has_permission(permission.clone())?;
+
+        let res = self
+            .repositories
+            .doit_repository()
+            .update(cmd, audit::actor(permission, ctx), webhook::doit_updated)
+            .await;
+
+        res.map_err(DoitUseCaseError::DoitRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    // todo_id が None なら Doit の内容から Todo を作り、指定されていればその Todo に紐づける
+    pub async fn accept(
+        &self,
+        id: DoitId,
+        todo_id: Option<TodoId>,
+        ctx: &impl ContextProvider,
+    ) -> Result<TodoId, ErrorCode> {
+        ctx.client().has_permission(Permission::AcceptDoit)?;
+
         let doit = self
             .repositories
             .doit_repository()
-            .get_by_id(cmd.id().clone())
+            .get_by_id(id.clone())
             .await
             .map_err(DoitUseCaseError::DoitRepositoryError)?
-            .ok_or(DoitUseCaseError::DoitNotFound(cmd.id().clone()))?;
+            .ok_or(DoitUseCaseError::DoitNotFound(id.clone()))?;
+
+        if let Some(affects_to) = doit.affects_to() {
+            return Err(DoitUseCaseError::DoitAlreadyAccepted(id, affects_to.clone()).into());
+        }
+
+        doit.status().transition_to(DoitStatus::Accepted)?;
+
+        let (acceptance, todo_events) = match todo_id {
+            Some(todo_id) => {
+                self.get_alive_todo(todo_id.clone()).await?;
+                (DoitAcceptance::ExistingTodo(todo_id), vec![])
+            }
+            None => {
+                let todo = doit.generate_todo();
+                let todo_events = webhook::events(WebhookEventType::TodoCreated, &todo);
+                (DoitAcceptance::NewTodo(Box::new(todo)), todo_events)
+            }
+        };
+
+        let accepted = self
+            .repositories
+            .doit_repository()
+            .accept(
+                id.clone(),
+                acceptance,
+                audit::actor(Permission::AcceptDoit, ctx),
+                todo_events,
+                webhook::doit_accepted,
+            )
+            .await
+            .map_err(DoitUseCaseError::DoitRepositoryError)?;
+
+        match accepted {
+            Some(todo_id) => Ok(todo_id),
+            // NOTE: 取得後に他のリクエストで受け入れられた場合
+            None => {
+                let doit = self
+                    .repositories
+                    .doit_repository()
+                    .get_by_id(id.clone())
+                    .await
+                    .map_err(DoitUseCaseError::DoitRepositoryError)?
+                    .ok_or(DoitUseCaseError::DoitNotFound(id.clone()))?;
+
+                if let Some(affects_to) = doit.affects_to() {
+                    return Err(
+                        DoitUseCaseError::DoitAlreadyAccepted(id, affects_to.clone()).into(),
+                    );
+                }
+
+                doit.status().transition_to(DoitStatus::Accepted)?;
+
+                Err(DoitUseCaseError::DoitNotFound(id).into())
+            }
+        }
+    }
+
+    // 保留・却下・重複とするか、審査をやり直す
+    pub async fn review(
+        &self,
+        cmd: DoitReviewCommand,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::ReviewDoit)?;
+
+        let doit = self.get_doit(cmd.id().clone()).await?;
+
+        doit.status().transition_to(*cmd.status())?;
 
-        ctx.client().has_permission(Permission::UpdateDoit(doit))?;
+        if let Some(duplicate_of) = cmd.duplicate_of() {
+            if duplicate_of == cmd.id() {
+                return Err(ErrorCode::DoitDuplicateOfSelf(cmd.id().clone()));
+            }
 
-        let res = self.repositories.doit_repository().update(cmd).await;
+            self.repositories
+                .doit_repository()
+                .get_by_id(duplicate_of.clone())
+                .await
+                .map_err(DoitUseCaseError::DoitRepositoryError)?
+                .ok_or(DoitUseCaseError::DoitNotFound(duplicate_of.clone()))?;
+        }
+
+        let res = self
+            .repositories
+            .doit_repository()
+            .review(
+                cmd,
+                audit::actor(Permission::ReviewDoit, ctx),
+                webhook::doit_updated,
+            )
+            .await;
+
+        res.map_err(DoitUseCaseError::DoitRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    // NOTE: 投票は頻繁に切り替えられるため、監査ログや webhook には残さない
+    pub async fn vote(
+        &self,
+        id: DoitId,
+        user_id: UserId,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::VoteDoit)?;
+
+        self.get_doit(id.clone()).await?;
+
+        let res = self.repositories.doit_repository().vote(id, user_id).await;
 
         res.map_err(DoitUseCaseError::DoitRepositoryError)
             .map_err(|e| e.into())
     }
+
+    pub async fn unvote(
+        &self,
+        id: DoitId,
+        user_id: UserId,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::VoteDoit)?;
+
+        self.get_doit(id.clone()).await?;
+
+        let res = self
+            .repositories
+            .doit_repository()
+            .unvote(id, user_id)
+            .await;
+
+        res.map_err(DoitUseCaseError::DoitRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_by_todo(
+        &self,
+        todo_id: TodoId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<DoitDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+        ctx.client().has_permission(Permission::ReadDoit)?;
+
+        self.get_alive_todo(todo_id.clone()).await?;
+
+        let res = self
+            .repositories
+            .doit_repository()
+            .list(
+                DoitFilter::new(
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(DoitAffectsToFilter::Todo(todo_id)),
+                    None,
+                ),
+                PageRequest::default(),
+            )
+            .await;
+
+        res.map_err(DoitUseCaseError::DoitRepositoryError)
+            .map_err(ErrorCode::from)?
+            .into_items()
+            .into_iter()
+            .map(|d| DoitDto::try_from_with_permission(d, ctx.client()))
+            .collect()
+    }
+
+    async fn get_doit(&self, id: DoitId) -> Result<Doit, ErrorCode> {
+        let doit = self
+            .repositories
+            .doit_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(DoitUseCaseError::DoitRepositoryError)?
+            .ok_or(DoitUseCaseError::DoitNotFound(id))?;
+
+        Ok(doit)
+    }
+
+    // 論理削除されていない todo があることを確かめる
+    async fn get_alive_todo(&self, id: TodoId) -> Result<(), ErrorCode> {
+        self.repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(DoitUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(DoitUseCaseError::TodoNotFound(id))?;
+
+        Ok(())
+    }
 }
@@ -4,7 +4,10 @@ pub mod operations;
 use std::sync::Arc;
 use thiserror::Error;
 
-use todoroki_domain::repositories::{label::LabelRepositoryError, Repositories};
+use todoroki_domain::{
+    entities::label::LabelId,
+    repositories::{label::LabelRepositoryError, Repositories},
+};
 
 pub struct LabelUseCase<R: Repositories> {
     repositories: Arc<R>,
@@ -14,6 +17,8 @@ pub struct LabelUseCase<R: Repositories> {
 pub enum LabelUseCaseError {
     #[error(transparent)]
     LabelRepositoryError(#[from] LabelRepositoryError),
+    #[error("Label Not Found: {0:?}")]
+    LabelNotFound(LabelId),
 }
 
 impl<R: Repositories> LabelUseCase<R> {
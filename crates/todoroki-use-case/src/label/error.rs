@@ -6,6 +6,7 @@ impl From<LabelUseCaseError> for ErrorCode {
     fn from(value: LabelUseCaseError) -> Self {
         match value {
             LabelUseCaseError::LabelRepositoryError(e) => Self::LabelRepositoryInternalError(e),
+            LabelUseCaseError::LabelNotFound(id) => Self::LabelNotFound(id),
         }
     }
 }
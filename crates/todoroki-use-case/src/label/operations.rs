@@ -1,11 +1,20 @@
 use crate::{
+    audit,
     label::{LabelUseCase, LabelUseCaseError},
     shared::ContextProvider,
+    webhook,
 };
 
 use todoroki_domain::{
-    entities::label::{Label, LabelId},
-    repositories::{label::LabelRepository, Repositories},
+    entities::{
+        label::{Label, LabelId},
+        webhook::WebhookEventType,
+    },
+    repositories::{
+        label::{LabelRepository, LabelSortKey},
+        pagination::{Page, PageRequest},
+        Repositories,
+    },
     value_objects::{error::ErrorCode, permission::Permission},
 };
 
@@ -17,18 +26,49 @@ impl<R: Repositories> LabelUseCase<R> {
     ) -> Result<LabelId, ErrorCode> {
         ctx.client().has_permission(Permission::CreateLabel)?;
 
-        let res = self.repositories.label_repository().create(label).await;
+        let id = self
+            .repositories
+            .label_repository()
+            .create(
+                label.clone(),
+                audit::actor(Permission::CreateLabel, ctx),
+                webhook::events(WebhookEventType::LabelCreated, &label),
+            )
+            .await
+            .map_err(LabelUseCaseError::LabelRepositoryError)?;
+
+        Ok(id)
+    }
+
+    pub async fn list(
+        &self,
+        page: PageRequest<LabelSortKey>,
+        ctx: &impl ContextProvider,
+    ) -> Result<Page<Label>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadLabel)?;
+
+        let res = self.repositories.label_repository().list(page).await;
 
         res.map_err(LabelUseCaseError::LabelRepositoryError)
             .map_err(|e| e.into())
     }
 
-    pub async fn list(&self, ctx: &impl ContextProvider) -> Result<Vec<Label>, ErrorCode> {
+    pub async fn get_by_id(
+        &self,
+        id: LabelId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Label, ErrorCode> {
         ctx.client().has_permission(Permission::ReadLabel)?;
 
-        let res = self.repositories.label_repository().list().await;
+        let res = self
+            .repositories
+            .label_repository()
+            .get_by_id(id.clone())
+            .await;
 
         res.map_err(LabelUseCaseError::LabelRepositoryError)
+            .map_err(ErrorCode::from)?
+            .ok_or(LabelUseCaseError::LabelNotFound(id))
             .map_err(|e| e.into())
     }
 }
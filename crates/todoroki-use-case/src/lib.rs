@@ -1,8 +1,13 @@
+pub mod audit;
+pub mod change;
+pub mod comment;
 pub mod doit;
 pub mod label;
+pub mod search;
 pub mod shared;
 pub mod todo;
 pub mod user;
+pub mod webhook;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
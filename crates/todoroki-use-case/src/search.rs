@@ -0,0 +1,30 @@
+pub mod dto;
+pub mod error;
+pub mod operations;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+use todoroki_domain::repositories::{
+    doit::DoitRepositoryError, label::LabelRepositoryError, todo::TodoRepositoryError, Repositories,
+};
+
+pub struct SearchUseCase<R: Repositories> {
+    repositories: Arc<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum SearchUseCaseError {
+    #[error(transparent)]
+    TodoRepositoryError(#[from] TodoRepositoryError),
+    #[error(transparent)]
+    DoitRepositoryError(#[from] DoitRepositoryError),
+    #[error(transparent)]
+    LabelRepositoryError(#[from] LabelRepositoryError),
+}
+
+impl<R: Repositories> SearchUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+}
@@ -0,0 +1,10 @@
+use todoroki_domain::entities::label::Label;
+
+use crate::{doit::dto::DoitDto, todo::dto::TodoDto};
+
+// それぞれ関連度の高い順
+pub struct SearchResultDto {
+    pub todos: Vec<TodoDto>,
+    pub doits: Vec<DoitDto>,
+    pub labels: Vec<Label>,
+}
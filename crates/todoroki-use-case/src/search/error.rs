@@ -0,0 +1,13 @@
+use todoroki_domain::value_objects::error::ErrorCode;
+
+use crate::search::SearchUseCaseError;
+
+impl From<SearchUseCaseError> for ErrorCode {
+    fn from(value: SearchUseCaseError) -> Self {
+        match value {
+            SearchUseCaseError::TodoRepositoryError(e) => Self::TodoRepositoryInternalError(e),
+            SearchUseCaseError::DoitRepositoryError(e) => Self::DoitRepositoryInternalError(e),
+            SearchUseCaseError::LabelRepositoryError(e) => Self::LabelRepositoryInternalError(e),
+        }
+    }
+}
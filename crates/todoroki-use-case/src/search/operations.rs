@@ -0,0 +1,70 @@
+use crate::{
+    doit::dto::DoitDto,
+    search::{dto::SearchResultDto, SearchUseCase, SearchUseCaseError},
+    shared::ContextProvider,
+    todo::dto::TodoDto,
+};
+
+use todoroki_domain::{
+    repositories::{
+        doit::DoitRepository, label::LabelRepository, search::SearchQuery, todo::TodoRepository,
+        Repositories,
+    },
+    value_objects::{error::ErrorCode, permission::Permission},
+};
+
+impl<R: Repositories> SearchUseCase<R> {
+    pub async fn search(
+        &self,
+        text: String,
+        limit: u32,
+        ctx: &impl ContextProvider,
+    ) -> Result<SearchResultDto, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+        ctx.client().has_permission(Permission::ReadDoit)?;
+        ctx.client().has_permission(Permission::ReadLabel)?;
+
+        // NOTE: 非公開のものの本来の名前で検索できると、代替名で隠していても名前を推測できてしまうため、
+        //       非公開の Todo を読める場合のみ本来の name や description でも検索する
+        //       Doit についても作成者ごとの判定はせず同じ基準を用いる
+        let include_private = ctx
+            .client()
+            .has_permission(Permission::ReadPrivateTodo)
+            .is_ok();
+
+        let query = SearchQuery::new(text, include_private, limit);
+
+        let todos = self
+            .repositories
+            .todo_repository()
+            .search(query.clone())
+            .await
+            .map_err(SearchUseCaseError::TodoRepositoryError)?;
+
+        let doits = self
+            .repositories
+            .doit_repository()
+            .search(query.clone())
+            .await
+            .map_err(SearchUseCaseError::DoitRepositoryError)?;
+
+        let labels = self
+            .repositories
+            .label_repository()
+            .search(query)
+            .await
+            .map_err(SearchUseCaseError::LabelRepositoryError)?;
+
+        Ok(SearchResultDto {
+            todos: todos
+                .into_iter()
+                .map(|t| TodoDto::try_from_with_permission(t, ctx.client()))
+                .collect::<Result<_, _>>()?,
+            doits: doits
+                .into_iter()
+                .map(|d| DoitDto::try_from_with_permission(d, ctx.client()))
+                .collect::<Result<_, _>>()?,
+            labels,
+        })
+    }
+}
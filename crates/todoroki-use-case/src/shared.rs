@@ -1,4 +1,7 @@
-use todoroki_domain::entities::client::ContextedClient;
+use todoroki_domain::entities::{
+    client::{Client, ContextedClient},
+    user::UserId,
+};
 
 pub trait ContextProvider {
     fn client<'a>(&'a self) -> ContextedClient<'a>;
@@ -11,3 +14,11 @@ pub trait ConfigProvider {
 
     fn default_owner_email(&self) -> &str;
 }
+
+// 操作しているユーザー。未登録のユーザーなどでは None
+pub(crate) fn actor_id(ctx: &impl ContextProvider) -> Option<UserId> {
+    match ctx.client().client() {
+        Client::User(u) => Some(u.id().clone()),
+        _ => None,
+    }
+}
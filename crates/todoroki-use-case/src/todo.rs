@@ -5,7 +5,11 @@ pub mod operations;
 use std::sync::Arc;
 use thiserror::Error;
 
-use todoroki_domain::repositories::{todo::TodoRepositoryError, Repositories};
+use todoroki_domain::{
+    entities::todo::TodoId,
+    repositories::{label::LabelRepositoryError, todo::TodoRepositoryError, Repositories},
+    value_objects::datetime::DateTime,
+};
 
 pub struct TodoUseCase<R: Repositories> {
     repositories: Arc<R>,
@@ -15,6 +19,14 @@ pub struct TodoUseCase<R: Repositories> {
 pub enum TodoUseCaseError {
     #[error(transparent)]
     TodoRepositoryError(#[from] TodoRepositoryError),
+    #[error(transparent)]
+    LabelRepositoryError(#[from] LabelRepositoryError),
+    #[error("Todo Not Found: {0:?}")]
+    TodoNotFound(TodoId),
+    #[error("Todo Occurrence Not Found: {0:?} {1:?}")]
+    TodoOccurrenceNotFound(TodoId, DateTime),
+    #[error("Todo Version Not Found: {0:?} {1}")]
+    TodoVersionNotFound(TodoId, u32),
 }
 
 impl<R: Repositories> TodoUseCase<R> {
@@ -2,8 +2,11 @@ use todoroki_domain::{
     entities::{
         self,
         client::ContextedClient,
-        label::Label,
-        todo::{TodoPublishment, TodoSchedule},
+        label::{Label, LabelName},
+        todo::{
+            TodoDescription, TodoId, TodoName, TodoProgress, TodoPublishment, TodoSchedule,
+            TodoStatus, TodoVersion, TodoVersionChange,
+        },
     },
     value_objects::{datetime::DateTime, error::ErrorCode, permission::Permission},
 };
@@ -19,13 +22,98 @@ pub struct TodoDto {
     pub is_public: bool,
     pub description: String,
     pub alternative_name: Option<String>,
+    pub parent_id: Option<Uuid>,
     pub labels: Vec<Label>,
+    pub blocker_ids: Vec<Uuid>,
+    pub is_blocked: bool,
     pub schedules: Vec<TodoSchedule>,
+    pub status: TodoStatus,
+    // 子がない場合は None
+    pub progress: Option<TodoProgress>,
+    // 進行中の回と直近の回
+    pub occurrences: Vec<TodoOccurrenceDto>,
     pub deadlined_at: Option<DateTime>,
     pub started_at: Option<DateTime>,
     pub ended_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    pub deleted_at: Option<DateTime>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TodoUpdateOptions {
+    // 完了にする場合に、子孫の todo もすべて完了にする
+    pub cascade: bool,
+    // blocker が完了していなくても始める
+    pub force: bool,
+}
+
+// 変更履歴の版と、直前の版からの変更
+#[derive(Debug, Clone)]
+pub struct TodoVersionDto {
+    pub version: TodoVersion,
+    // 最初の版では空
+    pub changes: Vec<TodoVersionChange>,
+}
+
+// blocker と、それを待っている todo の関係
+#[derive(Debug, Clone)]
+pub struct TodoGraphDto {
+    pub nodes: Vec<TodoDto>,
+    pub edges: Vec<TodoGraphEdgeDto>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoGraphEdgeDto {
+    pub todo_id: Uuid,
+    pub blocker_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoOccurrenceDto {
+    pub starts_at: DateTime,
+    pub ends_at: DateTime,
+    pub completed_at: Option<DateTime>,
+}
+
+// スケジュールを展開した1回分
+#[derive(Debug, Clone)]
+pub struct TodoOccurrenceEntryDto {
+    pub todo_id: Uuid,
+    pub name: String,
+    pub is_public: bool,
+    pub starts_at: DateTime,
+    pub ends_at: DateTime,
+}
+
+// iCalendar などから取り込む todo の下書き。ラベルは名前で指定する
+#[derive(Debug, Clone)]
+pub struct TodoImportDraft {
+    pub name: TodoName,
+    pub description: TodoDescription,
+    pub is_public: TodoPublishment,
+    pub categories: Vec<LabelName>,
+    pub schedules: Vec<TodoSchedule>,
+    pub deadlined_at: Option<DateTime>,
+    // (status, 変更された日時)
+    pub status: Option<(TodoStatus, DateTime)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoImportEntryDto {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    // 取り込めないものは Err に理由を入れる
+    pub draft: Result<TodoImportDraft, String>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoImportResultDto {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub result: Result<TodoId, String>,
+    pub warnings: Vec<String>,
 }
 
 impl TodoDto {
@@ -70,13 +158,32 @@ impl TodoDto {
             } else {
                 None
             },
+            parent_id: value.parent_id().clone().map(|id| id.value()),
             labels: value.labels().clone(),
+            blocker_ids: value
+                .blockers()
+                .iter()
+                .map(|blocker| blocker.id().clone().value())
+                .collect(),
+            is_blocked: value.is_blocked(),
             schedules: value.schedules().clone(),
+            status: *value.status(),
+            progress: Some(*value.progress()).filter(|p| p.has_children()),
+            occurrences: value
+                .recent_occurrences(&DateTime::now())
+                .into_iter()
+                .map(|(occurrence, completed_at)| TodoOccurrenceDto {
+                    starts_at: occurrence.starts_at().clone(),
+                    ends_at: occurrence.ends_at().clone(),
+                    completed_at,
+                })
+                .collect(),
             started_at: value.started_at().clone(),
             deadlined_at: value.deadlined_at().clone(),
             ended_at: value.ended_at().clone(),
             created_at: value.created_at().clone(),
             updated_at: value.updated_at().clone(),
+            deleted_at: value.deleted_at().clone(),
         })
     }
 }
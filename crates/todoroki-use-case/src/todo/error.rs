@@ -1,11 +1,33 @@
-use todoroki_domain::value_objects::error::ErrorCode;
+use todoroki_domain::{repositories::todo::TodoRepositoryError, value_objects::error::ErrorCode};
 
 use crate::todo::TodoUseCaseError;
 
 impl From<TodoUseCaseError> for ErrorCode {
     fn from(value: TodoUseCaseError) -> Self {
         match value {
+            TodoUseCaseError::TodoRepositoryError(
+                TodoRepositoryError::StatusTransitionInvalid(from, to),
+            ) => Self::TodoStatusTransitionInvalid(from, to),
+            TodoUseCaseError::TodoRepositoryError(TodoRepositoryError::HierarchyCycle(
+                id,
+                parent_id,
+            )) => Self::TodoHierarchyCycle(id, parent_id),
+            TodoUseCaseError::TodoRepositoryError(TodoRepositoryError::DependencyCycle(
+                id,
+                blocker_id,
+            )) => Self::TodoDependencyCycle(id, blocker_id),
+            TodoUseCaseError::TodoRepositoryError(TodoRepositoryError::TodoNotFound(id)) => {
+                Self::TodoNotFound(id)
+            }
             TodoUseCaseError::TodoRepositoryError(e) => Self::TodoRepositoryInternalError(e),
+            TodoUseCaseError::LabelRepositoryError(e) => Self::LabelRepositoryInternalError(e),
+            TodoUseCaseError::TodoNotFound(id) => Self::TodoNotFound(id),
+            TodoUseCaseError::TodoOccurrenceNotFound(id, occurrence_start) => {
+                Self::TodoOccurrenceNotFound(id, occurrence_start)
+            }
+            TodoUseCaseError::TodoVersionNotFound(id, version) => {
+                Self::TodoVersionNotFound(id, version)
+            }
         }
     }
 }
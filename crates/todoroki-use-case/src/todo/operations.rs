@@ -1,12 +1,34 @@
 use crate::{
+    audit,
     shared::ContextProvider,
-    todo::{dto::TodoDto, TodoUseCase, TodoUseCaseError},
+    todo::{
+        dto::{
+            TodoDto, TodoGraphDto, TodoGraphEdgeDto, TodoImportEntryDto, TodoImportResultDto,
+            TodoOccurrenceEntryDto, TodoUpdateOptions, TodoVersionDto,
+        },
+        TodoUseCase, TodoUseCaseError,
+    },
+    webhook,
 };
 
+use std::collections::{HashMap, HashSet};
+
 use todoroki_domain::{
-    entities::todo::{Todo, TodoId, TodoUpdateCommand},
-    repositories::{todo::TodoRepository, Repositories},
-    value_objects::{error::ErrorCode, permission::Permission},
+    entities::{
+        label::{Label, LabelDescription},
+        todo::{
+            Todo, TodoId, TodoPublishment, TodoStatus, TodoStatusTransition, TodoUpdateCommand,
+        },
+        webhook::WebhookEventType,
+    },
+    repositories::{
+        label::LabelRepository,
+        pagination::{Page, PageRequest},
+        todo::{TodoFilter, TodoRepository, TodoSortKey},
+        Repositories,
+    },
+    services::{dependency, hierarchy, occurrence},
+    value_objects::{datetime::DateTime, error::ErrorCode, permission::Permission},
 };
 
 impl<R: Repositories> TodoUseCase<R> {
@@ -17,32 +39,583 @@ impl<R: Repositories> TodoUseCase<R> {
     ) -> Result<TodoId, ErrorCode> {
         ctx.client().has_permission(Permission::CreateTodo)?;
 
-        let res = self.repositories.todo_repository().create(todo).await;
+        if let Some(parent_id) = todo.parent_id() {
+            self.get_alive(parent_id.clone()).await?;
+        }
+
+        let id = self
+            .repositories
+            .todo_repository()
+            .create(
+                todo.clone(),
+                audit::actor(Permission::CreateTodo, ctx),
+                webhook::events(WebhookEventType::TodoCreated, &todo),
+            )
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?;
+
+        Ok(id)
+    }
+
+    // 取り込めなかったものがあっても全体は失敗させず、項目ごとの結果を返す
+    pub async fn import(
+        &self,
+        entries: Vec<TodoImportEntryDto>,
+        create_missing_labels: bool,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoImportResultDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::CreateTodo)?;
+
+        if create_missing_labels {
+            ctx.client().has_permission(Permission::CreateLabel)?;
+        }
+
+        let mut labels: HashMap<String, Label> = self
+            .repositories
+            .label_repository()
+            .list(PageRequest::default())
+            .await
+            .map_err(TodoUseCaseError::LabelRepositoryError)?
+            .into_items()
+            .into_iter()
+            .map(|label| (label.name().clone().value(), label))
+            .collect();
+
+        let mut results = Vec::new();
+
+        for entry in entries {
+            let mut warnings = entry.warnings;
+
+            let draft = match entry.draft {
+                Ok(draft) => draft,
+                Err(reason) => {
+                    results.push(TodoImportResultDto {
+                        uid: entry.uid,
+                        summary: entry.summary,
+                        result: Err(reason),
+                        warnings,
+                    });
+                    continue;
+                }
+            };
+
+            let mut todo_labels: Vec<Label> = Vec::new();
+
+            for category in draft.categories {
+                let name = category.clone().value();
+
+                let label = match labels.get(&name) {
+                    Some(label) => label.clone(),
+                    None if create_missing_labels => {
+                        let label =
+                            Label::generate(category, LabelDescription::new(String::new()), None);
+
+                        self.repositories
+                            .label_repository()
+                            .create(
+                                label.clone(),
+                                audit::actor(Permission::CreateLabel, ctx),
+                                webhook::events(WebhookEventType::LabelCreated, &label),
+                            )
+                            .await
+                            .map_err(TodoUseCaseError::LabelRepositoryError)?;
+
+                        labels.insert(name, label.clone());
+
+                        label
+                    }
+                    None => {
+                        warnings.push(format!("label-not-found; name={name}"));
+                        continue;
+                    }
+                };
+
+                if !todo_labels.iter().any(|l| l.id() == label.id()) {
+                    todo_labels.push(label);
+                }
+            }
+
+            let todo = Todo::generate(
+                draft.name,
+                draft.description,
+                draft.is_public,
+                None,
+                todo_labels,
+                draft.schedules,
+                draft.deadlined_at,
+            );
+
+            let todo = match draft.status {
+                Some((status, at)) => todo.with_status(status, at),
+                None => todo,
+            };
+
+            let id = self
+                .repositories
+                .todo_repository()
+                .create(
+                    todo.clone(),
+                    audit::actor(Permission::CreateTodo, ctx),
+                    webhook::events(WebhookEventType::TodoCreated, &todo),
+                )
+                .await
+                .map_err(TodoUseCaseError::TodoRepositoryError)?;
+
+            results.push(TodoImportResultDto {
+                uid: entry.uid,
+                summary: entry.summary,
+                result: Ok(id),
+                warnings,
+            });
+        }
+
+        Ok(results)
+    }
+
+    pub async fn list(
+        &self,
+        filter: TodoFilter,
+        page: PageRequest<TodoSortKey>,
+        ctx: &impl ContextProvider,
+    ) -> Result<Page<TodoDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        let res = self.repositories.todo_repository().list(filter, page).await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(ErrorCode::from)?
+            .try_map(|d| TodoDto::try_from_with_permission(d, ctx.client()))
+    }
+
+    pub async fn get_by_id(
+        &self,
+        id: TodoId,
+        ctx: &impl ContextProvider,
+    ) -> Result<TodoDto, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        let todo = self
+            .repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(TodoUseCaseError::TodoNotFound(id))?;
+
+        TodoDto::try_from_with_permission(todo, ctx.client())
+    }
+
+    pub async fn update(
+        &self,
+        cmd: TodoUpdateCommand,
+        options: TodoUpdateOptions,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::UpdateTodo)?;
+
+        let before = self.get_alive(cmd.id().clone()).await?;
+
+        if let Some(to) = cmd.status() {
+            before.status().transition_to(*to)?;
+
+            if !options.force {
+                dependency::validate_start(&before, to)?;
+            }
+        }
+
+        if let Some(Some(parent_id)) = cmd.parent_id() {
+            let parent = self.get_with_deleted(parent_id.clone()).await?;
+
+            let ancestors = self
+                .repositories
+                .todo_repository()
+                .list_ancestor_ids(parent_id.clone())
+                .await
+                .map_err(TodoUseCaseError::TodoRepositoryError)?;
+
+            hierarchy::validate_parent(cmd.id(), &parent, &ancestors)?;
+        }
+
+        for blocker_id in cmd.add_blockers() {
+            let blocker = self.get_with_deleted(blocker_id.clone()).await?;
+
+            let blocker_blockers = self
+                .repositories
+                .todo_repository()
+                .list_transitive_blocker_ids(blocker_id.clone())
+                .await
+                .map_err(TodoUseCaseError::TodoRepositoryError)?;
+
+            dependency::validate_blocker(cmd.id(), &blocker, &blocker_blockers)?;
+        }
+
+        let completes = options.cascade && *cmd.status() == Some(TodoStatus::Completed);
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .update(
+                cmd,
+                completes,
+                audit::actor(Permission::UpdateTodo, ctx),
+                webhook::todo_updated,
+            )
+            .await;
 
         res.map_err(TodoUseCaseError::TodoRepositoryError)
             .map_err(|e| e.into())
     }
 
-    pub async fn list(&self, ctx: &impl ContextProvider) -> Result<Vec<TodoDto>, ErrorCode> {
+    // 論理削除されたものは含まない
+    pub async fn list_children(
+        &self,
+        id: TodoId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoDto>, ErrorCode> {
         ctx.client().has_permission(Permission::ReadTodo)?;
 
-        let res = self.repositories.todo_repository().list().await;
+        self.get_alive(id.clone()).await?;
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .list(
+                TodoFilter::new(None, None, None, None, None, Some(id)),
+                PageRequest::default(),
+            )
+            .await;
 
         res.map_err(TodoUseCaseError::TodoRepositoryError)
             .map_err(ErrorCode::from)?
+            .into_items()
             .into_iter()
             .map(|d| TodoDto::try_from_with_permission(d, ctx.client()))
             .collect()
     }
 
-    pub async fn update(
+    // blocker を持つものと、blocker になっているもののみを node とする
+    pub async fn graph(&self, ctx: &impl ContextProvider) -> Result<TodoGraphDto, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        let todos = self
+            .repositories
+            .todo_repository()
+            .list(TodoFilter::default(), PageRequest::default())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .into_items();
+
+        let edges = todos
+            .iter()
+            .flat_map(|todo| {
+                todo.blockers().iter().map(|blocker| TodoGraphEdgeDto {
+                    todo_id: todo.id().clone().value(),
+                    blocker_id: blocker.id().clone().value(),
+                })
+            })
+            .collect::<Vec<TodoGraphEdgeDto>>();
+
+        let linked = edges
+            .iter()
+            .flat_map(|edge| [edge.todo_id, edge.blocker_id])
+            .collect::<HashSet<uuid::Uuid>>();
+
+        let nodes = todos
+            .into_iter()
+            .filter(|todo| linked.contains(&todo.id().clone().value()))
+            .map(|todo| TodoDto::try_from_with_permission(todo, ctx.client()))
+            .collect::<Result<Vec<TodoDto>, ErrorCode>>()?;
+
+        Ok(TodoGraphDto { nodes, edges })
+    }
+
+    // 論理削除されていない todo を取得する
+    async fn get_alive(&self, id: TodoId) -> Result<Todo, ErrorCode> {
+        let todo = self
+            .repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(TodoUseCaseError::TodoNotFound(id))?;
+
+        Ok(todo)
+    }
+
+    // 論理削除されたものも含めて取得する
+    async fn get_with_deleted(&self, id: TodoId) -> Result<Todo, ErrorCode> {
+        let todo = self
+            .repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .ok_or(TodoUseCaseError::TodoNotFound(id))?;
+
+        Ok(todo)
+    }
+
+    pub async fn list_status_transitions(
         &self,
-        cmd: TodoUpdateCommand,
+        id: TodoId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoStatusTransition>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        self.repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(TodoUseCaseError::TodoNotFound(id.clone()))?;
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .list_status_transitions(id)
+            .await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    // 古い順
+    pub async fn history(
+        &self,
+        id: TodoId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoVersionDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        let todo = self.get_alive(id.clone()).await?;
+
+        let versions = self
+            .repositories
+            .todo_repository()
+            .list_versions(id)
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?;
+
+        // NOTE: 過去に非公開だった版の内容も隠す必要があるため、代わりの名前にはせず権限を求める
+        let is_private = |p: &TodoPublishment| !matches!(p, TodoPublishment::Public);
+        if is_private(todo.is_public()) || versions.iter().any(|v| is_private(v.is_public())) {
+            ctx.client().has_permission(Permission::ReadPrivateTodo)?;
+        }
+
+        let mut previous = None;
+        let mut res = Vec::new();
+
+        for version in versions {
+            res.push(TodoVersionDto {
+                changes: previous
+                    .map(|p| version.changes_from(&p))
+                    .unwrap_or_default(),
+                version: version.clone(),
+            });
+            previous = Some(version);
+        }
+
+        Ok(res)
+    }
+
+    // 通常の更新と同じ検証を経て、その版の内容に戻す
+    pub async fn revert(
+        &self,
+        id: TodoId,
+        version: u32,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::RevertTodo)?;
+
+        let current = self.get_alive(id.clone()).await?;
+
+        let version = self
+            .repositories
+            .todo_repository()
+            .get_version(id.clone(), version)
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .ok_or(TodoUseCaseError::TodoVersionNotFound(id, version))?;
+
+        self.update(
+            version.revert_command(&current),
+            TodoUpdateOptions::default(),
+            ctx,
+        )
+        .await
+    }
+
+    pub async fn delete(&self, id: TodoId, ctx: &impl ContextProvider) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::DeleteTodo)?;
+
+        let todo = self.get_with_deleted(id.clone()).await?;
+
+        if todo.is_deleted() {
+            return Err(TodoUseCaseError::TodoNotFound(id).into());
+        }
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .delete_by_id(
+                id,
+                audit::actor(Permission::DeleteTodo, ctx),
+                webhook::todo_deleted,
+            )
+            .await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_deleted(
+        &self,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoDto>, ErrorCode> {
+        // NOTE: ゴミ箱の中身は削除できる者にしか見せない
+        ctx.client().has_permission(Permission::DeleteTodo)?;
+
+        let res = self.repositories.todo_repository().list_deleted().await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(ErrorCode::from)?
+            .into_iter()
+            .map(|d| TodoDto::try_from_with_permission(d, ctx.client()))
+            .collect()
+    }
+
+    pub async fn restore(&self, id: TodoId, ctx: &impl ContextProvider) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::DeleteTodo)?;
+
+        let todo = self.get_with_deleted(id.clone()).await?;
+
+        if !todo.is_deleted() {
+            return Err(TodoUseCaseError::TodoNotFound(id).into());
+        }
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .restore_by_id(
+                id,
+                audit::actor(Permission::DeleteTodo, ctx),
+                webhook::todo_updated,
+            )
+            .await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_occurrences(
+        &self,
+        from: DateTime,
+        to: DateTime,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<TodoOccurrenceEntryDto>, ErrorCode> {
+        ctx.client().has_permission(Permission::ReadTodo)?;
+
+        occurrence::validate_range(&from, &to)?;
+
+        let todos = self
+            .repositories
+            .todo_repository()
+            .list(TodoFilter::default(), PageRequest::default())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .into_items();
+
+        let mut entries = Vec::new();
+
+        // NOTE: 完了や中止したものの回は展開しない
+        for todo in todos.into_iter().filter(|t| t.is_alive()) {
+            let occurrences = occurrence::expand_todo(&todo, &from, &to);
+
+            if occurrences.is_empty() {
+                continue;
+            }
+
+            let dto = TodoDto::try_from_with_permission(todo, ctx.client())?;
+
+            entries.extend(
+                occurrences
+                    .into_iter()
+                    .map(|occurrence| TodoOccurrenceEntryDto {
+                        todo_id: dto.id,
+                        name: dto.name.clone(),
+                        is_public: dto.is_public,
+                        starts_at: occurrence.starts_at().clone(),
+                        ends_at: occurrence.ends_at().clone(),
+                    }),
+            );
+        }
+
+        entries.sort_by_key(|entry| (entry.starts_at.clone().value(), entry.todo_id));
+
+        Ok(entries)
+    }
+
+    pub async fn complete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence_start: DateTime,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::UpdateTodo)?;
+
+        let todo = self
+            .repositories
+            .todo_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(TodoUseCaseError::TodoRepositoryError)?
+            .filter(|t| !t.is_deleted())
+            .ok_or(TodoUseCaseError::TodoNotFound(id.clone()))?;
+
+        // NOTE: スケジュール上に存在しない回は完了にできない
+        let occurrence = todo.find_occurrence(&occurrence_start).ok_or(
+            TodoUseCaseError::TodoOccurrenceNotFound(id.clone(), occurrence_start),
+        )?;
+
+        let res = self
+            .repositories
+            .todo_repository()
+            .complete_occurrence(
+                id,
+                occurrence,
+                audit::actor(Permission::UpdateTodo, ctx),
+                webhook::todo_updated,
+            )
+            .await;
+
+        res.map_err(TodoUseCaseError::TodoRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn uncomplete_occurrence(
+        &self,
+        id: TodoId,
+        occurrence_start: DateTime,
         ctx: &impl ContextProvider,
     ) -> Result<(), ErrorCode> {
         ctx.client().has_permission(Permission::UpdateTodo)?;
 
-        let res = self.repositories.todo_repository().update(cmd).await;
+        self.get_alive(id.clone()).await?;
+
+        // NOTE: スケジュール変更で存在しなくなった回の完了も取り消せるよう、回の存在は検証しない
+        let res = self
+            .repositories
+            .todo_repository()
+            .uncomplete_occurrence(
+                id,
+                occurrence_start,
+                audit::actor(Permission::UpdateTodo, ctx),
+                webhook::todo_updated,
+            )
+            .await;
 
         res.map_err(TodoUseCaseError::TodoRepositoryError)
             .map_err(|e| e.into())
@@ -1,4 +1,5 @@
 use crate::{
+    audit,
     shared::{ConfigProvider, ContextProvider},
     user::{UserUseCase, UserUseCaseError},
 };
@@ -6,7 +7,7 @@ use crate::{
 use todoroki_domain::{
     entities::{
         client::Client,
-        user::{User, UserEmail, UserId},
+        user::{User, UserCalendarToken, UserEmail, UserId},
         user_auth::UserAuthToken,
     },
     repositories::{
@@ -14,7 +15,11 @@ use todoroki_domain::{
         user_auth::{UserAuthRepository, UserAuthRepositoryError},
         Repositories,
     },
-    value_objects::{error::ErrorCode, permission::Permission},
+    value_objects::{
+        datetime::{DateTime, Timezone},
+        error::ErrorCode,
+        permission::Permission,
+    },
 };
 
 use serde::{Deserialize, Serialize};
@@ -38,6 +43,17 @@ impl<R: Repositories> UserUseCase<R> {
         token: UserAuthToken,
         config: &impl ConfigProvider,
     ) -> Result<Client, ErrorCode> {
+        self.verify_with_expiry(token, config)
+            .await
+            .map(|(client, _)| client)
+    }
+
+    // 接続を保ったまま認証し続ける場合に、トークンの有効期限も返す
+    pub async fn verify_with_expiry(
+        &self,
+        token: UserAuthToken,
+        config: &impl ConfigProvider,
+    ) -> Result<(Client, DateTime), ErrorCode> {
         let header = decode_header(token.clone().value()).map_err(|_| {
             ErrorCode::from(UserUseCaseError::UserAuthTokenVerificationError(
                 "Failed to decode jwt header".to_string(),
@@ -91,6 +107,10 @@ impl<R: Repositories> UserUseCase<R> {
             ));
         }
 
+        let expires_at = DateTime::from_timestamp(data.claims.exp as i64).ok_or(
+            UserUseCaseError::UserAuthTokenVerificationError("Invalid expiration time".to_string()),
+        )?;
+
         let email = UserEmail::new(data.claims.email);
 
         let opt_user = self
@@ -101,10 +121,12 @@ impl<R: Repositories> UserUseCase<R> {
             .map_err(UserUseCaseError::UserRepositoryError)
             .map_err(ErrorCode::from)?;
 
-        match opt_user {
-            Some(u) => Ok(Client::User(u)),
-            None => Ok(Client::Unregistered(email)),
-        }
+        let client = match opt_user {
+            Some(u) => Client::User(u),
+            None => Client::Unregistered(email),
+        };
+
+        Ok((client, expires_at))
     }
 
     pub async fn create(
@@ -112,10 +134,15 @@ impl<R: Repositories> UserUseCase<R> {
         user: User,
         ctx: &impl ContextProvider,
     ) -> Result<UserId, ErrorCode> {
-        ctx.client()
-            .has_permission(Permission::CreateUser(user.clone()))?;
+        let permission = Permission::CreateUser(user.clone());
 
-        let res = self.repositories.user_repository().create(user).await;
+        ctx.client().has_permission(permission.clone())?;
+
+        let res = self
+            .repositories
+            .user_repository()
+            .create(user, audit::actor(permission, ctx))
+            .await;
 
         res.map_err(UserUseCaseError::UserRepositoryError)
             .map_err(|e| e.into())
@@ -139,4 +166,82 @@ impl<R: Repositories> UserUseCase<R> {
             .ok_or(UserUseCaseError::UserNotFound(id))
             .map_err(|e| e.into())
     }
+
+    // カレンダーの購読 URL 用のトークンを (再) 発行する。以前のトークンは無効になる
+    pub async fn issue_calendar_token(
+        &self,
+        ctx: &impl ContextProvider,
+    ) -> Result<UserCalendarToken, ErrorCode> {
+        let Client::User(user) = ctx.client().client() else {
+            return Err(ErrorCode::UserNotVerified);
+        };
+
+        let token = UserCalendarToken::generate();
+
+        self.repositories
+            .user_repository()
+            .update_calendar_token(user.id().clone(), Some(token.clone()))
+            .await
+            .map_err(UserUseCaseError::UserRepositoryError)
+            .map_err(ErrorCode::from)?;
+
+        Ok(token)
+    }
+
+    pub async fn revoke_calendar_token(&self, ctx: &impl ContextProvider) -> Result<(), ErrorCode> {
+        let Client::User(user) = ctx.client().client() else {
+            return Err(ErrorCode::UserNotVerified);
+        };
+
+        let res = self
+            .repositories
+            .user_repository()
+            .update_calendar_token(user.id().clone(), None)
+            .await;
+
+        res.map_err(UserUseCaseError::UserRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    // スケジュールの既定のタイムゾーンを変更する
+    pub async fn update_timezone(
+        &self,
+        timezone: Timezone,
+        ctx: &impl ContextProvider,
+    ) -> Result<(), ErrorCode> {
+        let Client::User(user) = ctx.client().client() else {
+            return Err(ErrorCode::UserNotVerified);
+        };
+
+        let permission = Permission::UpdateUser(user.clone());
+
+        ctx.client().has_permission(permission.clone())?;
+
+        let res = self
+            .repositories
+            .user_repository()
+            .update_timezone(user.id().clone(), timezone, audit::actor(permission, ctx))
+            .await;
+
+        res.map_err(UserUseCaseError::UserRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn verify_calendar_token(
+        &self,
+        token: UserCalendarToken,
+    ) -> Result<Client, ErrorCode> {
+        let user = self
+            .repositories
+            .user_repository()
+            .get_by_calendar_token(token)
+            .await
+            .map_err(UserUseCaseError::UserRepositoryError)
+            .map_err(ErrorCode::from)?
+            .ok_or(UserUseCaseError::UserAuthTokenVerificationError(
+                "invalid-calendar-token".to_string(),
+            ))?;
+
+        Ok(Client::User(user))
+    }
 }
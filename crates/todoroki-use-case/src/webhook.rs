@@ -0,0 +1,120 @@
+pub mod error;
+pub mod operations;
+
+use std::sync::Arc;
+use thiserror::Error;
+
+use todoroki_domain::{
+    entities::{
+        audit::Auditable,
+        doit::Doit,
+        todo::{Todo, TodoStatus},
+        webhook::{WebhookDeliveryId, WebhookEvent, WebhookEventType, WebhookId},
+    },
+    repositories::{
+        webhook::WebhookRepositoryError, webhook_sender::WebhookSenderRepositoryError, Repositories,
+    },
+};
+
+pub struct WebhookUseCase<R: Repositories> {
+    repositories: Arc<R>,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookUseCaseError {
+    #[error(transparent)]
+    WebhookRepositoryError(#[from] WebhookRepositoryError),
+    #[error(transparent)]
+    WebhookSenderRepositoryError(#[from] WebhookSenderRepositoryError),
+    #[error("Webhook Not Found: {0:?}")]
+    WebhookNotFound(WebhookId),
+    #[error("Webhook Delivery Not Found: {0:?}")]
+    WebhookDeliveryNotFound(WebhookDeliveryId),
+}
+
+impl<R: Repositories> WebhookUseCase<R> {
+    pub fn new(repositories: Arc<R>) -> Self {
+        Self { repositories }
+    }
+}
+
+// 変更と同じトランザクションで配送を積むため、リポジトリでの変更に渡す
+pub(crate) fn events<T: Auditable>(event_type: WebhookEventType, entity: &T) -> Vec<WebhookEvent> {
+    vec![WebhookEvent::generate(event_type, entity)]
+}
+
+pub(crate) fn todo_updated(before: &Todo, after: &Todo) -> Vec<WebhookEvent> {
+    let mut events = events(WebhookEventType::TodoUpdated, after);
+
+    // NOTE: 完了になった場合は todo.completed も送る
+    if *before.status() != TodoStatus::Completed && *after.status() == TodoStatus::Completed {
+        events.push(WebhookEvent::generate(
+            WebhookEventType::TodoCompleted,
+            after,
+        ));
+    }
+
+    events
+}
+
+pub(crate) fn todo_deleted(_: &Todo, after: &Todo) -> Vec<WebhookEvent> {
+    events(WebhookEventType::TodoDeleted, after)
+}
+
+pub(crate) fn doit_updated(_: &Doit, after: &Doit) -> Vec<WebhookEvent> {
+    events(WebhookEventType::DoitUpdated, after)
+}
+
+pub(crate) fn doit_accepted(_: &Doit, after: &Doit) -> Vec<WebhookEvent> {
+    events(WebhookEventType::DoitAccepted, after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todoroki_domain::{
+        entities::todo::{TodoDescription, TodoName, TodoPublishment},
+        value_objects::datetime::DateTime,
+    };
+
+    fn todo(status: TodoStatus) -> Todo {
+        Todo::generate(
+            TodoName::new("todo".to_string()),
+            TodoDescription::new(String::new()),
+            TodoPublishment::Public,
+            None,
+            vec![],
+            vec![],
+            None,
+        )
+        .with_status(status, DateTime::now())
+    }
+
+    fn event_types(events: &[WebhookEvent]) -> Vec<WebhookEventType> {
+        events.iter().map(|e| *e.event_type()).collect()
+    }
+
+    #[test]
+    fn completed_event_is_sent_only_when_newly_completed() {
+        let cases = [
+            (TodoStatus::OnProgress, TodoStatus::Completed, true),
+            (TodoStatus::NotStarted, TodoStatus::Completed, true),
+            (TodoStatus::Completed, TodoStatus::Completed, false),
+            (TodoStatus::NotStarted, TodoStatus::OnProgress, false),
+            (TodoStatus::Completed, TodoStatus::OnProgress, false),
+        ];
+
+        for (before, after, completed) in cases {
+            let mut expected = vec![WebhookEventType::TodoUpdated];
+            if completed {
+                expected.push(WebhookEventType::TodoCompleted);
+            }
+
+            assert_eq!(
+                event_types(&todo_updated(&todo(before), &todo(after))),
+                expected,
+                "{before:?} -> {after:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use todoroki_domain::value_objects::error::ErrorCode;
+
+use crate::webhook::WebhookUseCaseError;
+
+impl From<WebhookUseCaseError> for ErrorCode {
+    fn from(value: WebhookUseCaseError) -> Self {
+        match value {
+            WebhookUseCaseError::WebhookRepositoryError(e) => {
+                Self::WebhookRepositoryInternalError(e)
+            }
+            WebhookUseCaseError::WebhookSenderRepositoryError(e) => {
+                Self::WebhookSenderRepositoryInternalError(e)
+            }
+            WebhookUseCaseError::WebhookNotFound(id) => Self::WebhookNotFound(id),
+            WebhookUseCaseError::WebhookDeliveryNotFound(id) => Self::WebhookDeliveryNotFound(id),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{
+    shared::ContextProvider,
+    webhook::{WebhookUseCase, WebhookUseCaseError},
+};
+
+use todoroki_domain::{
+    entities::webhook::{
+        Webhook, WebhookDelivery, WebhookDeliveryAttempt, WebhookDeliveryId, WebhookId,
+    },
+    repositories::{
+        pagination::{Page, PageRequest},
+        webhook::{WebhookDeliverySortKey, WebhookRepository},
+        webhook_sender::WebhookSenderRepository,
+        Repositories,
+    },
+    value_objects::{datetime::DateTime, error::ErrorCode, permission::Permission},
+};
+
+impl<R: Repositories> WebhookUseCase<R> {
+    pub async fn create(
+        &self,
+        webhook: Webhook,
+        ctx: &impl ContextProvider,
+    ) -> Result<WebhookId, ErrorCode> {
+        ctx.client().has_permission(Permission::ManageWebhook)?;
+
+        let res = self.repositories.webhook_repository().create(webhook).await;
+
+        res.map_err(WebhookUseCaseError::WebhookRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list(&self, ctx: &impl ContextProvider) -> Result<Vec<Webhook>, ErrorCode> {
+        ctx.client().has_permission(Permission::ManageWebhook)?;
+
+        let res = self.repositories.webhook_repository().list().await;
+
+        res.map_err(WebhookUseCaseError::WebhookRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn delete(&self, id: WebhookId, ctx: &impl ContextProvider) -> Result<(), ErrorCode> {
+        ctx.client().has_permission(Permission::ManageWebhook)?;
+
+        self.get(id.clone()).await?;
+
+        let res = self
+            .repositories
+            .webhook_repository()
+            .delete_by_id(id)
+            .await;
+
+        res.map_err(WebhookUseCaseError::WebhookRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_deliveries(
+        &self,
+        id: WebhookId,
+        page: PageRequest<WebhookDeliverySortKey>,
+        ctx: &impl ContextProvider,
+    ) -> Result<Page<WebhookDelivery>, ErrorCode> {
+        ctx.client().has_permission(Permission::ManageWebhook)?;
+
+        self.get(id.clone()).await?;
+
+        let res = self
+            .repositories
+            .webhook_repository()
+            .list_deliveries(id, page)
+            .await;
+
+        res.map_err(WebhookUseCaseError::WebhookRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    pub async fn list_attempts(
+        &self,
+        id: WebhookId,
+        delivery_id: WebhookDeliveryId,
+        ctx: &impl ContextProvider,
+    ) -> Result<Vec<WebhookDeliveryAttempt>, ErrorCode> {
+        ctx.client().has_permission(Permission::ManageWebhook)?;
+
+        self.repositories
+            .webhook_repository()
+            .get_delivery_by_id(delivery_id.clone())
+            .await
+            .map_err(WebhookUseCaseError::WebhookRepositoryError)?
+            .filter(|d| *d.webhook_id() == id)
+            .ok_or(WebhookUseCaseError::WebhookDeliveryNotFound(
+                delivery_id.clone(),
+            ))?;
+
+        let res = self
+            .repositories
+            .webhook_repository()
+            .list_attempts(delivery_id)
+            .await;
+
+        res.map_err(WebhookUseCaseError::WebhookRepositoryError)
+            .map_err(|e| e.into())
+    }
+
+    // 送信時刻を過ぎた配送を最大 limit 件送信し、送信した件数を返す
+    // NOTE: 利用者の操作ではなく、定期的に実行されるタスクから呼ばれるため権限は確認しない
+    pub async fn deliver_due(&self, limit: u32) -> Result<usize, ErrorCode> {
+        let mut count = 0;
+
+        // NOTE: まとめて取り出すと送信中に lease が切れて他から取り出されるため、1件ずつ取り出してすぐに送信する
+        while count < limit as usize {
+            let lease_until = WebhookDelivery::lease_until(&DateTime::now());
+
+            let Some((webhook, delivery)) = self
+                .repositories
+                .webhook_repository()
+                .claim_due_delivery(lease_until)
+                .await
+                .map_err(WebhookUseCaseError::WebhookRepositoryError)?
+            else {
+                break;
+            };
+
+            count += 1;
+
+            let attempt = self
+                .repositories
+                .webhook_sender_repository()
+                .send(&webhook, &delivery)
+                .await
+                .map_err(WebhookUseCaseError::WebhookSenderRepositoryError)?;
+
+            let delivery = delivery.attempted(&attempt);
+
+            self.repositories
+                .webhook_repository()
+                .record_attempt(delivery, attempt)
+                .await
+                .map_err(WebhookUseCaseError::WebhookRepositoryError)?;
+        }
+
+        Ok(count)
+    }
+
+    async fn get(&self, id: WebhookId) -> Result<Webhook, ErrorCode> {
+        let webhook = self
+            .repositories
+            .webhook_repository()
+            .get_by_id(id.clone())
+            .await
+            .map_err(WebhookUseCaseError::WebhookRepositoryError)?
+            .ok_or(WebhookUseCaseError::WebhookNotFound(id))?;
+
+        Ok(webhook)
+    }
+}
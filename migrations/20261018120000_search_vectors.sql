@@ -0,0 +1,47 @@
+-- Add migration script here
+
+/*
+// search_vector: name と description
+// public_search_vector: 非公開のものは alternative_name のみ
+*/
+ALTER TABLE todos
+  ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', name), 'A') ||
+    setweight(to_tsvector('simple', description), 'B')
+  ) STORED,
+  ADD COLUMN public_search_vector TSVECTOR GENERATED ALWAYS AS (
+    CASE WHEN is_public THEN
+      setweight(to_tsvector('simple', name), 'A') ||
+      setweight(to_tsvector('simple', description), 'B')
+    ELSE
+      setweight(to_tsvector('simple', COALESCE(alternative_name, '')), 'A')
+    END
+  ) STORED;
+
+CREATE INDEX todos_search_vector_idx ON todos USING GIN (search_vector);
+CREATE INDEX todos_public_search_vector_idx ON todos USING GIN (public_search_vector);
+
+ALTER TABLE doits
+  ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', name), 'A') ||
+    setweight(to_tsvector('simple', description), 'B')
+  ) STORED,
+  ADD COLUMN public_search_vector TSVECTOR GENERATED ALWAYS AS (
+    CASE WHEN is_public THEN
+      setweight(to_tsvector('simple', name), 'A') ||
+      setweight(to_tsvector('simple', description), 'B')
+    ELSE
+      setweight(to_tsvector('simple', COALESCE(alternative_name, '')), 'A')
+    END
+  ) STORED;
+
+CREATE INDEX doits_search_vector_idx ON doits USING GIN (search_vector);
+CREATE INDEX doits_public_search_vector_idx ON doits USING GIN (public_search_vector);
+
+ALTER TABLE labels
+  ADD COLUMN search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', name), 'A') ||
+    setweight(to_tsvector('simple', description), 'B')
+  ) STORED;
+
+CREATE INDEX labels_search_vector_idx ON labels USING GIN (search_vector);
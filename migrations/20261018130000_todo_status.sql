@@ -0,0 +1,37 @@
+-- Add migration script here
+
+CREATE TYPE todo_status AS ENUM ('not_started', 'on_progress', 'paused', 'completed', 'cancelled');
+
+ALTER TABLE todos ADD COLUMN status todo_status NOT NULL DEFAULT 'not_started';
+
+-- NOTE: updated_at を明示的に指定して、既存の Todo の updated_at を変えないようにする
+UPDATE todos
+SET
+  status = CASE
+    WHEN ended_at IS NOT NULL THEN 'completed'::todo_status
+    WHEN started_at IS NOT NULL THEN 'on_progress'::todo_status
+    ELSE 'not_started'::todo_status
+  END,
+  updated_at = updated_at;
+
+CREATE TABLE todo_status_transitions (
+  id BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+  from_status todo_status NOT NULL,
+  to_status todo_status NOT NULL,
+  transitioned_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX todo_status_transitions_todo_id_idx ON todo_status_transitions (todo_id, transitioned_at);
+
+-- started_at と ended_at から分かる範囲で履歴を埋める
+INSERT INTO todo_status_transitions (todo_id, from_status, to_status, transitioned_at)
+SELECT id, 'not_started', 'on_progress', started_at FROM todos WHERE started_at IS NOT NULL;
+
+INSERT INTO todo_status_transitions (todo_id, from_status, to_status, transitioned_at)
+SELECT
+  id,
+  CASE WHEN started_at IS NOT NULL THEN 'on_progress'::todo_status ELSE 'not_started'::todo_status END,
+  'completed',
+  ended_at
+FROM todos WHERE ended_at IS NOT NULL;
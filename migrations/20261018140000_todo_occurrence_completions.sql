@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TABLE todo_occurrence_completions (
+    todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+    occurrence_start TIMESTAMPTZ NOT NULL,
+    -- 直近の回の完了状況だけを読み込むために終了日時も持っておく
+    occurrence_end TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (todo_id, occurrence_start)
+);
+
+CREATE INDEX idx_todo_occurrence_completions_todo_id_occurrence_end
+    ON todo_occurrence_completions (todo_id, occurrence_end);
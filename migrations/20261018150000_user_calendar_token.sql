@@ -0,0 +1,3 @@
+-- Add migration script here
+-- カレンダー購読用のトークンはハッシュのみを保存する
+ALTER TABLE users ADD COLUMN calendar_token_hash BYTEA UNIQUE DEFAULT NULL;
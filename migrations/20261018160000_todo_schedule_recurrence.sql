@@ -0,0 +1,8 @@
+-- Add migration script here
+
+ALTER TYPE todo_schedule_interval ADD VALUE 'recurrence';
+
+-- NOTE: interval が recurrence のものだけが rrule を持つ
+ALTER TABLE todo_schedules
+  ADD COLUMN rrule TEXT,
+  ADD COLUMN exdates TIMESTAMPTZ[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,6 @@
+-- Add migration script here
+
+-- NOTE: これまでの時刻はすべて UTC として保存されている
+ALTER TABLE todo_schedules ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
+
+ALTER TABLE users ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
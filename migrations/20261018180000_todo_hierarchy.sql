@@ -0,0 +1,5 @@
+-- Add migration script here
+
+ALTER TABLE todos ADD COLUMN parent_id UUID REFERENCES todos(id) ON DELETE SET NULL;
+
+CREATE INDEX todos_parent_id_idx ON todos(parent_id);
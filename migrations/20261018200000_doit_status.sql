@@ -0,0 +1,19 @@
+-- Add migration script here
+
+CREATE TYPE doit_status AS ENUM ('pending', 'on_hold', 'accepted', 'rejected', 'duplicate');
+
+ALTER TABLE doits
+  ADD COLUMN status doit_status NOT NULL DEFAULT 'pending',
+  ADD COLUMN status_reason TEXT DEFAULT NULL,
+  ADD COLUMN duplicate_of UUID DEFAULT NULL REFERENCES doits(id) ON DELETE SET NULL,
+  ADD COLUMN reviewed_at TIMESTAMPTZ DEFAULT NULL;
+
+-- NOTE: updated_at を明示的に指定して、既存の Doit の updated_at を変えないようにする
+UPDATE doits
+SET
+  status = 'accepted',
+  reviewed_at = updated_at,
+  updated_at = updated_at
+WHERE affects_to IS NOT NULL;
+
+CREATE INDEX doits_status_idx ON doits (status);
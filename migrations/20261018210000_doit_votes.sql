@@ -0,0 +1,10 @@
+-- Add migration script here
+
+CREATE TABLE doit_votes (
+  doit_id UUID NOT NULL REFERENCES doits(id) ON DELETE CASCADE,
+  user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (doit_id, user_id)
+);
+
+CREATE INDEX doit_votes_user_id_idx ON doit_votes (user_id);
@@ -0,0 +1,31 @@
+-- Add migration script here
+
+CREATE TABLE comments (
+  id UUID PRIMARY KEY NOT NULL,
+  todo_id UUID DEFAULT NULL REFERENCES todos(id) ON DELETE CASCADE,
+  doit_id UUID DEFAULT NULL REFERENCES doits(id) ON DELETE CASCADE,
+  parent_id UUID DEFAULT NULL REFERENCES comments(id) ON DELETE CASCADE,
+  body TEXT NOT NULL,
+  created_by UUID NOT NULL REFERENCES users(id),
+  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  deleted_at TIMESTAMPTZ DEFAULT NULL,
+  -- NOTE: todo と doit のどちらか一方にだけ付く
+  CHECK ((todo_id IS NULL) <> (doit_id IS NULL))
+);
+
+CREATE INDEX comments_todo_id_idx ON comments (todo_id, created_at);
+CREATE INDEX comments_doit_id_idx ON comments (doit_id, created_at);
+
+/*
+// TRIGGERS (comments)
+*/
+CREATE TRIGGER refresh_comments_updated_at_step1
+    BEFORE UPDATE ON comments FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step1();
+CREATE TRIGGER refresh_comments_updated_at_step2
+    BEFORE UPDATE OF updated_at ON comments FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step2();
+CREATE TRIGGER refresh_comments_updated_at_step3
+    BEFORE UPDATE ON comments FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step3();
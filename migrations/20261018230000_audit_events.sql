@@ -0,0 +1,35 @@
+-- Add migration script here
+
+CREATE TYPE audit_entity AS ENUM ('todo', 'doit', 'label', 'user');
+CREATE TYPE audit_action AS ENUM ('create', 'update', 'delete', 'status_change');
+
+CREATE TABLE audit_events (
+  id UUID PRIMARY KEY NOT NULL,
+  entity audit_entity NOT NULL,
+  entity_id UUID NOT NULL,
+  action audit_action NOT NULL,
+  -- NOTE: ユーザーを削除しても記録は残すため、外部キーにしない
+  actor_id UUID DEFAULT NULL,
+  permission TEXT NOT NULL,
+  before JSONB DEFAULT NULL,
+  after JSONB DEFAULT NULL,
+  occurred_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX audit_events_occurred_at_idx ON audit_events (occurred_at, id);
+CREATE INDEX audit_events_entity_idx ON audit_events (entity, entity_id, occurred_at);
+CREATE INDEX audit_events_actor_id_idx ON audit_events (actor_id, occurred_at);
+
+/*
+// TRIGGERS (audit_events)
+*/
+CREATE FUNCTION reject_audit_events_modification() RETURNS trigger AS
+$$
+BEGIN
+  RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reject_audit_events_modification
+    BEFORE UPDATE OR DELETE ON audit_events FOR EACH ROW
+    EXECUTE PROCEDURE reject_audit_events_modification();
@@ -0,0 +1,17 @@
+-- Add migration script here
+
+CREATE TABLE todo_versions (
+  todo_id UUID NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+  version INTEGER NOT NULL,
+  name TEXT NOT NULL,
+  description TEXT NOT NULL,
+  is_public BOOLEAN NOT NULL,
+  alternative_name TEXT DEFAULT NULL,
+  deadlined_at TIMESTAMPTZ DEFAULT NULL,
+  label_ids UUID[] NOT NULL DEFAULT '{}',
+  -- NOTE: todo_schedules の行と同じ形の JSON の配列
+  schedules JSONB NOT NULL DEFAULT '[]',
+  created_by UUID DEFAULT NULL REFERENCES users(id) ON DELETE SET NULL,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (todo_id, version)
+);
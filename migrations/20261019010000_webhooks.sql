@@ -0,0 +1,66 @@
+-- Add migration script here
+
+CREATE TABLE webhooks (
+  id UUID PRIMARY KEY NOT NULL,
+  url TEXT NOT NULL,
+  secret TEXT NOT NULL,
+  -- NOTE: todo.created などのイベントの種類
+  event_types TEXT[] NOT NULL DEFAULT '{}',
+  created_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TYPE webhook_delivery_status AS ENUM ('pending', 'succeeded', 'failed');
+
+CREATE TABLE webhook_deliveries (
+  id UUID PRIMARY KEY NOT NULL,
+  webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+  event_id UUID NOT NULL,
+  event_type TEXT NOT NULL,
+  data JSONB NOT NULL,
+  occurred_at TIMESTAMPTZ NOT NULL,
+  status webhook_delivery_status NOT NULL DEFAULT 'pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX webhook_deliveries_due_idx ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
+CREATE INDEX webhook_deliveries_webhook_id_idx ON webhook_deliveries (webhook_id, created_at, id);
+
+CREATE TABLE webhook_delivery_attempts (
+  delivery_id UUID NOT NULL REFERENCES webhook_deliveries(id) ON DELETE CASCADE,
+  attempt INTEGER NOT NULL,
+  status_code INTEGER DEFAULT NULL,
+  error TEXT DEFAULT NULL,
+  attempted_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (delivery_id, attempt)
+);
+
+/*
+// TRIGGERS (webhooks)
+*/
+CREATE TRIGGER refresh_webhooks_updated_at_step1
+    BEFORE UPDATE ON webhooks FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step1();
+CREATE TRIGGER refresh_webhooks_updated_at_step2
+    BEFORE UPDATE OF updated_at ON webhooks FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step2();
+CREATE TRIGGER refresh_webhooks_updated_at_step3
+    BEFORE UPDATE ON webhooks FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step3();
+
+/*
+// TRIGGERS (webhook_deliveries)
+*/
+CREATE TRIGGER refresh_webhook_deliveries_updated_at_step1
+    BEFORE UPDATE ON webhook_deliveries FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step1();
+CREATE TRIGGER refresh_webhook_deliveries_updated_at_step2
+    BEFORE UPDATE OF updated_at ON webhook_deliveries FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step2();
+CREATE TRIGGER refresh_webhook_deliveries_updated_at_step3
+    BEFORE UPDATE ON webhook_deliveries FOR EACH ROW
+    EXECUTE PROCEDURE refresh_updated_at_step3();
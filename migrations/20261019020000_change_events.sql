@@ -0,0 +1,4 @@
+-- Add migration script here
+
+-- 変更の通知に付ける ID。どのプロセスが書き込んでも、受け取ったすべてのプロセスで同じ ID になる
+CREATE SEQUENCE change_event_id_seq;